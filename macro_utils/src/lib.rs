@@ -2,7 +2,7 @@ use anyhow::anyhow;
 use lazy_static::lazy_static;
 use serde::Deserialize;
 use starknet_providers::{jsonrpc::HttpTransport, JsonRpcClient, Provider};
-use std::{fs::File, io::Read};
+use std::{collections::BTreeMap, fs::File, io::Read};
 use syn::{Expr, Lit};
 use tokio::runtime;
 use url::Url;
@@ -12,6 +12,20 @@ pub struct TestConfig {
     pub pathfinder: String,
     pub deoxys: String,
     pub juno: String,
+    #[serde(default)]
+    pub devnet: Option<DevnetConfig>,
+}
+
+///
+/// A local devnet-style network for the write tests, which need funded accounts.
+///
+#[derive(PartialEq, Debug, Deserialize)]
+pub struct DevnetConfig {
+    /// Url of the starknet-devnet instance, which predeploys accounts and mints fee tokens.
+    pub url: String,
+    /// Other nodes following the devnet, by name, whose write API is tested as well.
+    #[serde(default)]
+    pub nodes: BTreeMap<String, String>,
 }
 
 impl TestConfig {
@@ -0,0 +1 @@
+%lang starknet
@@ -0,0 +1,219 @@
+use std::time::Duration;
+
+use anyhow::anyhow;
+use starknet_accounts::ExecutionEncoding;
+use starknet_core::types::{
+    BroadcastedDeployAccountTransaction, FieldElement, PriceUnit, TransactionExecutionStatus,
+    TransactionStatus,
+};
+use starknet_core::utils::get_contract_address;
+use starknet_crypto::poseidon_hash_many;
+use starknet_providers::Provider;
+use starknet_signers::SigningKey;
+
+use crate::builder::{NetworkProfile, TestAccount, TransactionBuilder};
+use crate::constants::mainnet;
+use crate::devnet;
+use crate::v3::sign;
+
+///
+/// Account contracts which can be deployed counterfactually.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccountKind {
+    /// OpenZeppelin account v0.8.1, owned by a single Stark key.
+    OpenZeppelin,
+    /// Argent X account, with an owner key and no guardian.
+    Argent,
+    /// Braavos account v1.0.0, deployed from its base class with the implementation passed as
+    /// auxiliary data in the signature.
+    Braavos,
+}
+
+impl AccountKind {
+    ///
+    /// The class a deploy account transaction uses for this kind of account.
+    ///
+    pub fn class_hash(self) -> FieldElement {
+        let class_hash = match self {
+            AccountKind::OpenZeppelin => mainnet::contract::CLASS_OZ_ACCOUNT,
+            AccountKind::Argent => mainnet::contract::CLASS_ARGENT_ACCOUNT,
+            AccountKind::Braavos => mainnet::contract::CLASS_BRAAVOS_BASE_ACCOUNT,
+        };
+        FieldElement::from_hex_be(class_hash).unwrap()
+    }
+
+    pub fn constructor_calldata(self, public_key: FieldElement) -> Vec<FieldElement> {
+        match self {
+            AccountKind::OpenZeppelin | AccountKind::Braavos => vec![public_key],
+            AccountKind::Argent => vec![public_key, FieldElement::ZERO],
+        }
+    }
+}
+
+///
+/// An account whose address is known before it is deployed, as it only depends on its class,
+/// salt and constructor calldata.
+///
+#[derive(Clone, Debug)]
+pub struct CounterfactualAccount {
+    pub kind: AccountKind,
+    pub signing_key: SigningKey,
+    pub salt: FieldElement,
+}
+
+impl CounterfactualAccount {
+    pub fn new(kind: AccountKind, private_key: &str, salt: FieldElement) -> Self {
+        Self {
+            kind,
+            signing_key: SigningKey::from_secret_scalar(
+                FieldElement::from_hex_be(private_key).expect("Invalid private key"),
+            ),
+            salt,
+        }
+    }
+
+    ///
+    /// Generates an account with a random key and salt.
+    ///
+    pub fn generate(kind: AccountKind) -> Self {
+        Self {
+            kind,
+            signing_key: SigningKey::from_random(),
+            salt: FieldElement::from(rand::random::<u128>()),
+        }
+    }
+
+    pub fn public_key(&self) -> FieldElement {
+        self.signing_key.verifying_key().scalar()
+    }
+
+    pub fn constructor_calldata(&self) -> Vec<FieldElement> {
+        self.kind.constructor_calldata(self.public_key())
+    }
+
+    ///
+    /// The address the account is deployed to.
+    ///
+    pub fn address(&self) -> FieldElement {
+        get_contract_address(
+            self.salt,
+            self.kind.class_hash(),
+            &self.constructor_calldata(),
+            FieldElement::ZERO,
+        )
+    }
+
+    ///
+    /// The account once deployed, to build its transactions.
+    ///
+    pub fn test_account(&self) -> TestAccount {
+        TestAccount {
+            address: self.address(),
+            signing_key: Some(self.signing_key.clone()),
+            encoding: ExecutionEncoding::New,
+        }
+    }
+
+    ///
+    /// Starts a transaction builder for the account, on the network of `profile`.
+    ///
+    pub fn transaction_builder(&self, profile: NetworkProfile) -> TransactionBuilder {
+        TransactionBuilder::new(profile, self.test_account())
+    }
+
+    ///
+    /// Builds the transaction deploying the account, with the nonce, fees and mutations of
+    /// `builder`. The builder should come from [`Self::transaction_builder`], so that the
+    /// transaction is signed by the account key.
+    ///
+    pub fn deploy_transaction(
+        &self,
+        builder: &TransactionBuilder,
+    ) -> BroadcastedDeployAccountTransaction {
+        let mut tx = builder.build_deploy_account(
+            self.kind.class_hash(),
+            self.salt,
+            self.constructor_calldata(),
+        );
+
+        if self.kind == AccountKind::Braavos {
+            let signature = match &mut tx {
+                BroadcastedDeployAccountTransaction::V1(tx) => &mut tx.signature,
+                BroadcastedDeployAccountTransaction::V3(tx) => &mut tx.signature,
+            };
+            signature.extend(self.braavos_aux_data(builder.profile().chain_id));
+        }
+
+        tx
+    }
+
+    ///
+    /// Braavos base accounts upgrade to the implementation class given after the transaction
+    /// signature, followed by the signer settings, the chain id and a signature over them.
+    /// No secp256r1 signer, multisig or withdrawal limit is set.
+    ///
+    fn braavos_aux_data(&self, chain_id: FieldElement) -> Vec<FieldElement> {
+        let mut aux_data =
+            vec![FieldElement::from_hex_be(mainnet::contract::CLASS_BRAAVOS_ACCOUNT).unwrap()];
+        aux_data.extend([FieldElement::ZERO; 9]);
+        aux_data.push(chain_id);
+
+        let aux_signature = sign(&self.signing_key, poseidon_hash_many(&aux_data));
+        aux_data.extend(aux_signature);
+        aux_data
+    }
+
+    ///
+    /// Runs the whole counterfactual flow against a devnet: mints `amount` to the account
+    /// address in the unit of the transaction fees, sends the deploy account transaction and
+    /// waits for it to succeed. Returns the hash of the deploy account transaction.
+    ///
+    /// The class of the account must be declared on the devnet. A plain starknet-devnet only
+    /// declares the OpenZeppelin account class, so Argent and Braavos accounts need a devnet
+    /// started with `--fork-network` on a network where their classes are declared.
+    ///
+    pub async fn deploy_on_devnet<P: Provider + Sync>(
+        &self,
+        provider: &P,
+        devnet_url: &str,
+        builder: &TransactionBuilder,
+        amount: u128,
+    ) -> anyhow::Result<FieldElement> {
+        let tx = self.deploy_transaction(builder);
+        let unit = match tx {
+            BroadcastedDeployAccountTransaction::V1(_) => PriceUnit::Wei,
+            BroadcastedDeployAccountTransaction::V3(_) => PriceUnit::Fri,
+        };
+        devnet::mint(devnet_url, self.address(), amount, unit).await?;
+
+        let result = provider
+            .add_deploy_account_transaction(tx)
+            .await
+            .map_err(|e| anyhow!("Failed to send deploy account transaction: {e}"))?;
+        if result.contract_address != self.address() {
+            return Err(anyhow!(
+                "Account deployed to {:#x} instead of {:#x}",
+                result.contract_address,
+                self.address()
+            ));
+        }
+
+        let status = devnet::wait_for_transaction(
+            provider,
+            result.transaction_hash,
+            Duration::from_secs(60),
+        )
+        .await?;
+        match status {
+            TransactionStatus::AcceptedOnL2(TransactionExecutionStatus::Succeeded)
+            | TransactionStatus::AcceptedOnL1(TransactionExecutionStatus::Succeeded) => {
+                Ok(result.transaction_hash)
+            }
+            status => Err(anyhow!(
+                "Deploy account transaction {:#x} ended as {status:?}",
+                result.transaction_hash
+            )),
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use std::collections::HashSet;
+
+use anyhow::anyhow;
+use serde_json::{json, Value};
+
+use crate::cases::RequestCase;
+use crate::conformance::post_raw;
+use crate::json_path;
+use crate::snapshot::{canonicalize, first_difference};
+use crate::transport::corpus::CorpusEntry;
+
+///
+/// Builds the request cases of every distinct call of a corpus, in recording order.
+///
+pub fn cases_from_corpus(path: &str) -> anyhow::Result<Vec<RequestCase>> {
+    let mut seen = HashSet::new();
+    let mut cases = vec![];
+
+    for (index, line) in std::fs::read_to_string(path)?.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let entry: CorpusEntry = serde_json::from_str(line)
+            .map_err(|e| anyhow!("Invalid corpus entry at {path}:{}: {e}", index + 1))?;
+        if seen.insert((entry.method.clone(), entry.params.to_string())) {
+            cases.push(RequestCase {
+                name: None,
+                method: entry.method,
+                params: entry.params,
+                ignore: vec![],
+                expected_error: None,
+                nodes: None,
+            });
+        }
+    }
+
+    Ok(cases)
+}
+
+///
+/// Builds the JSON-RPC call of a case, identified by its index.
+///
+fn call(index: usize, case: &RequestCase) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": index,
+        "method": case.method,
+        "params": case.params,
+    })
+}
+
+///
+/// Keeps the `result` or `error` of a response, canonicalized and stripped of ignored paths.
+///
+fn outcome(response: &Value, case: &RequestCase) -> anyhow::Result<Value> {
+    let mut outcome = canonicalize(&json!({
+        "result": response.get("result"),
+        "error": response.get("error"),
+    }));
+    for path in &case.ignore {
+        json_path::remove(&mut outcome, path)?;
+    }
+
+    Ok(outcome)
+}
+
+///
+/// Compares the responses of individual calls with the response of the same calls sent as a
+/// single batch, in which responses may come in any order. Returns one message per mismatch.
+///
+pub fn compare_batch(cases: &[RequestCase], singles: &[Value], batch: &Value) -> Vec<String> {
+    let Some(responses) = batch.as_array() else {
+        return vec![format!("Expected an array of responses, got {batch}")];
+    };
+
+    let mut failures = vec![];
+    let mut seen = HashSet::new();
+
+    for response in responses {
+        let id = response.get("id").and_then(Value::as_u64);
+        match id.map(|id| id as usize).filter(|id| *id < cases.len()) {
+            Some(id) if !seen.insert(id) => {
+                failures.push(format!("Batch answers id {id} more than once"))
+            }
+            Some(_) => {}
+            None => failures.push(format!("Batch response with an unknown id: {response}")),
+        }
+    }
+
+    for (index, (case, single)) in cases.iter().zip(singles).enumerate() {
+        let name = case.name.clone().unwrap_or_else(|| case.method.clone());
+        let Some(batched) = responses
+            .iter()
+            .find(|response| response["id"] == json!(index))
+        else {
+            failures.push(format!("{name}: missing from the batch response"));
+            continue;
+        };
+
+        match (outcome(single, case), outcome(batched, case)) {
+            (Ok(single), Ok(batched)) => {
+                if let Some(path) = first_difference(&single, &batched, "$") {
+                    failures.push(format!("{name}: batch response differs at {path}"));
+                }
+            }
+            (Err(e), _) | (_, Err(e)) => failures.push(format!("{name}: {e}")),
+        }
+    }
+
+    failures
+}
+
+///
+/// Sends every case to a node once as individual calls and once as a single batch, and
+/// compares the responses. Returns one message per mismatch.
+///
+/// Both runs must see the same state, so cases should target historical blocks or ignore the
+/// fields which change with the chain head.
+///
+pub async fn check_batch_equivalence(
+    url: &str,
+    cases: &[RequestCase],
+) -> anyhow::Result<Vec<String>> {
+    let mut singles = vec![];
+    for (index, case) in cases.iter().enumerate() {
+        let response = post_raw(url, &call(index, case).to_string())
+            .await?
+            .ok_or_else(|| anyhow!("No response to {}", case.method))?;
+        singles.push(response);
+    }
+
+    let batch: Vec<Value> = cases
+        .iter()
+        .enumerate()
+        .map(|(index, case)| call(index, case))
+        .collect();
+    let batch = post_raw(url, &Value::Array(batch).to_string())
+        .await?
+        .ok_or_else(|| anyhow!("No response to the batch"))?;
+
+    Ok(compare_batch(cases, &singles, &batch))
+}
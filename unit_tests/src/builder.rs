@@ -0,0 +1,474 @@
+use anyhow::anyhow;
+use starknet_accounts::{Call, ExecutionEncoding};
+use starknet_core::chain_id;
+use starknet_core::types::{
+    BroadcastedDeclareTransaction, BroadcastedDeployAccountTransaction,
+    BroadcastedDeployAccountTransactionV1, BroadcastedInvokeTransaction,
+    BroadcastedInvokeTransactionV1, BroadcastedTransaction, FieldElement,
+};
+use starknet_core::utils::cairo_short_string_to_felt;
+use starknet_signers::SigningKey;
+
+use crate::constants::mainnet;
+use crate::declare::{self, ClassArtifact};
+use crate::generate_call;
+use crate::hash::transaction::{
+    declare_v1_hash, declare_v2_hash, declare_v3_hash, deploy_account_v1_hash,
+    deploy_account_v3_hash, invoke_v1_hash, invoke_v3_hash,
+};
+use crate::v3::{self, sign, V3Fees};
+
+///
+/// The network transactions are built for.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NetworkProfile {
+    pub name: &'static str,
+    pub chain_id: FieldElement,
+}
+
+impl NetworkProfile {
+    pub fn mainnet() -> Self {
+        Self {
+            name: "mainnet",
+            chain_id: chain_id::MAINNET,
+        }
+    }
+
+    pub fn sepolia() -> Self {
+        Self {
+            name: "sepolia",
+            chain_id: chain_id::SEPOLIA,
+        }
+    }
+}
+
+///
+/// The account sending transactions. Without a signing key, transactions are left unsigned,
+/// which is enough to estimate or simulate them without validation.
+///
+#[derive(Clone, Debug)]
+pub struct TestAccount {
+    pub address: FieldElement,
+    pub signing_key: Option<SigningKey>,
+    pub encoding: ExecutionEncoding,
+}
+
+impl TestAccount {
+    pub fn new(address: &str) -> Self {
+        Self {
+            address: FieldElement::from_hex_be(address).expect("Invalid account address"),
+            signing_key: None,
+            encoding: ExecutionEncoding::New,
+        }
+    }
+
+    pub fn signing_key(mut self, private_key: &str) -> Self {
+        self.signing_key = Some(SigningKey::from_secret_scalar(
+            FieldElement::from_hex_be(private_key).expect("Invalid private key"),
+        ));
+        self
+    }
+
+    pub fn legacy(mut self) -> Self {
+        self.encoding = ExecutionEncoding::Legacy;
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransactionVersion {
+    V1,
+    V3,
+}
+
+///
+/// A change making an otherwise valid transaction invalid.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mutation {
+    /// The signature does not match the transaction hash.
+    BadSignature,
+    /// The nonce was already used, or is far ahead of the account nonce at zero.
+    WrongNonce,
+    /// The max fee, or the L1 gas bounds, exceed any account balance while still fitting the
+    /// 128 bits of a fee.
+    OversizedFee,
+    /// The transaction is signed for another network.
+    WrongChainId,
+}
+
+///
+/// Builds valid and invalid transactions for the fee estimation, simulation and write tests.
+///
+/// ```ignore
+/// let transaction = TransactionBuilder::new(NetworkProfile::mainnet(), account)
+///     .calls(vec![generate_call(contract, "transfer", vec![1, 81])])
+///     .version(TransactionVersion::V3)
+///     .nonce(FieldElement::ONE)
+///     .mutate(Mutation::BadSignature)
+///     .build();
+/// ```
+///
+#[derive(Clone, Debug)]
+pub struct TransactionBuilder {
+    profile: NetworkProfile,
+    account: TestAccount,
+    calldata: Vec<FieldElement>,
+    version: TransactionVersion,
+    nonce: FieldElement,
+    max_fee: FieldElement,
+    fees: V3Fees,
+    is_query: bool,
+    mutations: Vec<Mutation>,
+}
+
+///
+/// Encodes calls into the calldata of the account `__execute__` entry point.
+///
+pub fn encode_calls(calls: &[Call], encoding: ExecutionEncoding) -> Vec<FieldElement> {
+    let mut calldata = vec![FieldElement::from(calls.len())];
+
+    match encoding {
+        ExecutionEncoding::New => {
+            for call in calls {
+                calldata.extend([
+                    call.to,
+                    call.selector,
+                    FieldElement::from(call.calldata.len()),
+                ]);
+                calldata.extend(&call.calldata);
+            }
+        }
+        ExecutionEncoding::Legacy => {
+            let mut offset = 0;
+            for call in calls {
+                calldata.extend([
+                    call.to,
+                    call.selector,
+                    FieldElement::from(offset),
+                    FieldElement::from(call.calldata.len()),
+                ]);
+                offset += call.calldata.len();
+            }
+            calldata.push(FieldElement::from(offset));
+            for call in calls {
+                calldata.extend(&call.calldata);
+            }
+        }
+    }
+
+    calldata
+}
+
+impl TransactionBuilder {
+    ///
+    /// Starts a query-only v1 transaction with no calls, a zero nonce and zero fees.
+    ///
+    pub fn new(profile: NetworkProfile, account: TestAccount) -> Self {
+        Self {
+            profile,
+            account,
+            calldata: vec![],
+            version: TransactionVersion::V1,
+            nonce: FieldElement::ZERO,
+            max_fee: FieldElement::ZERO,
+            fees: V3Fees::default(),
+            is_query: true,
+            mutations: vec![],
+        }
+    }
+
+    ///
+    /// An unsigned ERC20 transfer from a mainnet account. Its nonce is zero until set with the
+    /// nonce of the account at the block it is sent to.
+    ///
+    pub fn transfer() -> Self {
+        Self::new(
+            NetworkProfile::mainnet(),
+            TestAccount::new(mainnet::contract::CONTRACT_ACCOUNT),
+        )
+        .calls(vec![generate_call(
+            mainnet::contract::CONTRACT_ERC20,
+            "transfer",
+            vec![1, 81, 0],
+        )])
+    }
+
+    ///
+    /// A transaction from the zero address, which cannot be executed.
+    ///
+    pub fn invalid() -> Self {
+        Self::new(NetworkProfile::mainnet(), TestAccount::new("0x0"))
+            .calldata(vec![FieldElement::ZERO])
+    }
+
+    ///
+    /// Sets the calldata from calls, encoded for the account.
+    ///
+    pub fn calls(mut self, calls: Vec<Call>) -> Self {
+        self.calldata = encode_calls(&calls, self.account.encoding);
+        self
+    }
+
+    ///
+    /// Sets the raw `__execute__` calldata.
+    ///
+    pub fn calldata(mut self, calldata: Vec<FieldElement>) -> Self {
+        self.calldata = calldata;
+        self
+    }
+
+    pub fn version(mut self, version: TransactionVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    pub fn nonce(mut self, nonce: FieldElement) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    pub fn max_fee(mut self, max_fee: FieldElement) -> Self {
+        self.max_fee = max_fee;
+        self
+    }
+
+    pub fn fees(mut self, fees: V3Fees) -> Self {
+        self.fees = fees;
+        self
+    }
+
+    ///
+    /// Whether the transaction uses a query-only version, valid for estimation and simulation
+    /// but never for execution.
+    ///
+    pub fn query(mut self, is_query: bool) -> Self {
+        self.is_query = is_query;
+        self
+    }
+
+    pub fn profile(&self) -> &NetworkProfile {
+        &self.profile
+    }
+
+    ///
+    /// The address of the sending account, to read its nonce from.
+    ///
+    pub fn address(&self) -> FieldElement {
+        self.account.address
+    }
+
+    pub fn mutate(mut self, mutation: Mutation) -> Self {
+        self.mutations.push(mutation);
+        self
+    }
+
+    fn mutated(&self, mutation: Mutation) -> bool {
+        self.mutations.contains(&mutation)
+    }
+
+    fn mutated_nonce(&self) -> FieldElement {
+        match self.mutated(Mutation::WrongNonce) {
+            false => self.nonce,
+            true if self.nonce == FieldElement::ZERO => FieldElement::from(u64::MAX),
+            true => self.nonce - FieldElement::ONE,
+        }
+    }
+
+    fn mutated_fees(&self) -> (FieldElement, V3Fees) {
+        if self.mutated(Mutation::OversizedFee) {
+            (
+                FieldElement::from(u128::MAX),
+                V3Fees {
+                    resource_bounds: V3Fees::l1_gas(u64::MAX, u64::MAX.into()).resource_bounds,
+                    ..self.fees.clone()
+                },
+            )
+        } else {
+            (self.max_fee, self.fees.clone())
+        }
+    }
+
+    ///
+    /// Signs a transaction hash with the account key, for the chain id of the profile or
+    /// another one, and applies the signature mutation.
+    ///
+    fn signature(&self, hash: impl Fn(FieldElement) -> FieldElement) -> Vec<FieldElement> {
+        let chain_id = if self.mutated(Mutation::WrongChainId) {
+            cairo_short_string_to_felt("SN_DITTO").unwrap()
+        } else {
+            self.profile.chain_id
+        };
+
+        let mut signature = match &self.account.signing_key {
+            Some(signing_key) => sign(signing_key, hash(chain_id)),
+            None => vec![],
+        };
+
+        if self.mutated(Mutation::BadSignature) {
+            match signature.first_mut() {
+                Some(r) => *r += FieldElement::ONE,
+                None => signature = vec![FieldElement::ONE, FieldElement::ONE],
+            }
+        }
+
+        signature
+    }
+
+    ///
+    /// Builds the invoke transaction.
+    ///
+    pub fn build_invoke(&self) -> BroadcastedInvokeTransaction {
+        let nonce = self.mutated_nonce();
+        let (max_fee, fees) = self.mutated_fees();
+
+        match self.version {
+            TransactionVersion::V1 => {
+                let mut tx = BroadcastedInvokeTransactionV1 {
+                    max_fee,
+                    signature: vec![],
+                    nonce,
+                    sender_address: self.account.address,
+                    calldata: self.calldata.clone(),
+                    is_query: self.is_query,
+                };
+                tx.signature = self.signature(|chain_id| invoke_v1_hash(&tx, chain_id));
+                BroadcastedInvokeTransaction::V1(tx)
+            }
+            TransactionVersion::V3 => {
+                let mut tx = v3::invoke_v3(
+                    self.account.address,
+                    self.calldata.clone(),
+                    nonce,
+                    &fees,
+                    self.is_query,
+                );
+                tx.signature = self.signature(|chain_id| invoke_v3_hash(&tx, chain_id));
+                BroadcastedInvokeTransaction::V3(tx)
+            }
+        }
+    }
+
+    ///
+    /// Builds the invoke transaction, ready for `estimate_fee` or `simulate_transactions`.
+    ///
+    pub fn build(&self) -> BroadcastedTransaction {
+        BroadcastedTransaction::Invoke(self.build_invoke())
+    }
+
+    ///
+    /// Builds a transaction declaring a class loaded from its artifacts. With fees paid as a max
+    /// fee, Sierra classes are declared by a v2 transaction and legacy classes by a v1
+    /// transaction. Legacy classes cannot be declared by v3 transactions.
+    ///
+    pub fn build_declare(
+        &self,
+        artifact: &ClassArtifact,
+    ) -> anyhow::Result<BroadcastedDeclareTransaction> {
+        let nonce = self.mutated_nonce();
+        let (max_fee, fees) = self.mutated_fees();
+        let sender_address = self.account.address;
+
+        let tx = match (artifact, self.version) {
+            (
+                ClassArtifact::Sierra {
+                    class,
+                    compiled_class_hash,
+                },
+                TransactionVersion::V1,
+            ) => {
+                let mut tx = declare::declare_v2(
+                    sender_address,
+                    class.clone(),
+                    *compiled_class_hash,
+                    nonce,
+                    max_fee,
+                    self.is_query,
+                );
+                tx.signature = self.signature(|chain_id| declare_v2_hash(&tx, chain_id));
+                BroadcastedDeclareTransaction::V2(tx)
+            }
+            (
+                ClassArtifact::Sierra {
+                    class,
+                    compiled_class_hash,
+                },
+                TransactionVersion::V3,
+            ) => {
+                let mut tx = v3::declare_v3(
+                    sender_address,
+                    class.clone(),
+                    *compiled_class_hash,
+                    nonce,
+                    &fees,
+                    self.is_query,
+                );
+                tx.signature = self.signature(|chain_id| declare_v3_hash(&tx, chain_id));
+                BroadcastedDeclareTransaction::V3(tx)
+            }
+            (ClassArtifact::Legacy { class, class_hash }, TransactionVersion::V1) => {
+                let mut tx = declare::declare_v1(
+                    sender_address,
+                    class.clone(),
+                    nonce,
+                    max_fee,
+                    self.is_query,
+                );
+                tx.signature =
+                    self.signature(|chain_id| declare_v1_hash(&tx, *class_hash, chain_id));
+                BroadcastedDeclareTransaction::V1(tx)
+            }
+            (ClassArtifact::Legacy { .. }, TransactionVersion::V3) => {
+                return Err(anyhow!(
+                    "Legacy classes can only be declared by v1 transactions"
+                ));
+            }
+        };
+
+        Ok(tx)
+    }
+
+    ///
+    /// Builds a transaction deploying an account of `class_hash`, signed by the account key.
+    /// The address of the builder account is ignored, as the deployed address is derived from
+    /// the class, salt and constructor calldata.
+    ///
+    pub fn build_deploy_account(
+        &self,
+        class_hash: FieldElement,
+        contract_address_salt: FieldElement,
+        constructor_calldata: Vec<FieldElement>,
+    ) -> BroadcastedDeployAccountTransaction {
+        let nonce = self.mutated_nonce();
+        let (max_fee, fees) = self.mutated_fees();
+
+        match self.version {
+            TransactionVersion::V1 => {
+                let mut tx = BroadcastedDeployAccountTransactionV1 {
+                    max_fee,
+                    signature: vec![],
+                    nonce,
+                    contract_address_salt,
+                    constructor_calldata,
+                    class_hash,
+                    is_query: self.is_query,
+                };
+                tx.signature = self.signature(|chain_id| deploy_account_v1_hash(&tx, chain_id));
+                BroadcastedDeployAccountTransaction::V1(tx)
+            }
+            TransactionVersion::V3 => {
+                let mut tx = v3::deploy_account_v3(
+                    class_hash,
+                    contract_address_salt,
+                    constructor_calldata,
+                    nonce,
+                    &fees,
+                    self.is_query,
+                );
+                tx.signature = self.signature(|chain_id| deploy_account_v3_hash(&tx, chain_id));
+                BroadcastedDeployAccountTransaction::V3(tx)
+            }
+        }
+    }
+}
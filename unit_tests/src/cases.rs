@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::fs;
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use starknet_providers::jsonrpc::{JsonRpcMethod, JsonRpcTransport};
+
+use crate::json_path;
+use crate::snapshot::{canonicalize, first_difference};
+use crate::transport::corpus::response_to_value;
+
+///
+/// Environment variable overriding the path of the request cases file.
+///
+pub const REQUESTS_ENV: &str = "DITTO_REQUESTS";
+
+///
+/// Default request cases file, relative to the `unit_tests` crate like `secret.json`.
+///
+pub const DEFAULT_REQUESTS: &str = "../cases.jsonl";
+
+///
+/// A JSON-RPC request to run against every node, written as one line of a JSONL file.
+///
+/// ```json
+/// {"method": "starknet_getStateUpdate", "params": {"block_id": {"block_number": 0}}}
+/// ```
+///
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RequestCase {
+    /// Name used in reports, the line number of the case by default.
+    #[serde(default)]
+    pub name: Option<String>,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+    /// JSON paths into the response, such as `$.result.timestamp`, left out of the comparison.
+    #[serde(default)]
+    pub ignore: Vec<String>,
+    /// Error code every node must answer with, instead of a result.
+    #[serde(default)]
+    pub expected_error: Option<i64>,
+    /// Nodes to run the case against, every node by default.
+    #[serde(default)]
+    pub nodes: Option<Vec<String>>,
+}
+
+///
+/// A request case on which a node did not behave as expected.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CaseFailure {
+    pub case: String,
+    pub node: String,
+    pub message: String,
+}
+
+impl fmt::Display for CaseFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}: {}", self.node, self.case, self.message)
+    }
+}
+
+///
+/// Returns the path of the request cases file.
+///
+pub fn requests_path() -> String {
+    env::var(REQUESTS_ENV).unwrap_or_else(|_| String::from(DEFAULT_REQUESTS))
+}
+
+///
+/// Parses a JSONL file of request cases, skipping blank lines. Unnamed cases are named after
+/// their line number.
+///
+pub fn load_cases(path: &str) -> anyhow::Result<Vec<RequestCase>> {
+    let mut cases = vec![];
+
+    for (index, line) in fs::read_to_string(path)?.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let mut case: RequestCase = serde_json::from_str(line)
+            .map_err(|e| anyhow!("Invalid request case at {path}:{}: {e}", index + 1))?;
+        case.name
+            .get_or_insert_with(|| format!("{path}:{}", index + 1));
+        cases.push(case);
+    }
+
+    Ok(cases)
+}
+
+///
+/// Sends a request and returns its response without `id` and `jsonrpc`, so that it holds
+/// either a `result` or an `error`.
+///
+pub async fn send<T: JsonRpcTransport + Sync>(
+    transport: &T,
+    method: &str,
+    params: &Value,
+) -> anyhow::Result<Value> {
+    let method: JsonRpcMethod = serde_json::from_value(Value::String(method.to_string()))
+        .map_err(|_| anyhow!("Unknown method {method}"))?;
+    let response = transport
+        .send_request::<_, Value>(method, params.clone())
+        .await
+        .map_err(|e| anyhow!("Transport error: {e}"))?;
+
+    let mut response = response_to_value(&response);
+    if let Value::Object(map) = &mut response {
+        map.remove("id");
+        map.remove("jsonrpc");
+    }
+
+    Ok(response)
+}
+
+///
+/// Runs a request case against its nodes.
+///
+/// With an expected error, every node must answer with that error code. Otherwise, every
+/// response must match the response of the first node, in name order, once canonicalized and
+/// stripped of the ignored paths.
+///
+pub async fn run_case<T: JsonRpcTransport + Sync>(
+    transports: &HashMap<String, T>,
+    case: &RequestCase,
+) -> Vec<CaseFailure> {
+    let name = case.name.clone().unwrap_or_else(|| case.method.clone());
+    let failure = |node: &str, message: String| CaseFailure {
+        case: name.clone(),
+        node: node.to_string(),
+        message,
+    };
+
+    let mut nodes: Vec<&String> = transports
+        .keys()
+        .filter(|node| {
+            case.nodes
+                .as_ref()
+                .map_or(true, |nodes| nodes.contains(node))
+        })
+        .collect();
+    nodes.sort();
+
+    let mut failures = vec![];
+    let mut reference: Option<(&str, Value)> = None;
+
+    for node in nodes {
+        let mut response = match send(&transports[node], &case.method, &case.params).await {
+            Ok(response) => canonicalize(&response),
+            Err(e) => {
+                failures.push(failure(node, e.to_string()));
+                continue;
+            }
+        };
+
+        if let Some(code) = case.expected_error {
+            let actual = response["error"]["code"].as_i64();
+            if actual != Some(code) {
+                failures.push(failure(
+                    node,
+                    format!("expected error {code}, got {response}"),
+                ));
+            }
+            continue;
+        }
+
+        for path in &case.ignore {
+            if let Err(e) = json_path::remove(&mut response, path) {
+                failures.push(failure(node, e.to_string()));
+            }
+        }
+
+        match &reference {
+            None => reference = Some((node, response)),
+            Some((reference_node, expected)) => {
+                if let Some(path) = first_difference(expected, &response, "$") {
+                    failures.push(failure(
+                        node,
+                        format!("response differs from {reference_node} at {path}"),
+                    ));
+                }
+            }
+        }
+    }
+
+    failures
+}
+
+///
+/// Runs every request case, collecting the failures of all nodes.
+///
+pub async fn run_cases<T: JsonRpcTransport + Sync>(
+    transports: &HashMap<String, T>,
+    cases: &[RequestCase],
+) -> Vec<CaseFailure> {
+    let mut failures = vec![];
+
+    for case in cases {
+        failures.extend(run_case(transports, case).await);
+    }
+
+    failures
+}
@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+
+use anyhow::anyhow;
+use serde_json::{json, Value};
+
+///
+/// Error code of JSON-RPC 2.0 for a body which is not valid JSON.
+///
+pub const PARSE_ERROR: i64 = -32700;
+
+///
+/// Error code of JSON-RPC 2.0 for a body which is not a valid request object.
+///
+pub const INVALID_REQUEST: i64 = -32600;
+
+///
+/// Error code of JSON-RPC 2.0 for a method the node does not know.
+///
+pub const METHOD_NOT_FOUND: i64 = -32601;
+
+///
+/// What a node must answer to a raw JSON-RPC body.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expectation {
+    /// A single response with a result, echoing `id`.
+    Result(Value),
+    /// A single response with the error `code`, echoing `id`.
+    Error(Value, i64),
+    /// No response at all, as for notifications.
+    Nothing,
+    /// An array of responses, in any order, each matched to its expectation by id.
+    Batch(Vec<Expectation>),
+}
+
+///
+/// A raw JSON-RPC body covering one rule of the JSON-RPC 2.0 specification.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConformanceCase {
+    pub name: &'static str,
+    pub body: String,
+    pub expectation: Expectation,
+}
+
+fn case(name: &'static str, body: Value, expectation: Expectation) -> ConformanceCase {
+    ConformanceCase {
+        name,
+        body: body.to_string(),
+        expectation,
+    }
+}
+
+///
+/// Returns the JSON-RPC 2.0 conformance cases: request ids of every type, notifications, named
+/// and positional params, unknown methods, malformed JSON and batches.
+///
+pub fn conformance_cases() -> Vec<ConformanceCase> {
+    let chain_id = |id: Value| json!({ "jsonrpc": "2.0", "id": id, "method": "starknet_chainId" });
+    let block = |params: Value| {
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "starknet_getBlockWithTxHashes",
+            "params": params,
+        })
+    };
+
+    vec![
+        case(
+            "string id",
+            chain_id(json!("ditto")),
+            Expectation::Result(json!("ditto")),
+        ),
+        case(
+            "number id",
+            chain_id(json!(42)),
+            Expectation::Result(json!(42)),
+        ),
+        case(
+            "null id",
+            chain_id(Value::Null),
+            Expectation::Result(Value::Null),
+        ),
+        case(
+            "notification",
+            json!({ "jsonrpc": "2.0", "method": "starknet_chainId" }),
+            Expectation::Nothing,
+        ),
+        case(
+            "named params",
+            block(json!({ "block_id": { "block_number": 0 } })),
+            Expectation::Result(json!(1)),
+        ),
+        case(
+            "positional params",
+            block(json!([{ "block_number": 0 }])),
+            Expectation::Result(json!(1)),
+        ),
+        case(
+            "unknown method",
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "starknet_ditto", "params": [] }),
+            Expectation::Error(json!(1), METHOD_NOT_FOUND),
+        ),
+        ConformanceCase {
+            name: "malformed JSON",
+            body: String::from(r#"{"jsonrpc": "2.0", "id": 1, "method": "starknet_chainId""#),
+            expectation: Expectation::Error(Value::Null, PARSE_ERROR),
+        },
+        case(
+            "empty batch",
+            json!([]),
+            Expectation::Error(Value::Null, INVALID_REQUEST),
+        ),
+        case(
+            "valid batch",
+            json!([chain_id(json!(1)), chain_id(json!("two"))]),
+            Expectation::Batch(vec![
+                Expectation::Result(json!(1)),
+                Expectation::Result(json!("two")),
+            ]),
+        ),
+        case(
+            "mixed batch",
+            json!([
+                chain_id(json!(1)),
+                { "jsonrpc": "2.0", "id": 2, "method": "starknet_ditto", "params": [] },
+                1,
+                { "jsonrpc": "2.0", "method": "starknet_chainId" },
+            ]),
+            Expectation::Batch(vec![
+                Expectation::Result(json!(1)),
+                Expectation::Error(json!(2), METHOD_NOT_FOUND),
+                Expectation::Error(Value::Null, INVALID_REQUEST),
+            ]),
+        ),
+        case(
+            "batch of notifications",
+            json!([
+                { "jsonrpc": "2.0", "method": "starknet_chainId" },
+                { "jsonrpc": "2.0", "method": "starknet_blockNumber" },
+            ]),
+            Expectation::Nothing,
+        ),
+    ]
+}
+
+///
+/// Posts a raw body to a node. Returns `None` when the node answers with an empty body.
+///
+pub async fn post_raw(url: &str, body: &str) -> anyhow::Result<Option<Value>> {
+    let response = reqwest::Client::new()
+        .post(url)
+        .header("Content-Type", "application/json")
+        .body(body.to_string())
+        .send()
+        .await?
+        .text()
+        .await?;
+
+    if response.trim().is_empty() {
+        return Ok(None);
+    }
+
+    serde_json::from_str(&response)
+        .map(Some)
+        .map_err(|e| anyhow!("Response is not JSON ({e}): {response}"))
+}
+
+///
+/// Checks a single response object against a `Result` or `Error` expectation.
+///
+fn check_object(expectation: &Expectation, response: &Value) -> anyhow::Result<()> {
+    if response["jsonrpc"] != json!("2.0") {
+        return Err(anyhow!("'jsonrpc' is not echoed back in {response}"));
+    }
+
+    let (id, code) = match expectation {
+        Expectation::Result(id) => (id, None),
+        Expectation::Error(id, code) => (id, Some(*code)),
+        _ => return Err(anyhow!("Expected {expectation:?}, got {response}")),
+    };
+
+    if response.get("id") != Some(id) {
+        return Err(anyhow!("Expected id {id}, got {response}"));
+    }
+    match code {
+        None if response.get("result").is_none() => {
+            Err(anyhow!("Expected a result, got {response}"))
+        }
+        Some(code) if response["error"]["code"].as_i64() != Some(code) => {
+            Err(anyhow!("Expected error {code}, got {response}"))
+        }
+        _ => Ok(()),
+    }
+}
+
+///
+/// Checks the answer of a node against an expectation.
+///
+pub fn check_expectation(
+    expectation: &Expectation,
+    response: Option<&Value>,
+) -> anyhow::Result<()> {
+    match (expectation, response) {
+        (Expectation::Nothing, None) => Ok(()),
+        (Expectation::Nothing, Some(response)) => {
+            Err(anyhow!("Expected no response, got {response}"))
+        }
+        (_, None) => Err(anyhow!("Expected {expectation:?}, got no response")),
+        (Expectation::Batch(expectations), Some(Value::Array(responses))) => {
+            if responses.len() != expectations.len() {
+                return Err(anyhow!(
+                    "Expected {} responses, got {}",
+                    expectations.len(),
+                    responses.len()
+                ));
+            }
+
+            let mut remaining: Vec<&Value> = responses.iter().collect();
+            for expectation in expectations {
+                let index = remaining
+                    .iter()
+                    .position(|response| check_object(expectation, response).is_ok())
+                    .ok_or_else(|| {
+                        anyhow!("No response matches {expectation:?} in {responses:?}")
+                    })?;
+                remaining.remove(index);
+            }
+
+            Ok(())
+        }
+        (Expectation::Batch(_), Some(response)) => {
+            Err(anyhow!("Expected an array of responses, got {response}"))
+        }
+        (_, Some(response)) => check_object(expectation, response),
+    }
+}
+
+///
+/// Reduces an answer to its protocol-level behaviour, the id and result or error code of each
+/// response, so that nodes can be compared regardless of their data.
+///
+pub fn behaviour(response: Option<&Value>) -> Value {
+    let object = |response: &Value| {
+        json!({
+            "id": response.get("id"),
+            "jsonrpc": response.get("jsonrpc"),
+            "error": response["error"]["code"],
+            "result": response.get("result").is_some(),
+        })
+    };
+
+    match response {
+        None => Value::Null,
+        Some(Value::Array(responses)) => {
+            let mut responses: Vec<Value> = responses.iter().map(object).collect();
+            responses.sort_by_key(|response| response.to_string());
+            Value::Array(responses)
+        }
+        Some(response) => object(response),
+    }
+}
+
+///
+/// Runs every conformance case against every node.
+///
+/// Each answer is checked against its expectation, and the behaviour of every node is compared
+/// with the first node in name order. Returns one message per failure.
+///
+pub async fn run_conformance(urls: &HashMap<String, String>) -> Vec<String> {
+    let mut nodes: Vec<&String> = urls.keys().collect();
+    nodes.sort();
+
+    let mut failures = vec![];
+    for case in conformance_cases() {
+        let mut reference: Option<(&str, Value)> = None;
+
+        for node in &nodes {
+            let response = match post_raw(&urls[*node], &case.body).await {
+                Ok(response) => response,
+                Err(e) => {
+                    failures.push(format!("[{node}] {}: {e}", case.name));
+                    continue;
+                }
+            };
+
+            if let Err(e) = check_expectation(&case.expectation, response.as_ref()) {
+                failures.push(format!("[{node}] {}: {e}", case.name));
+            }
+
+            let observed = behaviour(response.as_ref());
+            match &reference {
+                None => reference = Some((node.as_str(), observed)),
+                Some((reference_node, expected)) if *expected != observed => {
+                    failures.push(format!(
+                        "[{node}] {}: behaves as {observed}, {reference_node} as {expected}",
+                        case.name
+                    ));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    failures
+}
@@ -2,6 +2,7 @@
 /// Starknet Mainnet block number versions.
 ///
 pub const BLOCK_0: u64 = 0;
+pub const BLOCK_0_7_0: u64 = 833;
 pub const BLOCK_0_9_1: u64 = 3799;
 pub const BLOCK_0_10_0: u64 = 4883;
 pub const BLOCK_0_10_1: u64 = 6570;
@@ -17,3 +18,4 @@ pub const BLOCK_0_12_2: u64 = 194410;
 pub const BLOCK_0_12_3: u64 = 472644;
 pub const BLOCK_0_13_0: u64 = 501514;
 pub const BLOCK_0_13_1: u64 = 607878;
+pub const BLOCK_0_13_2: u64 = 671813;
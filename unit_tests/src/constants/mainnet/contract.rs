@@ -100,3 +100,36 @@ pub const CONTRACT_ARGENT_ACCOUNT_CAIRO_1: &str =
 ///
 pub const CONTRACT_ERC721_CAIRO_0: &str =
     "0x0717e6b64e3c48c016ffec829963696480bf40ad8ca1d5303902c1b23347ff57";
+
+///
+/// OpenZeppelin account v0.8.1 Cairo 1 class hash.
+///
+/// Details concerning this class can be found on [StarkScan](https://starkscan.co/class/0x061dac032f228abef9c6626f995015233097ae253a7f72d68552db02f2971b8f)
+///
+pub const CLASS_OZ_ACCOUNT: &str =
+    "0x061dac032f228abef9c6626f995015233097ae253a7f72d68552db02f2971b8f";
+
+///
+/// Argent X account Cairo 1 class hash.
+///
+/// Details concerning this class can be found on [StarkScan](https://starkscan.co/class/0x029927c8af6bccf3f6fda035981e765a7bdbf18a2dc0d630494f8758aa908e2b)
+///
+pub const CLASS_ARGENT_ACCOUNT: &str =
+    "0x029927c8af6bccf3f6fda035981e765a7bdbf18a2dc0d630494f8758aa908e2b";
+
+///
+/// Braavos base account v1.0.0 class hash. Braavos accounts are deployed with this class,
+/// which upgrades itself to [`CLASS_BRAAVOS_ACCOUNT`] in its constructor.
+///
+/// Details concerning this class can be found on [StarkScan](https://starkscan.co/class/0x013bfe114fb1cf405bfc3a7f8dbe2d91db146c17521d40dcf57e16d6b59fa8e6)
+///
+pub const CLASS_BRAAVOS_BASE_ACCOUNT: &str =
+    "0x013bfe114fb1cf405bfc3a7f8dbe2d91db146c17521d40dcf57e16d6b59fa8e6";
+
+///
+/// Braavos account v1.0.0 class hash.
+///
+/// Details concerning this class can be found on [StarkScan](https://starkscan.co/class/0x00816dd0297efc55dc1e7559020a3a825e81ef734b558f03c83325d4da7e6253)
+///
+pub const CLASS_BRAAVOS_ACCOUNT: &str =
+    "0x00816dd0297efc55dc1e7559020a3a825e81ef734b558f03c83325d4da7e6253";
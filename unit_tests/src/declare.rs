@@ -0,0 +1,151 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use serde::de::DeserializeOwned;
+use starknet_core::types::contract::legacy::LegacyContractClass;
+use starknet_core::types::contract::{CompiledClass, SierraClass};
+use starknet_core::types::{
+    BroadcastedDeclareTransactionV1, BroadcastedDeclareTransactionV2,
+    CompressedLegacyContractClass, FieldElement, FlattenedSierraClass,
+};
+use starknet_signers::SigningKey;
+
+use crate::hash::transaction::{declare_v1_hash, declare_v2_hash};
+use crate::v3::sign;
+
+///
+/// A class loaded from its compiled artifacts, ready to be declared.
+///
+#[derive(Clone, Debug)]
+pub enum ClassArtifact {
+    /// A Cairo 1 class, declared with its Sierra program and the hash of its CASM compilation.
+    Sierra {
+        class: Arc<FlattenedSierraClass>,
+        compiled_class_hash: FieldElement,
+    },
+    /// A Cairo 0 class, declared with its compressed program.
+    Legacy {
+        class: Arc<CompressedLegacyContractClass>,
+        class_hash: FieldElement,
+    },
+}
+
+impl ClassArtifact {
+    pub fn class_hash(&self) -> FieldElement {
+        match self {
+            ClassArtifact::Sierra { class, .. } => class.class_hash(),
+            ClassArtifact::Legacy { class_hash, .. } => *class_hash,
+        }
+    }
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> anyhow::Result<T> {
+    let file = File::open(path).map_err(|e| anyhow!("Failed to open {}: {e}", path.display()))?;
+    serde_json::from_reader(BufReader::new(file))
+        .map_err(|e| anyhow!("Invalid artifact {}: {e}", path.display()))
+}
+
+///
+/// Loads a Cairo 1 class from the Sierra and CASM artifacts produced by `scarb build`, and
+/// computes the compiled class hash committed to by the declare transaction.
+///
+pub fn load_sierra(
+    sierra_path: impl AsRef<Path>,
+    casm_path: impl AsRef<Path>,
+) -> anyhow::Result<ClassArtifact> {
+    let sierra: SierraClass = read_json(sierra_path.as_ref())?;
+    let casm: CompiledClass = read_json(casm_path.as_ref())?;
+
+    let class = sierra
+        .flatten()
+        .map_err(|e| anyhow!("Failed to flatten Sierra class: {e}"))?;
+    let compiled_class_hash = casm
+        .class_hash()
+        .map_err(|e| anyhow!("Failed to compute compiled class hash: {e}"))?;
+
+    Ok(ClassArtifact::Sierra {
+        class: Arc::new(class),
+        compiled_class_hash,
+    })
+}
+
+///
+/// Loads a Cairo 0 class from the JSON produced by `starknet-compile-deprecated`.
+///
+pub fn load_legacy(path: impl AsRef<Path>) -> anyhow::Result<ClassArtifact> {
+    let class: LegacyContractClass = read_json(path.as_ref())?;
+
+    let class_hash = class
+        .class_hash()
+        .map_err(|e| anyhow!("Failed to compute legacy class hash: {e}"))?;
+    let class = class
+        .compress()
+        .map_err(|e| anyhow!("Failed to compress legacy program: {e}"))?;
+
+    Ok(ClassArtifact::Legacy {
+        class: Arc::new(class),
+        class_hash,
+    })
+}
+
+///
+/// Builds an unsigned v1 declare transaction for a legacy class.
+///
+pub fn declare_v1(
+    sender_address: FieldElement,
+    contract_class: Arc<CompressedLegacyContractClass>,
+    nonce: FieldElement,
+    max_fee: FieldElement,
+    is_query: bool,
+) -> BroadcastedDeclareTransactionV1 {
+    BroadcastedDeclareTransactionV1 {
+        sender_address,
+        max_fee,
+        signature: vec![],
+        nonce,
+        contract_class,
+        is_query,
+    }
+}
+
+///
+/// Builds an unsigned v2 declare transaction for a Sierra class.
+///
+pub fn declare_v2(
+    sender_address: FieldElement,
+    contract_class: Arc<FlattenedSierraClass>,
+    compiled_class_hash: FieldElement,
+    nonce: FieldElement,
+    max_fee: FieldElement,
+    is_query: bool,
+) -> BroadcastedDeclareTransactionV2 {
+    BroadcastedDeclareTransactionV2 {
+        sender_address,
+        compiled_class_hash,
+        max_fee,
+        signature: vec![],
+        nonce,
+        contract_class,
+        is_query,
+    }
+}
+
+pub fn sign_declare_v1(
+    tx: &mut BroadcastedDeclareTransactionV1,
+    class_hash: FieldElement,
+    signing_key: &SigningKey,
+    chain_id: FieldElement,
+) {
+    tx.signature = sign(signing_key, declare_v1_hash(tx, class_hash, chain_id));
+}
+
+pub fn sign_declare_v2(
+    tx: &mut BroadcastedDeclareTransactionV2,
+    signing_key: &SigningKey,
+    chain_id: FieldElement,
+) {
+    tx.signature = sign(signing_key, declare_v2_hash(tx, chain_id));
+}
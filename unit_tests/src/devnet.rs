@@ -0,0 +1,311 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use anyhow::anyhow;
+use macro_utils::{DevnetConfig, TestConfig};
+use serde::Deserialize;
+use serde_json::json;
+use starknet_accounts::ExecutionEncoding;
+use starknet_core::types::{
+    BlockId, BlockTag, BroadcastedTransaction, FieldElement, PriceUnit, StarknetError,
+    TransactionExecutionStatus, TransactionReceiptWithBlockInfo, TransactionStatus,
+};
+use starknet_providers::jsonrpc::{HttpTransport, JsonRpcClient};
+use starknet_providers::{Provider, ProviderError};
+use starknet_signers::SigningKey;
+use url::Url;
+
+use crate::builder::{NetworkProfile, TestAccount, TransactionBuilder};
+use crate::transport::suite::MODE_ENV;
+use crate::v3::V3Fees;
+
+///
+/// Url of a starknet-devnet instance started with its default options.
+///
+pub const DEFAULT_URL: &str = "http://127.0.0.1:5050";
+
+///
+/// Name of the devnet itself among the nodes of a [`DevnetHarness`].
+///
+pub const DEVNET: &str = "devnet";
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+const ACCEPTANCE_TIMEOUT: Duration = Duration::from_secs(60);
+
+///
+/// Max fee of v1 transactions, well above the cost of a transfer at the devnet gas price.
+///
+const MAX_FEE: u64 = 10_000_000_000_000_000;
+
+///
+/// Url of a devnet endpoint other than JSON-RPC, such as `/mint`.
+///
+fn endpoint(url: &str, path: &str) -> anyhow::Result<Url> {
+    let mut url = Url::parse(url)?;
+    url.set_path(path);
+    Ok(url)
+}
+
+///
+/// Mints `amount` of the fee token paying in `unit` to `address`, through the `/mint` endpoint
+/// of starknet-devnet. The address does not need to hold a contract yet, which is how
+/// counterfactual accounts are funded before being deployed.
+///
+pub async fn mint(
+    url: &str,
+    address: FieldElement,
+    amount: u128,
+    unit: PriceUnit,
+) -> anyhow::Result<()> {
+    let unit = match unit {
+        PriceUnit::Wei => "WEI",
+        PriceUnit::Fri => "FRI",
+    };
+    let response = reqwest::Client::new()
+        .post(endpoint(url, "mint")?)
+        .json(&json!({
+            "address": format!("{address:#x}"),
+            "amount": amount,
+            "unit": unit,
+        }))
+        .send()
+        .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow!("Failed to mint to {address:#x} ({status}): {body}"));
+    }
+
+    Ok(())
+}
+
+///
+/// Polls the status of a transaction until it is accepted on L2 or L1, or rejected.
+///
+/// A transaction unknown to the node is assumed to be still on its way. Fails when no final
+/// status is reached within `timeout`.
+///
+pub async fn wait_for_transaction<P: Provider + Sync>(
+    provider: &P,
+    transaction_hash: FieldElement,
+    timeout: Duration,
+) -> anyhow::Result<TransactionStatus> {
+    let start = Instant::now();
+
+    loop {
+        match provider.get_transaction_status(transaction_hash).await {
+            Ok(TransactionStatus::Received) => {}
+            Ok(status) => return Ok(status),
+            Err(ProviderError::StarknetError(StarknetError::TransactionHashNotFound)) => {}
+            Err(e) => return Err(anyhow!("Failed to get transaction status: {e}")),
+        }
+
+        if start.elapsed() > timeout {
+            return Err(anyhow!(
+                "Transaction {transaction_hash:#x} not accepted after {timeout:?}"
+            ));
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+///
+/// An account funded at genesis by starknet-devnet.
+///
+#[derive(Clone, Debug, Deserialize)]
+pub struct PredeployedAccount {
+    pub address: FieldElement,
+    pub public_key: FieldElement,
+    pub private_key: FieldElement,
+}
+
+///
+/// Lists the accounts predeployed by starknet-devnet, through its `/predeployed_accounts`
+/// endpoint.
+///
+pub async fn predeployed_accounts(url: &str) -> anyhow::Result<Vec<PredeployedAccount>> {
+    let response = reqwest::get(endpoint(url, "predeployed_accounts")?).await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(anyhow!("Failed to list predeployed accounts ({status})"));
+    }
+
+    Ok(response.json().await?)
+}
+
+///
+/// Sends real transactions to a devnet and to the nodes following it, from the accounts the
+/// devnet predeploys.
+///
+/// Transactions are sent through the write API of one node at a time, and every node shares
+/// the devnet state, so each transaction should be waited for before the next one is built.
+///
+pub struct DevnetHarness {
+    pub url: String,
+    pub profile: NetworkProfile,
+    pub accounts: Vec<PredeployedAccount>,
+    pub nodes: BTreeMap<String, JsonRpcClient<HttpTransport>>,
+}
+
+impl DevnetHarness {
+    ///
+    /// Connects to the devnet of the config, reading its chain id and predeployed accounts.
+    ///
+    pub async fn connect(config: &DevnetConfig) -> anyhow::Result<Self> {
+        let client = |url: &str| -> anyhow::Result<JsonRpcClient<HttpTransport>> {
+            Ok(JsonRpcClient::new(HttpTransport::new(Url::parse(url)?)))
+        };
+
+        let mut nodes = BTreeMap::new();
+        nodes.insert(DEVNET.to_string(), client(&config.url)?);
+        for (name, url) in &config.nodes {
+            nodes.insert(name.clone(), client(url)?);
+        }
+
+        let chain_id = nodes[DEVNET]
+            .chain_id()
+            .await
+            .map_err(|e| anyhow!("Devnet is not reachable at {}: {e}", config.url))?;
+        let accounts = predeployed_accounts(&config.url).await?;
+        if accounts.is_empty() {
+            return Err(anyhow!(
+                "Devnet at {} has no predeployed account",
+                config.url
+            ));
+        }
+
+        Ok(Self {
+            url: config.url.clone(),
+            profile: NetworkProfile {
+                name: DEVNET,
+                chain_id,
+            },
+            accounts,
+            nodes,
+        })
+    }
+
+    ///
+    /// Connects to the devnet of the test config. Write tests are skipped when no devnet is
+    /// configured or when requests are replayed, in which case the reason is returned for the
+    /// test to report.
+    ///
+    pub async fn from_test_config(config: &TestConfig) -> Result<Self, String> {
+        if std::env::var(MODE_ENV).as_deref() == Ok("replay") {
+            return Err(String::from("Transactions cannot be sent in replay mode"));
+        }
+        let Some(devnet) = &config.devnet else {
+            return Err(String::from(
+                "No devnet in '../secret.json', skipping write tests",
+            ));
+        };
+
+        Ok(Self::connect(devnet)
+            .await
+            .expect("Error while connecting to the devnet"))
+    }
+
+    pub fn node(&self, node: &str) -> &JsonRpcClient<HttpTransport> {
+        self.nodes
+            .get(node)
+            .unwrap_or_else(|| panic!("Unknown devnet node {node}"))
+    }
+
+    ///
+    /// Whether a class is declared on the devnet. A plain devnet only declares the classes of
+    /// its predeployed contracts, such as the OpenZeppelin account, while classes like the
+    /// Argent and Braavos accounts need a devnet forking a network where they are declared.
+    ///
+    pub async fn is_declared(&self, class_hash: FieldElement) -> anyhow::Result<bool> {
+        match self
+            .node(DEVNET)
+            .get_class(BlockId::Tag(BlockTag::Pending), class_hash)
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(ProviderError::StarknetError(StarknetError::ClassHashNotFound)) => Ok(false),
+            Err(e) => Err(anyhow!("Failed to get class {class_hash:#x}: {e}")),
+        }
+    }
+
+    pub fn account(&self, index: usize) -> TestAccount {
+        let account = &self.accounts[index];
+        TestAccount {
+            address: account.address,
+            signing_key: Some(SigningKey::from_secret_scalar(account.private_key)),
+            encoding: ExecutionEncoding::New,
+        }
+    }
+
+    ///
+    /// Starts an executable transaction from a predeployed account, at its pending nonce on
+    /// `node`, with fees any predeployed account can pay.
+    ///
+    pub async fn builder(&self, node: &str, index: usize) -> anyhow::Result<TransactionBuilder> {
+        let account = self.account(index);
+        let nonce = self
+            .node(node)
+            .get_nonce(BlockId::Tag(BlockTag::Pending), account.address)
+            .await
+            .map_err(|e| anyhow!("Failed to get the nonce of {:#x}: {e}", account.address))?;
+
+        Ok(TransactionBuilder::new(self.profile.clone(), account)
+            .nonce(nonce)
+            .max_fee(FieldElement::from(MAX_FEE))
+            .fees(V3Fees::l1_gas(100_000, 1_000_000_000_000))
+            .query(false))
+    }
+
+    ///
+    /// Sends a transaction through the write API of `node`, returning its hash.
+    ///
+    pub async fn submit(
+        &self,
+        node: &str,
+        transaction: BroadcastedTransaction,
+    ) -> Result<FieldElement, ProviderError> {
+        let provider = self.node(node);
+        match transaction {
+            BroadcastedTransaction::Invoke(tx) => provider
+                .add_invoke_transaction(tx)
+                .await
+                .map(|result| result.transaction_hash),
+            BroadcastedTransaction::Declare(tx) => provider
+                .add_declare_transaction(tx)
+                .await
+                .map(|result| result.transaction_hash),
+            BroadcastedTransaction::DeployAccount(tx) => provider
+                .add_deploy_account_transaction(tx)
+                .await
+                .map(|result| result.transaction_hash),
+        }
+    }
+
+    ///
+    /// Waits until a transaction succeeds on `node`, and returns its receipt.
+    ///
+    pub async fn wait_for_acceptance(
+        &self,
+        node: &str,
+        transaction_hash: FieldElement,
+    ) -> anyhow::Result<TransactionReceiptWithBlockInfo> {
+        let provider = self.node(node);
+
+        match wait_for_transaction(provider, transaction_hash, ACCEPTANCE_TIMEOUT).await? {
+            TransactionStatus::AcceptedOnL2(TransactionExecutionStatus::Succeeded)
+            | TransactionStatus::AcceptedOnL1(TransactionExecutionStatus::Succeeded) => {}
+            status => {
+                return Err(anyhow!(
+                    "{node}: transaction {transaction_hash:#x} ended as {status:?}"
+                ))
+            }
+        }
+
+        provider
+            .get_transaction_receipt(transaction_hash)
+            .await
+            .map_err(|e| anyhow!("{node}: failed to get receipt of {transaction_hash:#x}: {e}"))
+    }
+}
@@ -2,47 +2,99 @@ use std::collections::HashMap;
 
 use macro_utils::TestConfig;
 use rstest::fixture;
-use starknet_providers::{jsonrpc::HttpTransport, JsonRpcClient};
-use url::Url;
+use starknet_providers::JsonRpcClient;
 
 use crate::constants::*;
 use crate::map;
+use crate::transport::recording::RecordingTransport;
+pub use crate::transport::suite::SuiteTransport;
+use crate::transport::suite::MODE_ENV;
 
 #[fixture]
 pub fn config() -> TestConfig {
-    TestConfig::new("../secret.json").expect("'../secret.json' must contain correct node urls")
+    match TestConfig::new("../secret.json") {
+        Ok(config) => config,
+        // Replayed runs never reach the nodes, so they do not need their urls
+        Err(_) if std::env::var(MODE_ENV).as_deref() == Ok("replay") => TestConfig {
+            pathfinder: String::new(),
+            deoxys: String::new(),
+            juno: String::new(),
+            devnet: None,
+        },
+        Err(e) => panic!("'../secret.json' must contain correct node urls: {e}"),
+    }
 }
 
 #[fixture]
-pub fn deoxys(config: TestConfig) -> JsonRpcClient<HttpTransport> {
-    JsonRpcClient::new(HttpTransport::new(
-        Url::parse(&config.deoxys).expect("Error parsing Deoxys node url"),
+pub fn deoxys(config: TestConfig) -> JsonRpcClient<SuiteTransport> {
+    JsonRpcClient::new(SuiteTransport::new(
+        mainnet::network::DEOXYS,
+        &config.deoxys,
     ))
 }
 
 #[fixture]
-pub fn pathfinder(config: TestConfig) -> JsonRpcClient<HttpTransport> {
-    JsonRpcClient::new(HttpTransport::new(
-        Url::parse(&config.pathfinder).expect("Error parsing Pathfinder node url"),
+pub fn pathfinder(config: TestConfig) -> JsonRpcClient<SuiteTransport> {
+    JsonRpcClient::new(SuiteTransport::new(
+        mainnet::network::PATHFINDER,
+        &config.pathfinder,
     ))
 }
 
 #[fixture]
-pub fn juno(config: TestConfig) -> JsonRpcClient<HttpTransport> {
-    JsonRpcClient::new(HttpTransport::new(
-        Url::parse(&config.juno).expect("Error parsing Juno node url"),
-    ))
+pub fn juno(config: TestConfig) -> JsonRpcClient<SuiteTransport> {
+    JsonRpcClient::new(SuiteTransport::new(mainnet::network::JUNO, &config.juno))
 }
 
 #[fixture]
 pub fn clients(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
-    juno: JsonRpcClient<HttpTransport>,
-) -> HashMap<String, JsonRpcClient<HttpTransport>> {
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
+    juno: JsonRpcClient<SuiteTransport>,
+) -> HashMap<String, JsonRpcClient<SuiteTransport>> {
     map! {
         String::from(mainnet::network::DEOXYS) => deoxys,
         String::from(mainnet::network::PATHFINDER) => pathfinder,
         String::from(mainnet::network::JUNO) => juno,
     }
 }
+
+///
+/// Node urls by node name, for tests sending raw HTTP requests.
+///
+#[fixture]
+pub fn urls(config: TestConfig) -> HashMap<String, String> {
+    map! {
+        String::from(mainnet::network::DEOXYS) => config.deoxys,
+        String::from(mainnet::network::PATHFINDER) => config.pathfinder,
+        String::from(mainnet::network::JUNO) => config.juno,
+    }
+}
+
+#[fixture]
+pub fn transports(config: TestConfig) -> HashMap<String, SuiteTransport> {
+    map! {
+        String::from(mainnet::network::DEOXYS) =>
+            SuiteTransport::new(mainnet::network::DEOXYS, &config.deoxys),
+        String::from(mainnet::network::PATHFINDER) =>
+            SuiteTransport::new(mainnet::network::PATHFINDER, &config.pathfinder),
+        String::from(mainnet::network::JUNO) =>
+            SuiteTransport::new(mainnet::network::JUNO, &config.juno),
+    }
+}
+
+///
+/// Same clients as [`clients`], recording all of their traffic to the corpus file.
+///
+#[fixture]
+pub fn recording_clients(
+    transports: HashMap<String, SuiteTransport>,
+) -> HashMap<String, JsonRpcClient<RecordingTransport<SuiteTransport>>> {
+    transports
+        .into_iter()
+        .map(|(node, transport)| {
+            let client = JsonRpcClient::new(RecordingTransport::new(&node, transport));
+            (node, client)
+        })
+        .collect()
+}
@@ -0,0 +1,227 @@
+use anyhow::anyhow;
+use starknet_core::crypto::compute_hash_on_elements;
+use starknet_core::types::{BlockWithReceipts, Event, FieldElement, L1DataAvailabilityMode};
+use starknet_core::utils::cairo_short_string_to_felt;
+use starknet_crypto::poseidon_hash_many;
+
+use crate::constants::mainnet::block::{BLOCK_0_11_1, BLOCK_0_7_0};
+use crate::verify::events::receipt_events;
+
+use super::commitment::{
+    event_commitment_pedersen, event_commitment_poseidon, transaction_commitment_pedersen,
+    transaction_commitment_poseidon,
+};
+
+///
+/// Block hash algorithm which applies to a range of Starknet versions.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockHashScheme {
+    /// Pedersen hash chain including the chain id, without sequencer, timestamp or events.
+    V0_0,
+    /// Pedersen hash chain over the full header, committing to invoke signatures only.
+    V0_7,
+    /// Pedersen hash chain over the full header, committing to every signature.
+    V0_11_1,
+    /// Poseidon hash over the full header, state diff and receipt commitments.
+    V0_13_2,
+    /// Poseidon hash tagged `STARKNET_BLOCK_HASH1`, committing to L2 gas prices as well. RPC
+    /// v0.7 headers do not expose those prices, so these blocks cannot be hashed locally.
+    V0_13_4,
+}
+
+///
+/// Parses the `major.minor.patch` prefix of a Starknet version string.
+///
+fn parse_version(starknet_version: &str) -> Option<(u64, u64, u64)> {
+    let mut parts = starknet_version.split('.').map(str::parse::<u64>);
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(major)), Some(Ok(minor)), patch) => {
+            Some((major, minor, patch.and_then(Result::ok).unwrap_or(0)))
+        }
+        _ => None,
+    }
+}
+
+impl BlockHashScheme {
+    ///
+    /// Selects the scheme of a mainnet block. Version ranges before v0.13.2 are taken from
+    /// [`crate::constants::mainnet::block`], later ones from the header version.
+    ///
+    pub fn for_block(block_number: u64, starknet_version: &str) -> Self {
+        let version = parse_version(starknet_version).unwrap_or_default();
+        if block_number < BLOCK_0_7_0 {
+            BlockHashScheme::V0_0
+        } else if block_number < BLOCK_0_11_1 {
+            BlockHashScheme::V0_7
+        } else if version >= (0, 13, 4) {
+            BlockHashScheme::V0_13_4
+        } else if version >= (0, 13, 2) {
+            BlockHashScheme::V0_13_2
+        } else {
+            BlockHashScheme::V0_11_1
+        }
+    }
+}
+
+///
+/// Commitments included in a v0.13.2 block hash, which cannot all be derived from the block
+/// alone.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockCommitments {
+    pub transaction_commitment: FieldElement,
+    pub event_commitment: FieldElement,
+    pub receipt_commitment: FieldElement,
+    pub state_diff_commitment: FieldElement,
+    pub state_diff_length: u64,
+}
+
+///
+/// Returns every event of a block along with the hash of the transaction which emitted it.
+///
+pub fn block_events(block: &BlockWithReceipts) -> Vec<(FieldElement, &Event)> {
+    block
+        .transactions
+        .iter()
+        .flat_map(|transaction| {
+            let (transaction_hash, events) = receipt_events(&transaction.receipt);
+            events.iter().map(move |event| (transaction_hash, event))
+        })
+        .collect()
+}
+
+///
+/// Computes the transaction and event commitments of a block with the scheme that applies to it.
+///
+pub fn block_commitments(
+    block: &BlockWithReceipts,
+    scheme: BlockHashScheme,
+) -> (FieldElement, FieldElement) {
+    let transactions: Vec<_> = block
+        .transactions
+        .iter()
+        .map(|transaction| {
+            (
+                receipt_events(&transaction.receipt).0,
+                &transaction.transaction,
+            )
+        })
+        .collect();
+    let events = block_events(block);
+
+    match scheme {
+        BlockHashScheme::V0_0 | BlockHashScheme::V0_7 => (
+            transaction_commitment_pedersen(&transactions, false),
+            event_commitment_pedersen(&events),
+        ),
+        BlockHashScheme::V0_11_1 => (
+            transaction_commitment_pedersen(&transactions, true),
+            event_commitment_pedersen(&events),
+        ),
+        BlockHashScheme::V0_13_2 | BlockHashScheme::V0_13_4 => (
+            transaction_commitment_poseidon(&transactions),
+            event_commitment_poseidon(&events),
+        ),
+    }
+}
+
+///
+/// Computes the hash of a block from before v0.13.2.
+///
+/// v0.13.2 blocks also commit to their receipts and state diff: use [`block_hash_v0_13_2`]
+/// for them.
+///
+pub fn block_hash(
+    block: &BlockWithReceipts,
+    chain_id: FieldElement,
+) -> anyhow::Result<FieldElement> {
+    let scheme = BlockHashScheme::for_block(block.block_number, &block.starknet_version);
+    let (transaction_commitment, event_commitment) = block_commitments(block, scheme);
+    let transaction_count = FieldElement::from(block.transactions.len());
+    let event_count = FieldElement::from(block_events(block).len());
+
+    match scheme {
+        BlockHashScheme::V0_0 => Ok(compute_hash_on_elements(&[
+            FieldElement::from(block.block_number),
+            block.new_root,
+            FieldElement::ZERO,
+            FieldElement::ZERO,
+            transaction_count,
+            transaction_commitment,
+            FieldElement::ZERO,
+            FieldElement::ZERO,
+            FieldElement::ZERO,
+            FieldElement::ZERO,
+            chain_id,
+            block.parent_hash,
+        ])),
+        BlockHashScheme::V0_7 | BlockHashScheme::V0_11_1 => Ok(compute_hash_on_elements(&[
+            FieldElement::from(block.block_number),
+            block.new_root,
+            block.sequencer_address,
+            FieldElement::from(block.timestamp),
+            transaction_count,
+            transaction_commitment,
+            event_count,
+            event_commitment,
+            FieldElement::ZERO,
+            FieldElement::ZERO,
+            block.parent_hash,
+        ])),
+        BlockHashScheme::V0_13_2 => Err(anyhow!(
+            "Block {} needs receipt and state diff commitments to be hashed",
+            block.block_number
+        )),
+        BlockHashScheme::V0_13_4 => Err(anyhow!(
+            "Block {} commits to L2 gas prices, which RPC v0.7 does not expose",
+            block.block_number
+        )),
+    }
+}
+
+///
+/// Computes the hash of a v0.13.2 or v0.13.3 block, given its commitments.
+///
+pub fn block_hash_v0_13_2(
+    block: &BlockWithReceipts,
+    commitments: &BlockCommitments,
+) -> anyhow::Result<FieldElement> {
+    let scheme = BlockHashScheme::for_block(block.block_number, &block.starknet_version);
+    if scheme != BlockHashScheme::V0_13_2 {
+        return Err(anyhow!(
+            "Block {} ({}) is not hashed with the v0.13.2 scheme",
+            block.block_number,
+            block.starknet_version
+        ));
+    }
+
+    let event_count = block_events(block).len() as u64;
+    let mut counts = [0u8; 32];
+    counts[0..8].copy_from_slice(&(block.transactions.len() as u64).to_be_bytes());
+    counts[8..16].copy_from_slice(&event_count.to_be_bytes());
+    counts[16..24].copy_from_slice(&commitments.state_diff_length.to_be_bytes());
+    if block.l1_da_mode == L1DataAvailabilityMode::Blob {
+        counts[24] = 0x80;
+    }
+
+    Ok(poseidon_hash_many(&[
+        cairo_short_string_to_felt("STARKNET_BLOCK_HASH0")?,
+        FieldElement::from(block.block_number),
+        block.new_root,
+        block.sequencer_address,
+        FieldElement::from(block.timestamp),
+        FieldElement::from_bytes_be(&counts)?,
+        commitments.state_diff_commitment,
+        commitments.transaction_commitment,
+        commitments.event_commitment,
+        commitments.receipt_commitment,
+        block.l1_gas_price.price_in_wei,
+        block.l1_gas_price.price_in_fri,
+        block.l1_data_gas_price.price_in_wei,
+        block.l1_data_gas_price.price_in_fri,
+        cairo_short_string_to_felt(&block.starknet_version)?,
+        FieldElement::ZERO,
+        block.parent_hash,
+    ]))
+}
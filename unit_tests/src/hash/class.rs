@@ -0,0 +1,55 @@
+use anyhow::anyhow;
+use starknet_core::types::{ContractClass, FieldElement, FlattenedSierraClass, SierraEntryPoint};
+use starknet_core::utils::{cairo_short_string_to_felt, starknet_keccak};
+use starknet_crypto::poseidon_hash_many;
+
+fn entry_points_hash(entry_points: &[SierraEntryPoint]) -> FieldElement {
+    let elements: Vec<FieldElement> = entry_points
+        .iter()
+        .flat_map(|entry_point| {
+            [
+                entry_point.selector,
+                FieldElement::from(entry_point.function_idx),
+            ]
+        })
+        .collect();
+
+    poseidon_hash_many(&elements)
+}
+
+///
+/// Computes the Poseidon class hash of a Sierra class from its entry points, ABI and program.
+///
+pub fn sierra_class_hash(class: &FlattenedSierraClass) -> anyhow::Result<FieldElement> {
+    let version =
+        cairo_short_string_to_felt(&format!("CONTRACT_CLASS_V{}", class.contract_class_version))?;
+
+    Ok(poseidon_hash_many(&[
+        version,
+        entry_points_hash(&class.entry_points_by_type.external),
+        entry_points_hash(&class.entry_points_by_type.l1_handler),
+        entry_points_hash(&class.entry_points_by_type.constructor),
+        starknet_keccak(class.abi.as_bytes()),
+        poseidon_hash_many(&class.sierra_program),
+    ]))
+}
+
+///
+/// Computes the hash of a class as returned by `starknet_getClass`.
+///
+/// Legacy classes are served with a compressed program, which is decompressed to compute the
+/// Cairo 0 hinted class hash.
+///
+pub fn class_hash(class: &ContractClass) -> anyhow::Result<FieldElement> {
+    match class {
+        ContractClass::Sierra(class) => sierra_class_hash(class),
+        ContractClass::Legacy(class) => {
+            let class = class
+                .decompress()
+                .map_err(|e| anyhow!("Failed to decompress legacy program: {e}"))?;
+            class
+                .class_hash()
+                .map_err(|e| anyhow!("Failed to compute legacy class hash: {e}"))
+        }
+    }
+}
@@ -0,0 +1,376 @@
+use starknet_core::crypto::compute_hash_on_elements;
+use std::collections::{BTreeMap, BTreeSet};
+
+use starknet_core::types::{
+    DeclareTransaction, DeployAccountTransaction, Event, ExecutionResult, FieldElement,
+    InvokeTransaction, MsgToL1, StateDiff, Transaction, TransactionReceipt,
+};
+use starknet_core::utils::{cairo_short_string_to_felt, starknet_keccak};
+use starknet_crypto::{pedersen_hash, poseidon_hash, poseidon_hash_many};
+
+///
+/// Height of the Patricia-Merkle trees used for block commitments.
+///
+const COMMITMENT_TREE_HEIGHT: u32 = 64;
+
+///
+/// Hash function used by a commitment tree.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashFunction {
+    Pedersen,
+    Poseidon,
+}
+
+impl HashFunction {
+    pub fn hash(&self, x: FieldElement, y: FieldElement) -> FieldElement {
+        match self {
+            HashFunction::Pedersen => pedersen_hash(&x, &y),
+            HashFunction::Poseidon => poseidon_hash(x, y),
+        }
+    }
+}
+
+///
+/// Returns the bottom hash of a subtree along with the edge path leading to it. The path is
+/// empty when the subtree starts with a binary node.
+///
+fn subtree(
+    leaves: &[(u64, FieldElement)],
+    height: u32,
+    hash_function: HashFunction,
+) -> (FieldElement, u64, u32) {
+    if height == 0 {
+        return (leaves[0].1, 0, 0);
+    }
+
+    let bit = 1u64 << (height - 1);
+    let (left, right) = leaves.split_at(leaves.partition_point(|(key, _)| key & bit == 0));
+
+    match (left.is_empty(), right.is_empty()) {
+        (false, false) => {
+            let left = node_hash(subtree(left, height - 1, hash_function), hash_function);
+            let right = node_hash(subtree(right, height - 1, hash_function), hash_function);
+            (hash_function.hash(left, right), 0, 0)
+        }
+        (true, _) => {
+            let (child, path, length) = subtree(right, height - 1, hash_function);
+            (child, (1 << length) | path, length + 1)
+        }
+        (_, true) => {
+            let (child, path, length) = subtree(left, height - 1, hash_function);
+            (child, path, length + 1)
+        }
+    }
+}
+
+fn node_hash(
+    (child, path, length): (FieldElement, u64, u32),
+    hash_function: HashFunction,
+) -> FieldElement {
+    if length == 0 {
+        child
+    } else {
+        hash_function.hash(child, FieldElement::from(path)) + FieldElement::from(length)
+    }
+}
+
+///
+/// Computes the root of a height 64 Patricia-Merkle tree where each value is stored at the key
+/// of its index.
+///
+pub fn patricia_root(values: &[FieldElement], hash_function: HashFunction) -> FieldElement {
+    if values.is_empty() {
+        return FieldElement::ZERO;
+    }
+
+    let leaves: Vec<(u64, FieldElement)> = values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| (index as u64, *value))
+        .collect();
+
+    node_hash(
+        subtree(&leaves, COMMITMENT_TREE_HEIGHT, hash_function),
+        hash_function,
+    )
+}
+
+///
+/// Returns the signature of a transaction, which is empty for transaction types that are not
+/// signed.
+///
+pub fn transaction_signature(transaction: &Transaction) -> &[FieldElement] {
+    match transaction {
+        Transaction::Invoke(InvokeTransaction::V0(tx)) => &tx.signature,
+        Transaction::Invoke(InvokeTransaction::V1(tx)) => &tx.signature,
+        Transaction::Invoke(InvokeTransaction::V3(tx)) => &tx.signature,
+        Transaction::Declare(DeclareTransaction::V0(tx)) => &tx.signature,
+        Transaction::Declare(DeclareTransaction::V1(tx)) => &tx.signature,
+        Transaction::Declare(DeclareTransaction::V2(tx)) => &tx.signature,
+        Transaction::Declare(DeclareTransaction::V3(tx)) => &tx.signature,
+        Transaction::DeployAccount(DeployAccountTransaction::V1(tx)) => &tx.signature,
+        Transaction::DeployAccount(DeployAccountTransaction::V3(tx)) => &tx.signature,
+        Transaction::Deploy(_) | Transaction::L1Handler(_) => &[],
+    }
+}
+
+///
+/// Computes a Pedersen transaction commitment, used by blocks before v0.13.2.
+///
+/// Before v0.11.1 only the signature of invoke transactions was committed to.
+///
+pub fn transaction_commitment_pedersen(
+    transactions: &[(FieldElement, &Transaction)],
+    all_signatures: bool,
+) -> FieldElement {
+    let leaves: Vec<FieldElement> = transactions
+        .iter()
+        .map(|(transaction_hash, transaction)| {
+            let signature = match transaction {
+                Transaction::Invoke(_) => transaction_signature(transaction),
+                _ if all_signatures => transaction_signature(transaction),
+                _ => &[],
+            };
+            pedersen_hash(transaction_hash, &compute_hash_on_elements(signature))
+        })
+        .collect();
+
+    patricia_root(&leaves, HashFunction::Pedersen)
+}
+
+///
+/// Computes a Poseidon transaction commitment, used by blocks from v0.13.2 onward.
+///
+pub fn transaction_commitment_poseidon(
+    transactions: &[(FieldElement, &Transaction)],
+) -> FieldElement {
+    let leaves: Vec<FieldElement> = transactions
+        .iter()
+        .map(|(transaction_hash, transaction)| {
+            let signature = transaction_signature(transaction);
+            if signature.is_empty() {
+                poseidon_hash_many(&[*transaction_hash, FieldElement::ZERO])
+            } else {
+                poseidon_hash_many(&[&[*transaction_hash][..], signature].concat())
+            }
+        })
+        .collect();
+
+    patricia_root(&leaves, HashFunction::Poseidon)
+}
+
+///
+/// Computes a Pedersen event commitment, used by blocks before v0.13.2.
+///
+pub fn event_commitment_pedersen(events: &[(FieldElement, &Event)]) -> FieldElement {
+    let leaves: Vec<FieldElement> = events
+        .iter()
+        .map(|(_, event)| {
+            compute_hash_on_elements(&[
+                event.from_address,
+                compute_hash_on_elements(&event.keys),
+                compute_hash_on_elements(&event.data),
+            ])
+        })
+        .collect();
+
+    patricia_root(&leaves, HashFunction::Pedersen)
+}
+
+///
+/// Computes a Poseidon event commitment, used by blocks from v0.13.2 onward. Each event is
+/// committed to along with the hash of the transaction which emitted it.
+///
+pub fn event_commitment_poseidon(events: &[(FieldElement, &Event)]) -> FieldElement {
+    let leaves: Vec<FieldElement> = events
+        .iter()
+        .map(|(transaction_hash, event)| {
+            let mut elements = vec![
+                event.from_address,
+                *transaction_hash,
+                FieldElement::from(event.keys.len()),
+            ];
+            elements.extend(&event.keys);
+            elements.push(FieldElement::from(event.data.len()));
+            elements.extend(&event.data);
+            poseidon_hash_many(&elements)
+        })
+        .collect();
+
+    patricia_root(&leaves, HashFunction::Poseidon)
+}
+
+///
+/// Gas consumed by a transaction, which receipts commit to from v0.13.2 onward.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReceiptGas {
+    pub l1_gas: FieldElement,
+    pub l1_data_gas: FieldElement,
+}
+
+fn receipt_fields(
+    receipt: &TransactionReceipt,
+) -> (FieldElement, FieldElement, &[MsgToL1], &ExecutionResult) {
+    match receipt {
+        TransactionReceipt::Invoke(r) => (
+            r.transaction_hash,
+            r.actual_fee.amount,
+            &r.messages_sent,
+            &r.execution_result,
+        ),
+        TransactionReceipt::L1Handler(r) => (
+            r.transaction_hash,
+            r.actual_fee.amount,
+            &r.messages_sent,
+            &r.execution_result,
+        ),
+        TransactionReceipt::Declare(r) => (
+            r.transaction_hash,
+            r.actual_fee.amount,
+            &r.messages_sent,
+            &r.execution_result,
+        ),
+        TransactionReceipt::Deploy(r) => (
+            r.transaction_hash,
+            r.actual_fee.amount,
+            &r.messages_sent,
+            &r.execution_result,
+        ),
+        TransactionReceipt::DeployAccount(r) => (
+            r.transaction_hash,
+            r.actual_fee.amount,
+            &r.messages_sent,
+            &r.execution_result,
+        ),
+    }
+}
+
+///
+/// Computes a Poseidon receipt commitment, used by blocks from v0.13.2 onward.
+///
+pub fn receipt_commitment(receipts: &[(&TransactionReceipt, ReceiptGas)]) -> FieldElement {
+    let leaves: Vec<FieldElement> = receipts
+        .iter()
+        .map(|(receipt, gas)| {
+            let (transaction_hash, actual_fee, messages, execution_result) =
+                receipt_fields(receipt);
+
+            let mut message_elements = vec![FieldElement::from(messages.len())];
+            for message in messages {
+                message_elements.push(message.from_address);
+                message_elements.push(message.to_address);
+                message_elements.push(FieldElement::from(message.payload.len()));
+                message_elements.extend(&message.payload);
+            }
+
+            let revert_reason = match execution_result {
+                ExecutionResult::Succeeded => FieldElement::ZERO,
+                ExecutionResult::Reverted { reason } => starknet_keccak(reason.as_bytes()),
+            };
+
+            poseidon_hash_many(&[
+                transaction_hash,
+                actual_fee,
+                poseidon_hash_many(&message_elements),
+                revert_reason,
+                poseidon_hash_many(&[FieldElement::ZERO, gas.l1_gas, gas.l1_data_gas]),
+            ])
+        })
+        .collect();
+
+    patricia_root(&leaves, HashFunction::Poseidon)
+}
+
+///
+/// Counts the entries of a state diff, which blocks commit to from v0.13.2 onward.
+///
+pub fn state_diff_length(state_diff: &StateDiff) -> u64 {
+    let storage_entries: usize = state_diff
+        .storage_diffs
+        .iter()
+        .map(|diff| diff.storage_entries.len())
+        .sum();
+
+    (storage_entries
+        + state_diff.deprecated_declared_classes.len()
+        + state_diff.declared_classes.len()
+        + state_diff.deployed_contracts.len()
+        + state_diff.replaced_classes.len()
+        + state_diff.nonces.len()) as u64
+}
+
+///
+/// Computes the Poseidon state diff commitment, used by blocks from v0.13.2 onward. Every part
+/// of the state diff is sorted before being hashed.
+///
+pub fn state_diff_commitment(state_diff: &StateDiff) -> FieldElement {
+    let mut elements =
+        vec![cairo_short_string_to_felt("STARKNET_STATE_DIFF0").expect("Invalid short string")];
+
+    let deployed: BTreeMap<FieldElement, FieldElement> = state_diff
+        .deployed_contracts
+        .iter()
+        .map(|deployed| (deployed.address, deployed.class_hash))
+        .chain(
+            state_diff
+                .replaced_classes
+                .iter()
+                .map(|replaced| (replaced.contract_address, replaced.class_hash)),
+        )
+        .collect();
+    elements.push(FieldElement::from(deployed.len()));
+    for (address, class_hash) in deployed {
+        elements.extend([address, class_hash]);
+    }
+
+    let declared: BTreeSet<(FieldElement, FieldElement)> = state_diff
+        .declared_classes
+        .iter()
+        .map(|declared| (declared.class_hash, declared.compiled_class_hash))
+        .collect();
+    elements.push(FieldElement::from(declared.len()));
+    for (class_hash, compiled_class_hash) in declared {
+        elements.extend([class_hash, compiled_class_hash]);
+    }
+
+    let deprecated: BTreeSet<FieldElement> = state_diff
+        .deprecated_declared_classes
+        .iter()
+        .copied()
+        .collect();
+    elements.push(FieldElement::from(deprecated.len()));
+    elements.extend(deprecated);
+
+    // Data availability mode, which is always L1
+    elements.extend([FieldElement::ONE, FieldElement::ZERO]);
+
+    let mut storage: BTreeMap<FieldElement, BTreeMap<FieldElement, FieldElement>> = BTreeMap::new();
+    for diff in &state_diff.storage_diffs {
+        storage.entry(diff.address).or_default().extend(
+            diff.storage_entries
+                .iter()
+                .map(|entry| (entry.key, entry.value)),
+        );
+    }
+    elements.push(FieldElement::from(storage.len()));
+    for (address, entries) in storage {
+        elements.extend([address, FieldElement::from(entries.len())]);
+        for (key, value) in entries {
+            elements.extend([key, value]);
+        }
+    }
+
+    let nonces: BTreeMap<FieldElement, FieldElement> = state_diff
+        .nonces
+        .iter()
+        .map(|nonce| (nonce.contract_address, nonce.nonce))
+        .collect();
+    elements.push(FieldElement::from(nonces.len()));
+    for (address, nonce) in nonces {
+        elements.extend([address, nonce]);
+    }
+
+    poseidon_hash_many(&elements)
+}
@@ -0,0 +1,4 @@
+pub mod block;
+pub mod class;
+pub mod commitment;
+pub mod transaction;
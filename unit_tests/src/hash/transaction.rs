@@ -0,0 +1,536 @@
+use starknet_core::crypto::compute_hash_on_elements;
+use starknet_core::types::{
+    BroadcastedDeclareTransactionV1, BroadcastedDeclareTransactionV2,
+    BroadcastedDeclareTransactionV3, BroadcastedDeployAccountTransactionV1,
+    BroadcastedDeployAccountTransactionV3, BroadcastedInvokeTransactionV1,
+    BroadcastedInvokeTransactionV3, DataAvailabilityMode, DeclareTransaction,
+    DeployAccountTransaction, FieldElement, InvokeTransaction, ResourceBounds,
+    ResourceBoundsMapping, Transaction,
+};
+use starknet_core::utils::{
+    cairo_short_string_to_felt, get_contract_address, get_selector_from_name,
+};
+use starknet_crypto::poseidon_hash_many;
+
+fn short_string(value: &str) -> FieldElement {
+    cairo_short_string_to_felt(value).expect("Invalid short string")
+}
+
+///
+/// Hashes a transaction with the Pedersen scheme used before v3 transactions.
+///
+#[allow(clippy::too_many_arguments)]
+fn pedersen_transaction_hash(
+    prefix: &str,
+    version: FieldElement,
+    address: FieldElement,
+    entry_point_selector: FieldElement,
+    calldata: &[FieldElement],
+    max_fee: FieldElement,
+    chain_id: FieldElement,
+    additional_data: &[FieldElement],
+) -> FieldElement {
+    let mut elements = vec![
+        short_string(prefix),
+        version,
+        address,
+        entry_point_selector,
+        compute_hash_on_elements(calldata),
+        max_fee,
+        chain_id,
+    ];
+    elements.extend(additional_data);
+    compute_hash_on_elements(&elements)
+}
+
+///
+/// Hashes a transaction from before Starknet v0.8.0, which committed to neither a version nor
+/// a max fee.
+///
+fn legacy_transaction_hash(
+    prefix: &str,
+    address: FieldElement,
+    entry_point_selector: FieldElement,
+    calldata: &[FieldElement],
+    chain_id: FieldElement,
+) -> FieldElement {
+    compute_hash_on_elements(&[
+        short_string(prefix),
+        address,
+        entry_point_selector,
+        compute_hash_on_elements(calldata),
+        chain_id,
+    ])
+}
+
+fn resource_bound(name: &[u8], bound: &ResourceBounds) -> FieldElement {
+    let mut bytes = [0u8; 32];
+    bytes[8 - name.len()..8].copy_from_slice(name);
+    bytes[8..16].copy_from_slice(&bound.max_amount.to_be_bytes());
+    bytes[16..32].copy_from_slice(&bound.max_price_per_unit.to_be_bytes());
+    FieldElement::from_bytes_be(&bytes).expect("Resource bound does not fit in a felt")
+}
+
+fn data_availability_mode(mode: &DataAvailabilityMode) -> u64 {
+    match mode {
+        DataAvailabilityMode::L1 => 0,
+        DataAvailabilityMode::L2 => 1,
+    }
+}
+
+///
+/// Fields shared by the Poseidon hash of every v3 transaction.
+///
+struct V3Fields<'a> {
+    version: FieldElement,
+    sender_address: FieldElement,
+    nonce: FieldElement,
+    tip: u64,
+    resource_bounds: &'a ResourceBoundsMapping,
+    paymaster_data: &'a [FieldElement],
+    nonce_data_availability_mode: &'a DataAvailabilityMode,
+    fee_data_availability_mode: &'a DataAvailabilityMode,
+}
+
+///
+/// Hashes a v3 transaction with the Poseidon scheme introduced in Starknet v0.13.0.
+///
+fn poseidon_transaction_hash(
+    prefix: &str,
+    fields: V3Fields,
+    chain_id: FieldElement,
+    additional_data: &[FieldElement],
+) -> FieldElement {
+    let fee_fields = poseidon_hash_many(&[
+        FieldElement::from(fields.tip),
+        resource_bound(b"L1_GAS", &fields.resource_bounds.l1_gas),
+        resource_bound(b"L2_GAS", &fields.resource_bounds.l2_gas),
+    ]);
+    let data_availability_modes = (data_availability_mode(fields.nonce_data_availability_mode)
+        << 32)
+        + data_availability_mode(fields.fee_data_availability_mode);
+
+    let mut elements = vec![
+        short_string(prefix),
+        fields.version,
+        fields.sender_address,
+        fee_fields,
+        poseidon_hash_many(fields.paymaster_data),
+        chain_id,
+        fields.nonce,
+        FieldElement::from(data_availability_modes),
+    ];
+    elements.extend(additional_data);
+    poseidon_hash_many(&elements)
+}
+
+///
+/// Computes the hashes a transaction could have been sequenced with on the network identified
+/// by `chain_id`.
+///
+/// Most transactions have a single possible hash. Invoke v0, deploy and L1 handler
+/// transactions were hashed differently by earlier Starknet versions, so every historical
+/// variant is returned and the served hash must match one of them.
+///
+pub fn transaction_hashes(transaction: &Transaction, chain_id: FieldElement) -> Vec<FieldElement> {
+    match transaction {
+        Transaction::Invoke(InvokeTransaction::V0(tx)) => vec![
+            pedersen_transaction_hash(
+                "invoke",
+                FieldElement::ZERO,
+                tx.contract_address,
+                tx.entry_point_selector,
+                &tx.calldata,
+                tx.max_fee,
+                chain_id,
+                &[],
+            ),
+            legacy_transaction_hash(
+                "invoke",
+                tx.contract_address,
+                tx.entry_point_selector,
+                &tx.calldata,
+                chain_id,
+            ),
+        ],
+        Transaction::Invoke(InvokeTransaction::V1(tx)) => vec![pedersen_transaction_hash(
+            "invoke",
+            FieldElement::ONE,
+            tx.sender_address,
+            FieldElement::ZERO,
+            &tx.calldata,
+            tx.max_fee,
+            chain_id,
+            &[tx.nonce],
+        )],
+        Transaction::Invoke(InvokeTransaction::V3(tx)) => vec![poseidon_transaction_hash(
+            "invoke",
+            V3Fields {
+                version: FieldElement::THREE,
+                sender_address: tx.sender_address,
+                nonce: tx.nonce,
+                tip: tx.tip,
+                resource_bounds: &tx.resource_bounds,
+                paymaster_data: &tx.paymaster_data,
+                nonce_data_availability_mode: &tx.nonce_data_availability_mode,
+                fee_data_availability_mode: &tx.fee_data_availability_mode,
+            },
+            chain_id,
+            &[
+                poseidon_hash_many(&tx.account_deployment_data),
+                poseidon_hash_many(&tx.calldata),
+            ],
+        )],
+        Transaction::Declare(DeclareTransaction::V0(tx)) => vec![pedersen_transaction_hash(
+            "declare",
+            FieldElement::ZERO,
+            tx.sender_address,
+            FieldElement::ZERO,
+            &[],
+            tx.max_fee,
+            chain_id,
+            &[tx.class_hash],
+        )],
+        Transaction::Declare(DeclareTransaction::V1(tx)) => vec![pedersen_transaction_hash(
+            "declare",
+            FieldElement::ONE,
+            tx.sender_address,
+            FieldElement::ZERO,
+            &[tx.class_hash],
+            tx.max_fee,
+            chain_id,
+            &[tx.nonce],
+        )],
+        Transaction::Declare(DeclareTransaction::V2(tx)) => vec![pedersen_transaction_hash(
+            "declare",
+            FieldElement::TWO,
+            tx.sender_address,
+            FieldElement::ZERO,
+            &[tx.class_hash],
+            tx.max_fee,
+            chain_id,
+            &[tx.nonce, tx.compiled_class_hash],
+        )],
+        Transaction::Declare(DeclareTransaction::V3(tx)) => vec![poseidon_transaction_hash(
+            "declare",
+            V3Fields {
+                version: FieldElement::THREE,
+                sender_address: tx.sender_address,
+                nonce: tx.nonce,
+                tip: tx.tip,
+                resource_bounds: &tx.resource_bounds,
+                paymaster_data: &tx.paymaster_data,
+                nonce_data_availability_mode: &tx.nonce_data_availability_mode,
+                fee_data_availability_mode: &tx.fee_data_availability_mode,
+            },
+            chain_id,
+            &[
+                poseidon_hash_many(&tx.account_deployment_data),
+                tx.class_hash,
+                tx.compiled_class_hash,
+            ],
+        )],
+        Transaction::DeployAccount(DeployAccountTransaction::V1(tx)) => {
+            let contract_address = get_contract_address(
+                tx.contract_address_salt,
+                tx.class_hash,
+                &tx.constructor_calldata,
+                FieldElement::ZERO,
+            );
+            let calldata = [
+                &[tx.class_hash, tx.contract_address_salt][..],
+                &tx.constructor_calldata,
+            ]
+            .concat();
+
+            vec![pedersen_transaction_hash(
+                "deploy_account",
+                FieldElement::ONE,
+                contract_address,
+                FieldElement::ZERO,
+                &calldata,
+                tx.max_fee,
+                chain_id,
+                &[tx.nonce],
+            )]
+        }
+        Transaction::DeployAccount(DeployAccountTransaction::V3(tx)) => {
+            let contract_address = get_contract_address(
+                tx.contract_address_salt,
+                tx.class_hash,
+                &tx.constructor_calldata,
+                FieldElement::ZERO,
+            );
+
+            vec![poseidon_transaction_hash(
+                "deploy_account",
+                V3Fields {
+                    version: FieldElement::THREE,
+                    sender_address: contract_address,
+                    nonce: tx.nonce,
+                    tip: tx.tip,
+                    resource_bounds: &tx.resource_bounds,
+                    paymaster_data: &tx.paymaster_data,
+                    nonce_data_availability_mode: &tx.nonce_data_availability_mode,
+                    fee_data_availability_mode: &tx.fee_data_availability_mode,
+                },
+                chain_id,
+                &[
+                    poseidon_hash_many(&tx.constructor_calldata),
+                    tx.class_hash,
+                    tx.contract_address_salt,
+                ],
+            )]
+        }
+        Transaction::Deploy(tx) => {
+            let contract_address = get_contract_address(
+                tx.contract_address_salt,
+                tx.class_hash,
+                &tx.constructor_calldata,
+                FieldElement::ZERO,
+            );
+            let constructor_selector =
+                get_selector_from_name("constructor").expect("Invalid selector");
+
+            vec![
+                pedersen_transaction_hash(
+                    "deploy",
+                    tx.version,
+                    contract_address,
+                    constructor_selector,
+                    &tx.constructor_calldata,
+                    FieldElement::ZERO,
+                    chain_id,
+                    &[],
+                ),
+                legacy_transaction_hash(
+                    "deploy",
+                    contract_address,
+                    constructor_selector,
+                    &tx.constructor_calldata,
+                    chain_id,
+                ),
+            ]
+        }
+        Transaction::L1Handler(tx) => vec![
+            pedersen_transaction_hash(
+                "l1_handler",
+                tx.version,
+                tx.contract_address,
+                tx.entry_point_selector,
+                &tx.calldata,
+                FieldElement::ZERO,
+                chain_id,
+                &[FieldElement::from(tx.nonce)],
+            ),
+            pedersen_transaction_hash(
+                "invoke",
+                FieldElement::ZERO,
+                tx.contract_address,
+                tx.entry_point_selector,
+                &tx.calldata,
+                FieldElement::ZERO,
+                chain_id,
+                &[],
+            ),
+            legacy_transaction_hash(
+                "invoke",
+                tx.contract_address,
+                tx.entry_point_selector,
+                &tx.calldata,
+                chain_id,
+            ),
+        ],
+    }
+}
+
+///
+/// Version of a broadcasted transaction, offset by 2^128 for query-only transactions so that
+/// their signature can never be replayed on chain.
+///
+fn query_version(version: u64, is_query: bool) -> FieldElement {
+    if is_query {
+        FieldElement::from_hex_be("0x100000000000000000000000000000000").unwrap()
+            + FieldElement::from(version)
+    } else {
+        FieldElement::from(version)
+    }
+}
+
+fn v3_version(is_query: bool) -> FieldElement {
+    query_version(3, is_query)
+}
+
+///
+/// Computes the hash a broadcasted v1 invoke transaction is signed over.
+///
+pub fn invoke_v1_hash(tx: &BroadcastedInvokeTransactionV1, chain_id: FieldElement) -> FieldElement {
+    pedersen_transaction_hash(
+        "invoke",
+        query_version(1, tx.is_query),
+        tx.sender_address,
+        FieldElement::ZERO,
+        &tx.calldata,
+        tx.max_fee,
+        chain_id,
+        &[tx.nonce],
+    )
+}
+
+///
+/// Computes the hash a broadcasted v1 declare transaction is signed over. The transaction only
+/// carries the compressed legacy class, so its hash is passed in, as loaded with the class.
+///
+pub fn declare_v1_hash(
+    tx: &BroadcastedDeclareTransactionV1,
+    class_hash: FieldElement,
+    chain_id: FieldElement,
+) -> FieldElement {
+    pedersen_transaction_hash(
+        "declare",
+        query_version(1, tx.is_query),
+        tx.sender_address,
+        FieldElement::ZERO,
+        &[class_hash],
+        tx.max_fee,
+        chain_id,
+        &[tx.nonce],
+    )
+}
+
+///
+/// Computes the hash a broadcasted v2 declare transaction is signed over.
+///
+pub fn declare_v2_hash(
+    tx: &BroadcastedDeclareTransactionV2,
+    chain_id: FieldElement,
+) -> FieldElement {
+    pedersen_transaction_hash(
+        "declare",
+        query_version(2, tx.is_query),
+        tx.sender_address,
+        FieldElement::ZERO,
+        &[tx.contract_class.class_hash()],
+        tx.max_fee,
+        chain_id,
+        &[tx.nonce, tx.compiled_class_hash],
+    )
+}
+
+///
+/// Computes the hash a broadcasted v1 deploy account transaction is signed over.
+///
+pub fn deploy_account_v1_hash(
+    tx: &BroadcastedDeployAccountTransactionV1,
+    chain_id: FieldElement,
+) -> FieldElement {
+    let contract_address = get_contract_address(
+        tx.contract_address_salt,
+        tx.class_hash,
+        &tx.constructor_calldata,
+        FieldElement::ZERO,
+    );
+    let calldata = [
+        &[tx.class_hash, tx.contract_address_salt][..],
+        &tx.constructor_calldata,
+    ]
+    .concat();
+
+    pedersen_transaction_hash(
+        "deploy_account",
+        query_version(1, tx.is_query),
+        contract_address,
+        FieldElement::ZERO,
+        &calldata,
+        tx.max_fee,
+        chain_id,
+        &[tx.nonce],
+    )
+}
+
+///
+/// Computes the hash a broadcasted v3 invoke transaction is signed over.
+///
+pub fn invoke_v3_hash(tx: &BroadcastedInvokeTransactionV3, chain_id: FieldElement) -> FieldElement {
+    poseidon_transaction_hash(
+        "invoke",
+        V3Fields {
+            version: v3_version(tx.is_query),
+            sender_address: tx.sender_address,
+            nonce: tx.nonce,
+            tip: tx.tip,
+            resource_bounds: &tx.resource_bounds,
+            paymaster_data: &tx.paymaster_data,
+            nonce_data_availability_mode: &tx.nonce_data_availability_mode,
+            fee_data_availability_mode: &tx.fee_data_availability_mode,
+        },
+        chain_id,
+        &[
+            poseidon_hash_many(&tx.account_deployment_data),
+            poseidon_hash_many(&tx.calldata),
+        ],
+    )
+}
+
+///
+/// Computes the hash a broadcasted v3 declare transaction is signed over.
+///
+pub fn declare_v3_hash(
+    tx: &BroadcastedDeclareTransactionV3,
+    chain_id: FieldElement,
+) -> FieldElement {
+    poseidon_transaction_hash(
+        "declare",
+        V3Fields {
+            version: v3_version(tx.is_query),
+            sender_address: tx.sender_address,
+            nonce: tx.nonce,
+            tip: tx.tip,
+            resource_bounds: &tx.resource_bounds,
+            paymaster_data: &tx.paymaster_data,
+            nonce_data_availability_mode: &tx.nonce_data_availability_mode,
+            fee_data_availability_mode: &tx.fee_data_availability_mode,
+        },
+        chain_id,
+        &[
+            poseidon_hash_many(&tx.account_deployment_data),
+            tx.contract_class.class_hash(),
+            tx.compiled_class_hash,
+        ],
+    )
+}
+
+///
+/// Computes the hash a broadcasted v3 deploy account transaction is signed over.
+///
+pub fn deploy_account_v3_hash(
+    tx: &BroadcastedDeployAccountTransactionV3,
+    chain_id: FieldElement,
+) -> FieldElement {
+    let contract_address = get_contract_address(
+        tx.contract_address_salt,
+        tx.class_hash,
+        &tx.constructor_calldata,
+        FieldElement::ZERO,
+    );
+
+    poseidon_transaction_hash(
+        "deploy_account",
+        V3Fields {
+            version: v3_version(tx.is_query),
+            sender_address: contract_address,
+            nonce: tx.nonce,
+            tip: tx.tip,
+            resource_bounds: &tx.resource_bounds,
+            paymaster_data: &tx.paymaster_data,
+            nonce_data_availability_mode: &tx.nonce_data_availability_mode,
+            fee_data_availability_mode: &tx.fee_data_availability_mode,
+        },
+        chain_id,
+        &[
+            poseidon_hash_many(&tx.constructor_calldata),
+            tx.class_hash,
+            tx.contract_address_salt,
+        ],
+    )
+}
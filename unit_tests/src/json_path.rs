@@ -0,0 +1,235 @@
+use anyhow::anyhow;
+use serde_json::Value;
+
+///
+/// A single step of a JSON path.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Segment {
+    Key(String),
+    Index(usize),
+    Wildcard,
+    /// Elements of an array whose value at a relative path equals a literal, as in
+    /// `[?(@.type=='DEPLOY_ACCOUNT')]`.
+    Filter(Vec<Segment>, Value),
+}
+
+///
+/// Parses the subset of JSONPath used by Ditto: `$`, `.key`, `[index]`, `['key']`, `[*]` and
+/// `[?(@.key=='value')]`, as in `$.result.transactions[*].transaction_hash`.
+///
+pub fn parse(path: &str) -> anyhow::Result<Vec<Segment>> {
+    let rest = path
+        .strip_prefix('$')
+        .ok_or_else(|| anyhow!("JSON path '{path}' must start with '$'"))?;
+    parse_segments(rest, path)
+}
+
+fn parse_filter(filter: &str, path: &str) -> anyhow::Result<Segment> {
+    let (relative, literal) = filter
+        .strip_prefix('@')
+        .and_then(|filter| filter.split_once("=="))
+        .ok_or_else(|| anyhow!("Invalid filter '{filter}' in JSON path '{path}'"))?;
+
+    let literal = literal.trim();
+    let value = match literal
+        .strip_prefix('\'')
+        .and_then(|literal| literal.strip_suffix('\''))
+    {
+        Some(literal) => Value::String(literal.to_string()),
+        None => serde_json::from_str(literal)
+            .map_err(|_| anyhow!("Invalid literal '{literal}' in JSON path '{path}'"))?,
+    };
+
+    Ok(Segment::Filter(
+        parse_segments(relative.trim(), path)?,
+        value,
+    ))
+}
+
+fn parse_segments(rest: &str, path: &str) -> anyhow::Result<Vec<Segment>> {
+    let mut segments = vec![];
+    let mut chars = rest.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => {
+                let mut key = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c == '.' || c == '[' {
+                        break;
+                    }
+                    key.push(c);
+                    chars.next();
+                }
+                match key.as_str() {
+                    "" => return Err(anyhow!("Empty key in JSON path '{path}'")),
+                    "*" => segments.push(Segment::Wildcard),
+                    _ => segments.push(Segment::Key(key)),
+                }
+            }
+            '[' => {
+                let mut inner = String::new();
+                let mut quoted = false;
+                for c in chars.by_ref() {
+                    if c == ']' && !quoted {
+                        break;
+                    }
+                    if c == '\'' {
+                        quoted = !quoted;
+                    }
+                    inner.push(c);
+                }
+                let inner = inner.trim();
+                if inner == "*" {
+                    segments.push(Segment::Wildcard);
+                } else if let Some(filter) = inner
+                    .strip_prefix("?(")
+                    .and_then(|inner| inner.strip_suffix(')'))
+                {
+                    segments.push(parse_filter(filter.trim(), path)?);
+                } else if let Some(key) = inner
+                    .strip_prefix('\'')
+                    .and_then(|inner| inner.strip_suffix('\''))
+                {
+                    segments.push(Segment::Key(key.to_string()));
+                } else {
+                    let index = inner
+                        .parse()
+                        .map_err(|_| anyhow!("Invalid index '{inner}' in JSON path '{path}'"))?;
+                    segments.push(Segment::Index(index));
+                }
+            }
+            _ => return Err(anyhow!("Unexpected '{c}' in JSON path '{path}'")),
+        }
+    }
+
+    Ok(segments)
+}
+
+fn select_segments<'a>(value: &'a Value, segments: &[Segment], selected: &mut Vec<&'a Value>) {
+    let Some((segment, rest)) = segments.split_first() else {
+        selected.push(value);
+        return;
+    };
+
+    match (segment, value) {
+        (Segment::Key(key), Value::Object(map)) => {
+            if let Some(value) = map.get(key) {
+                select_segments(value, rest, selected);
+            }
+        }
+        (Segment::Index(index), Value::Array(values)) => {
+            if let Some(value) = values.get(*index) {
+                select_segments(value, rest, selected);
+            }
+        }
+        (Segment::Wildcard, Value::Array(values)) => {
+            for value in values {
+                select_segments(value, rest, selected);
+            }
+        }
+        (Segment::Wildcard, Value::Object(map)) => {
+            for value in map.values() {
+                select_segments(value, rest, selected);
+            }
+        }
+        (Segment::Filter(relative, literal), Value::Array(values)) => {
+            for value in values
+                .iter()
+                .filter(|value| matches(value, relative, literal))
+            {
+                select_segments(value, rest, selected);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn matches(value: &Value, relative: &[Segment], literal: &Value) -> bool {
+    let mut selected = vec![];
+    select_segments(value, relative, &mut selected);
+    selected.contains(&literal)
+}
+
+///
+/// Returns every value matched by a JSON path.
+///
+pub fn select<'a>(value: &'a Value, path: &str) -> anyhow::Result<Vec<&'a Value>> {
+    let mut selected = vec![];
+    select_segments(value, &parse(path)?, &mut selected);
+    Ok(selected)
+}
+
+///
+/// Returns the single value matched by a JSON path.
+///
+pub fn select_one<'a>(value: &'a Value, path: &str) -> anyhow::Result<&'a Value> {
+    match select(value, path)?.as_slice() {
+        [value] => Ok(value),
+        [] => Err(anyhow!("JSON path '{path}' matched nothing")),
+        values => Err(anyhow!(
+            "JSON path '{path}' matched {} values",
+            values.len()
+        )),
+    }
+}
+
+fn remove_segments(value: &mut Value, segments: &[Segment]) {
+    let Some((segment, rest)) = segments.split_first() else {
+        return;
+    };
+
+    match (segment, value) {
+        (Segment::Key(key), Value::Object(map)) if rest.is_empty() => {
+            map.remove(key);
+        }
+        (Segment::Key(key), Value::Object(map)) => {
+            if let Some(value) = map.get_mut(key) {
+                remove_segments(value, rest);
+            }
+        }
+        (Segment::Index(index), Value::Array(values)) if rest.is_empty() => {
+            if *index < values.len() {
+                values.remove(*index);
+            }
+        }
+        (Segment::Index(index), Value::Array(values)) => {
+            if let Some(value) = values.get_mut(*index) {
+                remove_segments(value, rest);
+            }
+        }
+        (Segment::Wildcard, Value::Array(values)) if rest.is_empty() => values.clear(),
+        (Segment::Wildcard, Value::Array(values)) => {
+            for value in values {
+                remove_segments(value, rest);
+            }
+        }
+        (Segment::Wildcard, Value::Object(map)) if rest.is_empty() => map.clear(),
+        (Segment::Wildcard, Value::Object(map)) => {
+            for value in map.values_mut() {
+                remove_segments(value, rest);
+            }
+        }
+        (Segment::Filter(relative, literal), Value::Array(values)) if rest.is_empty() => {
+            values.retain(|value| !matches(value, relative, literal));
+        }
+        (Segment::Filter(relative, literal), Value::Array(values)) => {
+            for value in values
+                .iter_mut()
+                .filter(|value| matches(value, relative, literal))
+            {
+                remove_segments(value, rest);
+            }
+        }
+        _ => {}
+    }
+}
+
+///
+/// Removes every value matched by a JSON path, so it is ignored by comparisons.
+///
+pub fn remove(value: &mut Value, path: &str) -> anyhow::Result<()> {
+    remove_segments(value, &parse(path)?);
+    Ok(())
+}
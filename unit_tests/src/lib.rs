@@ -1,91 +1,46 @@
 #![feature(assert_matches)]
 
-use constants::mainnet;
-use starknet_accounts::{Account, Call, ConnectedAccount, Execution, SingleOwnerAccount};
+use anyhow::anyhow;
+use starknet_accounts::{
+    Account, Call, ConnectedAccount, Execution, ExecutionV3, SingleOwnerAccount,
+};
 use starknet_core::chain_id;
-use starknet_core::types::BroadcastedInvokeTransaction;
+use starknet_core::types::{BroadcastedInvokeTransaction, BroadcastedInvokeTransactionV3};
 use starknet_core::{
-    types::{BroadcastedInvokeTransactionV1, BroadcastedTransaction, FieldElement},
+    types::{BroadcastedInvokeTransactionV1, FieldElement},
     utils::get_selector_from_name,
 };
-use starknet_providers::{jsonrpc::HttpTransport, JsonRpcClient};
+use starknet_providers::JsonRpcClient;
 use starknet_signers::{LocalWallet, SigningKey};
-
+use transport::suite::SuiteTransport;
+use v3::V3Fees;
+
+pub mod account;
+pub mod batch;
+pub mod builder;
+pub mod cases;
+pub mod conformance;
 pub mod constants;
+pub mod declare;
+pub mod devnet;
 pub mod fixtures;
+pub mod hash;
+pub mod json_path;
+pub mod lifecycle;
 pub mod macros;
+pub mod mock;
+pub mod nonce;
+pub mod openrpc;
+pub mod scenario;
+pub mod snapshot;
+pub mod transport;
+pub mod v3;
+pub mod verify;
 
-pub trait TransactionFactory {
-    fn build(nonce: Option<FieldElement>) -> BroadcastedTransaction;
-}
-
-pub struct OkTransactionFactory;
-
-impl TransactionFactory for OkTransactionFactory {
-    fn build(nonce: Option<FieldElement>) -> BroadcastedTransaction {
-        BroadcastedTransaction::Invoke(BroadcastedInvokeTransaction::V1(
-            BroadcastedInvokeTransactionV1 {
-                max_fee: FieldElement::ZERO,
-                signature: vec![],
-                nonce: nonce.unwrap_or(FieldElement::ZERO),
-                sender_address: FieldElement::from_hex_be(mainnet::contract::CONTRACT_ACCOUNT)
-                    .unwrap(),
-                calldata: vec![
-                    FieldElement::from_hex_be(mainnet::contract::CONTRACT_ERC20).unwrap(),
-                    get_selector_from_name("transfer").unwrap(),
-                    FieldElement::from_hex_be("1").unwrap(),
-                    FieldElement::from(81u8),
-                ],
-                is_query: true,
-            },
-        ))
-    }
-}
-
-pub struct BadTransactionFactory;
-
-impl TransactionFactory for BadTransactionFactory {
-    fn build(_: Option<FieldElement>) -> BroadcastedTransaction {
-        let transaction_v1 = BroadcastedInvokeTransactionV1 {
-            max_fee: FieldElement::default(),
-            nonce: FieldElement::ZERO,
-            sender_address: FieldElement::default(),
-            signature: vec![],
-            calldata: vec![FieldElement::from_hex_be("0x0").unwrap()],
-            is_query: true,
-        };
-        BroadcastedTransaction::Invoke(BroadcastedInvokeTransaction::V1(transaction_v1))
-    }
-}
-
-pub struct MaxFeeTransactionFactory;
-
-impl TransactionFactory for MaxFeeTransactionFactory {
-    fn build(_: Option<FieldElement>) -> BroadcastedTransaction {
-        let transaction_v1 = BroadcastedInvokeTransactionV1 {
-            max_fee: FieldElement::from_hex_be("0x100000000000000000000000000000000").unwrap(),
-            signature: vec![],
-            nonce: FieldElement::ZERO,
-            sender_address: FieldElement::from_hex_be(
-                "0x019f57133d6a46990231a58a8f45be87405b4494161bf9ac7b25bd14de6e4d40",
-            )
-            .unwrap(),
-            calldata: vec![
-                FieldElement::from_hex_be(mainnet::contract::CONTRACT_ERC20).unwrap(),
-                get_selector_from_name("sqrt").unwrap(),
-                FieldElement::from_hex_be("1").unwrap(),
-                FieldElement::from(81u8),
-            ],
-            is_query: false,
-        };
-        BroadcastedTransaction::Invoke(BroadcastedInvokeTransaction::V1(transaction_v1))
-    }
-}
-
-type RpcAccount<'a> = SingleOwnerAccount<&'a JsonRpcClient<HttpTransport>, LocalWallet>;
+type RpcAccount<'a> = SingleOwnerAccount<&'a JsonRpcClient<SuiteTransport>, LocalWallet>;
 
 pub fn build_single_owner_account<'a>(
-    rpc: &'a JsonRpcClient<HttpTransport>,
+    rpc: &'a JsonRpcClient<SuiteTransport>,
     private_key: &str,
     account_address: &str,
     is_legacy: bool,
@@ -118,9 +73,24 @@ pub trait PrepareInvoke {
         max_fee: FieldElement,
         query_only: bool,
     ) -> BroadcastedInvokeTransactionV1;
+
+    ///
+    /// Prepares a v3 invoke transaction bounded by the L1 gas of `fees`.
+    ///
+    /// The account only signs v3 transactions bounded by L1 gas, without tip or paymaster data
+    /// and with L1 data availability, so any other fee field is rejected. Such transactions are
+    /// built with [`v3::invoke_v3`] and signed with [`v3::sign_invoke_v3`] instead.
+    ///
+    async fn prepare_invoke_v3(
+        &self,
+        calls: Vec<Call>,
+        nonce: FieldElement,
+        fees: &V3Fees,
+        query_only: bool,
+    ) -> anyhow::Result<BroadcastedInvokeTransactionV3>;
 }
 
-impl PrepareInvoke for SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet> {
+impl PrepareInvoke for SingleOwnerAccount<&JsonRpcClient<SuiteTransport>, LocalWallet> {
     async fn prepare_invoke(
         &self,
         calls: Vec<Call>,
@@ -144,7 +114,51 @@ impl PrepareInvoke for SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWa
 
         match invoke_request {
             BroadcastedInvokeTransaction::V1(invoke_transaction) => invoke_transaction,
-            BroadcastedInvokeTransaction::V3(_) => panic!("V3 transactions are not supported yet"),
+            BroadcastedInvokeTransaction::V3(_) => {
+                unreachable!("Execution only prepares v1 transactions, see prepare_invoke_v3")
+            }
+        }
+    }
+
+    async fn prepare_invoke_v3(
+        &self,
+        calls: Vec<Call>,
+        nonce: FieldElement,
+        fees: &V3Fees,
+        query_only: bool,
+    ) -> anyhow::Result<BroadcastedInvokeTransactionV3>
+    where
+        Self: Account + ConnectedAccount,
+    {
+        let supported = V3Fees::l1_gas(
+            fees.resource_bounds.l1_gas.max_amount,
+            fees.resource_bounds.l1_gas.max_price_per_unit,
+        );
+        if *fees != supported {
+            return Err(anyhow!(
+                "The account only signs v3 transactions bounded by L1 gas, without tip, \
+                 paymaster data or L2 data availability: use v3::invoke_v3 and \
+                 v3::sign_invoke_v3 for {fees:?}"
+            ));
+        }
+
+        let prepared_execution = ExecutionV3::new(calls, self)
+            .nonce(nonce)
+            .gas(fees.resource_bounds.l1_gas.max_amount)
+            .gas_price(fees.resource_bounds.l1_gas.max_price_per_unit)
+            .prepared()
+            .map_err(|e| anyhow!("Failed to prepare the v3 invoke: {e}"))?;
+
+        let invoke_request = prepared_execution
+            .get_invoke_request(query_only)
+            .await
+            .map_err(|e| anyhow!("Failed to build the v3 invoke request: {e}"))?;
+
+        match invoke_request {
+            BroadcastedInvokeTransaction::V3(invoke_transaction) => Ok(invoke_transaction),
+            BroadcastedInvokeTransaction::V1(_) => {
+                unreachable!("ExecutionV3 only prepares v3 transactions")
+            }
         }
     }
 }
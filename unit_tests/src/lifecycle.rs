@@ -0,0 +1,415 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use futures::future::join_all;
+use starknet_core::types::{
+    ExecutionResult, FieldElement, StarknetError, TransactionExecutionStatus, TransactionReceipt,
+    TransactionStatus,
+};
+use starknet_providers::{Provider, ProviderError};
+
+///
+/// A stage of the lifecycle of a transaction, as reported by `starknet_getTransactionStatus`.
+///
+/// Accepted transactions may still be reverted, which is their execution status.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    Received,
+    AcceptedOnL2,
+    AcceptedOnL1,
+    Rejected,
+}
+
+impl Stage {
+    fn of(status: &TransactionStatus) -> Self {
+        match status {
+            TransactionStatus::Received => Stage::Received,
+            TransactionStatus::Rejected => Stage::Rejected,
+            TransactionStatus::AcceptedOnL2(_) => Stage::AcceptedOnL2,
+            TransactionStatus::AcceptedOnL1(_) => Stage::AcceptedOnL1,
+        }
+    }
+
+    fn is_final(self, until: Stage) -> bool {
+        self == Stage::Rejected || self >= until
+    }
+}
+
+fn accepted(stage: Option<Stage>) -> bool {
+    matches!(stage, Some(Stage::AcceptedOnL2 | Stage::AcceptedOnL1))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Transition {
+    pub stage: Stage,
+    /// Time since the transaction was submitted.
+    pub elapsed: Duration,
+}
+
+///
+/// Everything a node reported about a transaction while it was tracked.
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Timeline {
+    pub transitions: Vec<Transition>,
+    /// Execution status of the last accepted status.
+    pub execution: Option<TransactionExecutionStatus>,
+    /// Execution result of the receipt, once the transaction is accepted.
+    pub receipt: Option<ExecutionResult>,
+    pub errors: Vec<String>,
+}
+
+impl Timeline {
+    pub fn stage(&self) -> Option<Stage> {
+        self.transitions.last().map(|transition| transition.stage)
+    }
+
+    ///
+    /// Time at which the node first reported `stage`.
+    ///
+    pub fn reached(&self, stage: Stage) -> Option<Duration> {
+        self.transitions
+            .iter()
+            .find(|transition| transition.stage == stage)
+            .map(|transition| transition.elapsed)
+    }
+
+    ///
+    /// Execution status from the receipt, or from the transaction status without a receipt.
+    ///
+    pub fn execution_status(&self) -> Option<TransactionExecutionStatus> {
+        match &self.receipt {
+            Some(ExecutionResult::Succeeded) => Some(TransactionExecutionStatus::Succeeded),
+            Some(ExecutionResult::Reverted { .. }) => Some(TransactionExecutionStatus::Reverted),
+            None => self.execution,
+        }
+    }
+}
+
+fn disagree<T: PartialEq>(mut values: impl Iterator<Item = T>) -> bool {
+    match values.next() {
+        Some(first) => values.any(|value| value != first),
+        None => false,
+    }
+}
+
+///
+/// The lifecycle of a transaction on every tracked node.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LifecycleReport {
+    pub transaction_hash: FieldElement,
+    pub nodes: BTreeMap<String, Timeline>,
+}
+
+impl LifecycleReport {
+    pub fn new(transaction_hash: FieldElement) -> Self {
+        Self {
+            transaction_hash,
+            nodes: BTreeMap::new(),
+        }
+    }
+
+    ///
+    /// Records a status reported by `node`, `elapsed` after submission. Only changes of stage
+    /// are kept as transitions.
+    ///
+    pub fn record_status(&mut self, node: &str, status: &TransactionStatus, elapsed: Duration) {
+        let stage = Stage::of(status);
+        let execution = match status {
+            TransactionStatus::AcceptedOnL2(execution)
+            | TransactionStatus::AcceptedOnL1(execution) => Some(*execution),
+            TransactionStatus::Received | TransactionStatus::Rejected => None,
+        };
+
+        let timeline = self.nodes.entry(node.to_string()).or_default();
+        if timeline.stage() != Some(stage) {
+            timeline.transitions.push(Transition { stage, elapsed });
+        }
+        if execution.is_some() {
+            timeline.execution = execution;
+        }
+    }
+
+    pub fn record_receipt(&mut self, node: &str, receipt: &TransactionReceipt) {
+        let execution_result = match receipt {
+            TransactionReceipt::Invoke(r) => &r.execution_result,
+            TransactionReceipt::L1Handler(r) => &r.execution_result,
+            TransactionReceipt::Declare(r) => &r.execution_result,
+            TransactionReceipt::Deploy(r) => &r.execution_result,
+            TransactionReceipt::DeployAccount(r) => &r.execution_result,
+        };
+
+        self.nodes.entry(node.to_string()).or_default().receipt = Some(execution_result.clone());
+    }
+
+    ///
+    /// Records an error from `node`, unless it repeats the previous one.
+    ///
+    pub fn record_error(&mut self, node: &str, error: String) {
+        let errors = &mut self.nodes.entry(node.to_string()).or_default().errors;
+        if errors.last() != Some(&error) {
+            errors.push(error);
+        }
+    }
+
+    ///
+    /// How long after the first node each node reported `stage`. Nodes which never reached it
+    /// are left out.
+    ///
+    pub fn propagation(&self, stage: Stage) -> BTreeMap<String, Duration> {
+        let reached: BTreeMap<&String, Duration> = self
+            .nodes
+            .iter()
+            .filter_map(|(node, timeline)| timeline.reached(stage).map(|time| (node, time)))
+            .collect();
+        let Some(first) = reached.values().min().copied() else {
+            return BTreeMap::new();
+        };
+
+        reached
+            .into_iter()
+            .map(|(node, time)| (node.clone(), time - first))
+            .collect()
+    }
+
+    ///
+    /// Returns one message per inconsistency: a node going back to an earlier stage, a status
+    /// and a receipt disagreeing on the execution, nodes disagreeing on the final stage or on
+    /// the execution, and errors.
+    ///
+    pub fn inconsistencies(&self) -> Vec<String> {
+        let mut inconsistencies = vec![];
+
+        for (node, timeline) in &self.nodes {
+            for pair in timeline.transitions.windows(2) {
+                let (from, to) = (pair[0].stage, pair[1].stage);
+                if from == Stage::Rejected || to < from {
+                    inconsistencies.push(format!("{node}: went from {from:?} back to {to:?}"));
+                }
+            }
+
+            if let (Some(execution), Some(receipt)) = (timeline.execution, &timeline.receipt) {
+                if Some(execution) != timeline.execution_status() {
+                    inconsistencies.push(format!(
+                        "{node}: status is {execution:?} but receipt is {receipt:?}"
+                    ));
+                }
+            }
+
+            for error in &timeline.errors {
+                inconsistencies.push(format!("{node}: {error}"));
+            }
+        }
+
+        let stages: BTreeMap<&String, Option<Stage>> = self
+            .nodes
+            .iter()
+            .map(|(node, timeline)| (node, timeline.stage()))
+            .collect();
+        if disagree(stages.values()) {
+            inconsistencies.push(format!("Nodes end at different stages: {stages:?}"));
+        }
+
+        let executions: BTreeMap<&String, TransactionExecutionStatus> = self
+            .nodes
+            .iter()
+            .filter_map(|(node, timeline)| timeline.execution_status().map(|e| (node, e)))
+            .collect();
+        if disagree(executions.values()) {
+            inconsistencies.push(format!("Nodes disagree on the execution: {executions:?}"));
+        }
+
+        inconsistencies
+    }
+}
+
+impl fmt::Display for LifecycleReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Transaction {:#x}", self.transaction_hash)?;
+        for (node, timeline) in &self.nodes {
+            let transitions: Vec<String> = timeline
+                .transitions
+                .iter()
+                .map(|t| format!("{:?} at {}ms", t.stage, t.elapsed.as_millis()))
+                .collect();
+            write!(f, "  {node}: {}", transitions.join(" -> "))?;
+            match timeline.execution_status() {
+                Some(execution) => writeln!(f, " ({execution:?})")?,
+                None => writeln!(f)?,
+            }
+        }
+
+        let propagation = self.propagation(Stage::AcceptedOnL2);
+        if !propagation.is_empty() {
+            writeln!(f, "  Propagation to ACCEPTED_ON_L2:")?;
+            for (node, delay) in propagation {
+                writeln!(f, "    {node}: +{}ms", delay.as_millis())?;
+            }
+        }
+
+        for inconsistency in self.inconsistencies() {
+            writeln!(f, "  Inconsistent: {inconsistency}")?;
+        }
+
+        Ok(())
+    }
+}
+
+///
+/// Follows a submitted transaction on several nodes, polling `starknet_getTransactionStatus`
+/// and, once it is accepted, `starknet_getTransactionReceipt`.
+///
+/// ```ignore
+/// let report = LifecycleTracker::new(clients.iter())
+///     .until(Stage::AcceptedOnL2)
+///     .track(transaction_hash, submitted_at)
+///     .await;
+/// assert!(report.inconsistencies().is_empty(), "{report}");
+/// ```
+///
+pub struct LifecycleTracker<'a, P> {
+    nodes: Vec<(String, &'a P)>,
+    poll_interval: Duration,
+    timeout: Duration,
+    until: Stage,
+}
+
+impl<'a, P: Provider + Sync> LifecycleTracker<'a, P> {
+    ///
+    /// Tracks a transaction on `nodes` until it is accepted on L2, polling every 250ms for at
+    /// most a minute.
+    ///
+    pub fn new<S: AsRef<str> + 'a>(nodes: impl IntoIterator<Item = (S, &'a P)>) -> Self {
+        Self {
+            nodes: nodes
+                .into_iter()
+                .map(|(node, provider)| (node.as_ref().to_string(), provider))
+                .collect(),
+            poll_interval: Duration::from_millis(250),
+            timeout: Duration::from_secs(60),
+            until: Stage::AcceptedOnL2,
+        }
+    }
+
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    ///
+    /// Stage after which a node is no longer polled. Rejected transactions are never polled
+    /// further.
+    ///
+    pub fn until(mut self, until: Stage) -> Self {
+        self.until = until;
+        self
+    }
+
+    ///
+    /// Whether a node reached the final stage, and its receipt was fetched if it accepted the
+    /// transaction.
+    ///
+    fn finished(&self, timeline: &Timeline) -> bool {
+        let stage = timeline.stage();
+        stage.is_some_and(|stage| stage.is_final(self.until))
+            && (!accepted(stage) || timeline.receipt.is_some())
+    }
+
+    ///
+    /// Polls every node until each reaches the final stage or the timeout expires. Times are
+    /// measured from `submitted_at`.
+    ///
+    /// Nodes are polled concurrently on each tick, and each status is timestamped as soon as
+    /// its node answers, so a slow node does not delay the times recorded for the others.
+    ///
+    /// A receipt which is not found yet, because it lags the status, is fetched again on the
+    /// next tick. Nodes still without a receipt at the timeout are reported.
+    ///
+    pub async fn track(
+        &self,
+        transaction_hash: FieldElement,
+        submitted_at: Instant,
+    ) -> LifecycleReport {
+        let mut report = LifecycleReport::new(transaction_hash);
+        for (node, _) in &self.nodes {
+            report.nodes.insert(node.clone(), Timeline::default());
+        }
+
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            let polls: Vec<_> = self
+                .nodes
+                .iter()
+                .filter(|(node, _)| !self.finished(&report.nodes[node]))
+                .map(|(node, provider)| {
+                    let timeline = &report.nodes[node];
+                    let was_accepted = accepted(timeline.stage());
+                    let has_receipt = timeline.receipt.is_some();
+
+                    async move {
+                        let status = provider.get_transaction_status(transaction_hash).await;
+                        let elapsed = submitted_at.elapsed();
+
+                        let accepted = match &status {
+                            Ok(status) => accepted(Some(Stage::of(status))),
+                            Err(_) => was_accepted,
+                        };
+                        let receipt = match accepted && !has_receipt {
+                            true => Some(provider.get_transaction_receipt(transaction_hash).await),
+                            false => None,
+                        };
+
+                        (node, status, elapsed, receipt)
+                    }
+                })
+                .collect();
+
+            if polls.is_empty() {
+                return report;
+            }
+
+            for (node, status, elapsed, receipt) in join_all(polls).await {
+                match status {
+                    Ok(status) => report.record_status(node, &status, elapsed),
+                    Err(ProviderError::StarknetError(StarknetError::TransactionHashNotFound)) => {}
+                    Err(e) => report.record_error(node, format!("status: {e}")),
+                }
+                match receipt {
+                    Some(Ok(receipt)) => report.record_receipt(node, &receipt.receipt),
+                    Some(Err(ProviderError::StarknetError(
+                        StarknetError::TransactionHashNotFound,
+                    ))) => {}
+                    Some(Err(e)) => report.record_error(node, format!("receipt: {e}")),
+                    None => {}
+                }
+            }
+
+            if Instant::now() > deadline {
+                for timeline in report.nodes.values_mut() {
+                    if !timeline
+                        .stage()
+                        .is_some_and(|stage| stage.is_final(self.until))
+                    {
+                        timeline
+                            .errors
+                            .push(format!("not {:?} after {:?}", self.until, self.timeout));
+                    } else if accepted(timeline.stage()) && timeline.receipt.is_none() {
+                        timeline
+                            .errors
+                            .push(format!("no receipt after {:?}", self.timeout));
+                    }
+                }
+                return report;
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+}
@@ -0,0 +1,350 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use serde_json::{json, Value};
+use starknet_providers::{jsonrpc::HttpTransport, JsonRpcClient};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use url::Url;
+
+use crate::constants::*;
+use crate::transport::suite::SuiteTransport;
+
+///
+/// Answer of the mock server to a single JSON-RPC call.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum MockResponse {
+    /// A successful call returning `result`.
+    Result(Value),
+    /// A JSON-RPC error, such as a Starknet error.
+    Error {
+        code: i64,
+        message: String,
+        data: Option<Value>,
+    },
+    /// A raw response object, sent as is, to simulate malformed or non-compliant nodes.
+    Raw(Value),
+}
+
+type Handler = Arc<dyn Fn(&Value) -> MockResponse + Send + Sync>;
+
+#[derive(Default)]
+struct MockState {
+    handlers: HashMap<String, Handler>,
+    requests: Vec<(String, Value)>,
+}
+
+///
+/// In-process JSON-RPC server listening on `127.0.0.1`, answering each method with canned or
+/// programmable responses.
+///
+/// Methods without a handler are answered with a `Method not found` error. The server stops
+/// when dropped.
+///
+pub struct MockServer {
+    address: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+    task: JoinHandle<()>,
+}
+
+impl MockServer {
+    ///
+    /// Starts a server on a random local port.
+    ///
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("Error while binding the mock server");
+        let address = listener
+            .local_addr()
+            .expect("Error while getting the mock server address");
+        let state = Arc::new(Mutex::new(MockState::default()));
+
+        let task = tokio::spawn({
+            let state = state.clone();
+            async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    tokio::spawn(serve_connection(stream, state.clone()));
+                }
+            }
+        });
+
+        Self {
+            address,
+            state,
+            task,
+        }
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    ///
+    /// Answers `method` with the response computed by `handler` from the request params.
+    ///
+    pub fn on<F>(&self, method: &str, handler: F)
+    where
+        F: Fn(&Value) -> MockResponse + Send + Sync + 'static,
+    {
+        self.state
+            .lock()
+            .unwrap()
+            .handlers
+            .insert(method.to_string(), Arc::new(handler));
+    }
+
+    ///
+    /// Answers `method` with the same result, whatever its params.
+    ///
+    pub fn on_result(&self, method: &str, result: Value) {
+        self.on(method, move |_| MockResponse::Result(result.clone()));
+    }
+
+    ///
+    /// Answers `method` with the same JSON-RPC error, whatever its params.
+    ///
+    pub fn on_error(&self, method: &str, code: i64, message: &str) {
+        let message = message.to_string();
+        self.on(method, move |_| MockResponse::Error {
+            code,
+            message: message.clone(),
+            data: None,
+        });
+    }
+
+    ///
+    /// Returns every call received so far, as method name and params.
+    ///
+    pub fn requests(&self) -> Vec<(String, Value)> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    pub fn transport(&self) -> HttpTransport {
+        HttpTransport::new(Url::parse(&self.url()).expect("Invalid mock server url"))
+    }
+
+    pub fn client(&self) -> JsonRpcClient<SuiteTransport> {
+        JsonRpcClient::new(SuiteTransport::Live(self.transport()))
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+///
+/// Answers a single JSON-RPC call object.
+///
+fn answer(request: &Value, state: &Mutex<MockState>) -> Value {
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let Some(method) = request.get("method").and_then(Value::as_str) else {
+        return json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": -32600, "message": "Invalid Request" },
+        });
+    };
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+    let handler = {
+        let mut state = state.lock().unwrap();
+        state.requests.push((method.to_string(), params.clone()));
+        state.handlers.get(method).cloned()
+    };
+
+    match handler.map(|handler| handler(&params)) {
+        Some(MockResponse::Result(result)) => {
+            json!({ "jsonrpc": "2.0", "id": id, "result": result })
+        }
+        Some(MockResponse::Error {
+            code,
+            message,
+            data,
+        }) => {
+            let mut error = json!({ "code": code, "message": message });
+            if let Some(data) = data {
+                error["data"] = data;
+            }
+            json!({ "jsonrpc": "2.0", "id": id, "error": error })
+        }
+        Some(MockResponse::Raw(response)) => response,
+        None => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": -32601, "message": "Method not found" },
+        }),
+    }
+}
+
+///
+/// Whether a call is a notification, which has a method but no id and is not answered.
+///
+fn is_notification(call: &Value) -> bool {
+    call.get("id").is_none() && call.get("method").is_some()
+}
+
+///
+/// Answers a request body, which is either a single call or a batch of calls. Returns `None`
+/// when there is nothing to answer, as for notifications.
+///
+fn answer_body(body: &[u8], state: &Mutex<MockState>) -> Option<Value> {
+    match serde_json::from_slice::<Value>(body) {
+        Ok(Value::Array(calls)) if !calls.is_empty() => {
+            let responses: Vec<Value> = calls
+                .iter()
+                .filter_map(|call| {
+                    let response = answer(call, state);
+                    (!is_notification(call)).then_some(response)
+                })
+                .collect();
+            (!responses.is_empty()).then_some(Value::Array(responses))
+        }
+        Ok(call @ Value::Object(_)) => {
+            let response = answer(&call, state);
+            (!is_notification(&call)).then_some(response)
+        }
+        Ok(_) => Some(json!({
+            "jsonrpc": "2.0",
+            "id": null,
+            "error": { "code": -32600, "message": "Invalid Request" },
+        })),
+        Err(_) => Some(json!({
+            "jsonrpc": "2.0",
+            "id": null,
+            "error": { "code": -32700, "message": "Parse error" },
+        })),
+    }
+}
+
+///
+/// Serves HTTP/1.1 requests on a connection until the client closes it.
+///
+async fn serve_connection(stream: TcpStream, state: Arc<Mutex<MockState>>) {
+    let mut stream = BufReader::new(stream);
+
+    loop {
+        let mut content_length = 0;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match stream.read_line(&mut line).await {
+                Ok(0) | Err(_) => return,
+                Ok(_) if line == "\r\n" => break,
+                Ok(_) => {
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap_or(0);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut body = vec![0; content_length];
+        if stream.read_exact(&mut body).await.is_err() {
+            return;
+        }
+
+        let response = answer_body(&body, &state)
+            .map(|response| response.to_string())
+            .unwrap_or_default();
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            response.len(),
+            response
+        );
+        if stream
+            .get_mut()
+            .write_all(response.as_bytes())
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+///
+/// One mock server per node of the `clients` fixture.
+///
+pub struct MockNetwork {
+    pub servers: HashMap<String, MockServer>,
+}
+
+impl MockNetwork {
+    pub async fn start() -> Self {
+        let mut servers = HashMap::new();
+        for node in [
+            mainnet::network::DEOXYS,
+            mainnet::network::PATHFINDER,
+            mainnet::network::JUNO,
+        ] {
+            servers.insert(node.to_string(), MockServer::start().await);
+        }
+
+        Self { servers }
+    }
+
+    ///
+    /// Answers `method` with the same result on every node.
+    ///
+    pub fn on_result(&self, method: &str, result: Value) {
+        for server in self.servers.values() {
+            server.on_result(method, result.clone());
+        }
+    }
+
+    ///
+    /// Same transports as the `transports` fixture, pointed at the mock servers.
+    ///
+    pub fn transports(&self) -> HashMap<String, SuiteTransport> {
+        self.servers
+            .iter()
+            .map(|(node, server)| (node.clone(), SuiteTransport::Live(server.transport())))
+            .collect()
+    }
+
+    ///
+    /// Same clients as the `clients` fixture, pointed at the mock servers.
+    ///
+    pub fn clients(&self) -> HashMap<String, JsonRpcClient<SuiteTransport>> {
+        self.servers
+            .iter()
+            .map(|(node, server)| (node.clone(), server.client()))
+            .collect()
+    }
+}
+
+impl std::ops::Index<&str> for MockNetwork {
+    type Output = MockServer;
+
+    fn index(&self, node: &str) -> &MockServer {
+        &self.servers[node]
+    }
+}
+
+///
+/// Builds a `getBlockWithTxHashes` result for an accepted block without transactions.
+///
+pub fn block_with_tx_hashes(block_number: u64, block_hash: &str, parent_hash: &str) -> Value {
+    json!({
+        "status": "ACCEPTED_ON_L1",
+        "block_hash": block_hash,
+        "parent_hash": parent_hash,
+        "block_number": block_number,
+        "new_root": "0x0",
+        "timestamp": 0,
+        "sequencer_address": "0x0",
+        "l1_gas_price": { "price_in_fri": "0x0", "price_in_wei": "0x0" },
+        "l1_data_gas_price": { "price_in_fri": "0x0", "price_in_wei": "0x0" },
+        "l1_da_mode": "CALLDATA",
+        "starknet_version": "0.13.1",
+        "transactions": [],
+    })
+}
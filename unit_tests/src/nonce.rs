@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use std::future::Future;
+
+use starknet_core::types::{BlockId, BlockTag, FieldElement, StarknetError};
+use starknet_providers::{Provider, ProviderError};
+use tokio::sync::Mutex;
+
+///
+/// Hands out nonces to concurrent tasks sending transactions from the same accounts.
+///
+/// The pending nonce of an account is fetched the first time it is needed, then every call
+/// to [`NonceManager::next`] returns the following one, so transactions can be pipelined
+/// without waiting for each other. After an `INVALID_TRANSACTION_NONCE` error, for instance
+/// when an earlier transaction was rejected and left a gap, [`NonceManager::resync`] fetches
+/// the pending nonce again. Each fetch starts a new generation, so that concurrent failures
+/// of nonces handed out before it do not resync the account once more.
+///
+/// ```ignore
+/// let nonces = Arc::new(NonceManager::new(client));
+/// let transaction_hash = nonces
+///     .send(address, 3, |nonce| async move {
+///         let transaction = builder.clone().nonce(nonce).build();
+///         harness.submit(DEVNET, transaction).await
+///     })
+///     .await?;
+/// ```
+///
+pub struct NonceManager<P> {
+    provider: P,
+    nonces: Mutex<Nonces>,
+}
+
+#[derive(Default)]
+struct Nonces {
+    accounts: HashMap<FieldElement, Account>,
+    generation: u64,
+}
+
+///
+/// The next nonce of an account, and the generation of the fetch it was counted from.
+///
+#[derive(Clone, Copy)]
+struct Account {
+    next: FieldElement,
+    generation: u64,
+}
+
+impl Nonces {
+    fn fetched(&mut self, address: FieldElement, nonce: FieldElement) {
+        self.generation += 1;
+        let account = Account {
+            next: nonce,
+            generation: self.generation,
+        };
+        self.accounts.insert(address, account);
+    }
+}
+
+impl<P: Provider + Sync> NonceManager<P> {
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider,
+            nonces: Mutex::new(Nonces::default()),
+        }
+    }
+
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    async fn pending_nonce(&self, address: FieldElement) -> Result<FieldElement, ProviderError> {
+        self.provider
+            .get_nonce(BlockId::Tag(BlockTag::Pending), address)
+            .await
+    }
+
+    ///
+    /// Returns the next unused nonce of `address`. Only the first call for an account queries
+    /// the node, and concurrent callers never get the same nonce.
+    ///
+    pub async fn next(&self, address: FieldElement) -> Result<FieldElement, ProviderError> {
+        Ok(self.take(address).await?.next)
+    }
+
+    async fn take(&self, address: FieldElement) -> Result<Account, ProviderError> {
+        let mut nonces = self.nonces.lock().await;
+        if !nonces.accounts.contains_key(&address) {
+            let nonce = self.pending_nonce(address).await?;
+            nonces.fetched(address, nonce);
+        }
+
+        let account = nonces.accounts.get_mut(&address).unwrap();
+        let taken = *account;
+        account.next += FieldElement::ONE;
+
+        Ok(taken)
+    }
+
+    ///
+    /// Fetches the pending nonce of `address` again, and hands out nonces from there on.
+    ///
+    /// Nonces handed out before are not taken back: transactions still in flight with them
+    /// may fail in turn, and should be resent with a new nonce.
+    ///
+    pub async fn resync(&self, address: FieldElement) -> Result<FieldElement, ProviderError> {
+        let mut nonces = self.nonces.lock().await;
+        let nonce = self.pending_nonce(address).await?;
+        nonces.fetched(address, nonce);
+
+        Ok(nonce)
+    }
+
+    ///
+    /// Resyncs `address` after the nonce `failed` was rejected, unless the account was fetched
+    /// again since `failed` was handed out, or its counter already went back below it.
+    ///
+    async fn resync_after(
+        &self,
+        address: FieldElement,
+        failed: Account,
+    ) -> Result<(), ProviderError> {
+        let mut nonces = self.nonces.lock().await;
+        let stale = match nonces.accounts.get(&address) {
+            Some(account) => account.generation == failed.generation && failed.next < account.next,
+            None => false,
+        };
+        if stale {
+            let nonce = self.pending_nonce(address).await?;
+            nonces.fetched(address, nonce);
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Forgets the nonce of `address`, which is fetched again on the next call to
+    /// [`NonceManager::next`].
+    ///
+    pub async fn reset(&self, address: FieldElement) {
+        self.nonces.lock().await.accounts.remove(&address);
+    }
+
+    ///
+    /// Sends a transaction from `address` with the next nonce. When the node answers
+    /// `INVALID_TRANSACTION_NONCE`, resyncs and sends it again with a new nonce, at most
+    /// `retries` times. Among concurrent failures, only the first one resyncs the account.
+    ///
+    pub async fn send<T, F, Fut>(
+        &self,
+        address: FieldElement,
+        retries: usize,
+        send: F,
+    ) -> Result<T, ProviderError>
+    where
+        F: Fn(FieldElement) -> Fut,
+        Fut: Future<Output = Result<T, ProviderError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            let taken = self.take(address).await?;
+            match send(taken.next).await {
+                Err(ProviderError::StarknetError(StarknetError::InvalidTransactionNonce))
+                    if attempt < retries =>
+                {
+                    attempt += 1;
+                    self.resync_after(address, taken).await?;
+                }
+                result => return result,
+            }
+        }
+    }
+}
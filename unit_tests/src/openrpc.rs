@@ -0,0 +1,394 @@
+use std::env;
+use std::fs;
+
+use anyhow::anyhow;
+use serde_json::{json, Map, Value};
+use starknet_core::utils::get_selector_from_name;
+use starknet_providers::jsonrpc::{JsonRpcMethod, JsonRpcTransport};
+
+use crate::cases::send;
+use crate::constants::mainnet;
+
+///
+/// Environment variable overriding the path of the OpenRPC specification.
+///
+pub const SPEC_ENV: &str = "DITTO_SPEC";
+
+///
+/// Default OpenRPC specification, relative to the `unit_tests` crate like `secret.json`.
+///
+pub const DEFAULT_SPEC: &str = "spec/starknet_api_openrpc.json";
+
+///
+/// Error code of JSON-RPC 2.0 for invalid method parameters.
+///
+pub const INVALID_PARAMS: i64 = -32602;
+
+///
+/// The field prime, as a hex string which still matches the `FELT` pattern of the spec.
+///
+pub const FIELD_PRIME: &str = "0x800000000000011000000000000000000000000000000000000000000000001";
+
+///
+/// L1 address of the StarkGate ETH bridge, which sends deposits to
+/// [`mainnet::contract::STARKGATE_ETH_BRIDGE_ADDR`].
+///
+const STARKGATE_ETH_BRIDGE_L1: &str = "0xae0ee0a63a2ce6baeeffe56e7714fb4efe48d419";
+
+///
+/// Returns a value of a param, or of a field of a param such as `message.to_address`, which
+/// exists on mainnet. Generated values such as `0x1` name blocks, contracts or classes which
+/// do not exist, and a node could answer a negative case with a documented error such as
+/// `BLOCK_NOT_FOUND` without ever validating the invalid param.
+///
+fn known_value(path: &str) -> Option<Value> {
+    let selector = |name: &str| json!(format!("{:#x}", get_selector_from_name(name).unwrap()));
+
+    Some(match path {
+        "block_id" => json!("latest"),
+        "contract_address" | "request.contract_address" => json!(mainnet::contract::CONTRACT_ERC20),
+        "class_hash" => json!(mainnet::contract::CLASS_OZ_ACCOUNT),
+        "transaction_hash" => json!(mainnet::transaction::TX_INVOKE_V1),
+        "request.entry_point_selector" => selector("name"),
+        "message.from_address" => json!(STARKGATE_ETH_BRIDGE_L1),
+        "message.to_address" => json!(mainnet::contract::STARKGATE_ETH_BRIDGE_ADDR),
+        "message.entry_point_selector" => selector("handle_deposit"),
+        "message.payload" => json!([mainnet::contract::CONTRACT_ACCOUNT, "0x1", "0x0"]),
+        _ => return None,
+    })
+}
+
+///
+/// A method parameter of the specification.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct Param {
+    pub name: String,
+    pub required: bool,
+    pub schema: Value,
+}
+
+///
+/// A method of the specification with the codes of its documented errors.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct Method {
+    pub name: String,
+    pub params: Vec<Param>,
+    pub errors: Vec<i64>,
+}
+
+///
+/// A call with invalid params, and the error codes a node may answer it with.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct NegativeCase {
+    pub method: String,
+    pub description: String,
+    pub params: Value,
+    pub expected_errors: Vec<i64>,
+}
+
+///
+/// Returns the path of the OpenRPC specification.
+///
+pub fn spec_path() -> String {
+    env::var(SPEC_ENV).unwrap_or_else(|_| String::from(DEFAULT_SPEC))
+}
+
+///
+/// Whether a method of the specification is known to the JSON-RPC client, and can be sent.
+///
+pub fn is_supported(method: &Method) -> bool {
+    serde_json::from_value::<JsonRpcMethod>(Value::String(method.name.clone())).is_ok()
+}
+
+///
+/// An OpenRPC document, such as `starknet_api_openrpc.json` from `starknet-specs`.
+///
+pub struct OpenRpcSpec {
+    document: Value,
+}
+
+impl OpenRpcSpec {
+    pub fn new(document: Value) -> Self {
+        Self { document }
+    }
+
+    pub fn load(path: &str) -> anyhow::Result<Self> {
+        let document = serde_json::from_str(&fs::read_to_string(path)?)
+            .map_err(|e| anyhow!("Invalid OpenRPC specification {path}: {e}"))?;
+        Ok(Self::new(document))
+    }
+
+    ///
+    /// Follows `$ref` until a schema is found. References to other files of the specification
+    /// are looked up in this document, where `starknet-specs` defines the shared schemas.
+    ///
+    pub fn resolve<'a>(&'a self, mut value: &'a Value) -> anyhow::Result<&'a Value> {
+        while let Some(reference) = value.get("$ref").and_then(Value::as_str) {
+            let pointer = reference
+                .split_once('#')
+                .map(|(_, pointer)| pointer)
+                .ok_or_else(|| anyhow!("Unsupported reference '{reference}'"))?;
+            value = self
+                .document
+                .pointer(pointer)
+                .ok_or_else(|| anyhow!("Unresolved reference '{reference}'"))?;
+        }
+
+        Ok(value)
+    }
+
+    ///
+    /// Returns every method of the specification.
+    ///
+    pub fn methods(&self) -> anyhow::Result<Vec<Method>> {
+        let methods = self.document["methods"]
+            .as_array()
+            .ok_or_else(|| anyhow!("Specification without methods"))?;
+
+        methods
+            .iter()
+            .map(|method| {
+                let params = method["params"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|param| {
+                        let param = self.resolve(param)?;
+                        Ok(Param {
+                            name: param["name"].as_str().unwrap_or_default().to_string(),
+                            required: param["required"].as_bool().unwrap_or(false),
+                            schema: param["schema"].clone(),
+                        })
+                    })
+                    .collect::<anyhow::Result<_>>()?;
+
+                let errors = method["errors"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|error| Ok(self.resolve(error)?["code"].as_i64().unwrap_or_default()))
+                    .collect::<anyhow::Result<_>>()?;
+
+                Ok(Method {
+                    name: method["name"].as_str().unwrap_or_default().to_string(),
+                    params,
+                    errors,
+                })
+            })
+            .collect()
+    }
+
+    ///
+    /// Builds a value matching a schema.
+    ///
+    pub fn valid_value(&self, schema: &Value) -> anyhow::Result<Value> {
+        self.valid_value_at("", schema)
+    }
+
+    ///
+    /// Builds a value matching the schema of the param or field at `path`, used as the valid
+    /// base of every negative case. Blocks, contracts, classes and transactions are ones which
+    /// exist on mainnet, see [`known_value`].
+    ///
+    fn valid_value_at(&self, path: &str, schema: &Value) -> anyhow::Result<Value> {
+        if let Some(value) = known_value(path) {
+            return Ok(value);
+        }
+        let schema = self.resolve(schema)?;
+
+        if let Some(value) = schema.get("enum").and_then(|values| values.get(0)) {
+            return Ok(value.clone());
+        }
+        if let Some(first) = ["oneOf", "anyOf"]
+            .iter()
+            .find_map(|key| schema.get(key).and_then(|schemas| schemas.get(0)))
+        {
+            return self.valid_value_at(path, first);
+        }
+        if let Some(schemas) = schema.get("allOf").and_then(Value::as_array) {
+            let mut merged = Map::new();
+            for schema in schemas {
+                if let Value::Object(map) = self.valid_value_at(path, schema)? {
+                    merged.extend(map);
+                }
+            }
+            return Ok(Value::Object(merged));
+        }
+
+        Ok(match schema["type"].as_str() {
+            Some("string") if schema.get("pattern").is_some() => json!("0x1"),
+            Some("string") => json!(""),
+            Some("integer") => json!(schema["minimum"].as_i64().unwrap_or(0)),
+            Some("boolean") => json!(false),
+            Some("array") => json!([]),
+            Some("object") => {
+                let mut object = Map::new();
+                let required = schema["required"].as_array().cloned().unwrap_or_default();
+                for name in required.iter().filter_map(Value::as_str) {
+                    let property = &schema["properties"][name];
+                    let field = match path {
+                        "" => name.to_string(),
+                        path => format!("{path}.{name}"),
+                    };
+                    object.insert(name.to_string(), self.valid_value_at(&field, property)?);
+                }
+                Value::Object(object)
+            }
+            _ => Value::Null,
+        })
+    }
+
+    ///
+    /// Returns a value of the wrong JSON type for a schema.
+    ///
+    fn wrong_type(&self, schema: &Value) -> anyhow::Result<Value> {
+        Ok(match self.valid_value(schema)? {
+            Value::String(_) => json!(12345),
+            Value::Number(_) => json!("not a number"),
+            Value::Bool(_) => json!("not a boolean"),
+            Value::Array(_) => json!({}),
+            Value::Object(_) => json!("not an object"),
+            Value::Null => json!([]),
+        })
+    }
+
+    ///
+    /// Whether a schema is a felt, or one of its aliases such as `ADDRESS`.
+    ///
+    fn is_felt(&self, schema: &Value) -> anyhow::Result<bool> {
+        let schema = self.resolve(schema)?;
+        if schema["pattern"]
+            .as_str()
+            .is_some_and(|pattern| pattern.starts_with("^0x"))
+        {
+            return Ok(true);
+        }
+
+        match schema.get("allOf").and_then(Value::as_array) {
+            Some(schemas) => Ok(schemas.len() == 1 && self.is_felt(&schemas[0])?),
+            None => Ok(false),
+        }
+    }
+
+    ///
+    /// Whether a schema is, or has a variant which is, an enum of tags.
+    ///
+    fn has_enum(&self, schema: &Value) -> anyhow::Result<bool> {
+        let schema = self.resolve(schema)?;
+        if schema.get("enum").is_some() {
+            return Ok(true);
+        }
+
+        for key in ["oneOf", "anyOf"] {
+            for schema in schema
+                .get(key)
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                if self.has_enum(schema)? {
+                    return Ok(true);
+                }
+            }
+        }
+
+        Ok(false)
+    }
+
+    ///
+    /// Generates the negative cases of a method: each param of the wrong JSON type, each
+    /// required param missing, each felt param above the field prime, each enum param with an
+    /// unknown tag, and an extra positional param.
+    ///
+    pub fn negative_cases(&self, method: &Method) -> anyhow::Result<Vec<NegativeCase>> {
+        let mut valid = Map::new();
+        for param in &method.params {
+            valid.insert(
+                param.name.clone(),
+                self.valid_value_at(&param.name, &param.schema)?,
+            );
+        }
+
+        let mut expected_errors = vec![INVALID_PARAMS];
+        expected_errors.extend(&method.errors);
+
+        let case = |description: String, params: Value| NegativeCase {
+            method: method.name.clone(),
+            description,
+            params,
+            expected_errors: expected_errors.clone(),
+        };
+        let with = |name: &str, value: Value| {
+            let mut params = valid.clone();
+            params.insert(name.to_string(), value);
+            Value::Object(params)
+        };
+
+        let mut cases = vec![];
+        for param in &method.params {
+            cases.push(case(
+                format!("wrong type for '{}'", param.name),
+                with(&param.name, self.wrong_type(&param.schema)?),
+            ));
+
+            if param.required {
+                let mut params = valid.clone();
+                params.remove(&param.name);
+                cases.push(case(
+                    format!("missing '{}'", param.name),
+                    Value::Object(params),
+                ));
+            }
+
+            if self.is_felt(&param.schema)? {
+                cases.push(case(
+                    format!("'{}' above the field prime", param.name),
+                    with(&param.name, json!(FIELD_PRIME)),
+                ));
+            }
+
+            if self.has_enum(&param.schema)? {
+                cases.push(case(
+                    format!("unknown tag for '{}'", param.name),
+                    with(&param.name, json!("UNKNOWN_TAG")),
+                ));
+            }
+        }
+
+        let mut positional: Vec<Value> = method
+            .params
+            .iter()
+            .map(|param| valid[&param.name].clone())
+            .collect();
+        positional.push(json!("0x1"));
+        cases.push(case(
+            String::from("extra positional param"),
+            Value::Array(positional),
+        ));
+
+        Ok(cases)
+    }
+}
+
+///
+/// Sends a negative case and checks that the node answers with one of its expected errors.
+///
+pub async fn check_negative_case<T: JsonRpcTransport + Sync>(
+    transport: &T,
+    case: &NegativeCase,
+) -> anyhow::Result<()> {
+    let response = send(transport, &case.method, &case.params).await?;
+
+    match response["error"]["code"].as_i64() {
+        Some(code) if case.expected_errors.contains(&code) => Ok(()),
+        _ => Err(anyhow!(
+            "{} ({}): expected one of {:?}, got {response}",
+            case.method,
+            case.description,
+            case.expected_errors
+        )),
+    }
+}
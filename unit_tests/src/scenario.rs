@@ -0,0 +1,335 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use starknet_providers::jsonrpc::JsonRpcTransport;
+
+use crate::cases::{send, CaseFailure};
+use crate::json_path;
+use crate::snapshot::{canonicalize, first_difference};
+
+///
+/// A sequence of JSON-RPC calls where later steps use values captured from earlier responses,
+/// written in YAML or TOML.
+///
+/// ```yaml
+/// name: deployed account class hash
+/// variables:
+///   block: 607878
+/// steps:
+///   - name: find a deployed account
+///     method: starknet_getBlockWithReceipts
+///     params: { block_id: { block_number: "${block}" } }
+///     capture:
+///       account: "$.result.transactions[?(@.transaction.type=='DEPLOY_ACCOUNT')]"
+///       parent: "$.result.parent_hash"
+///   - method: starknet_getClassHashAt
+///     params:
+///       block_id: { block_hash: "${parent}" }
+///       contract_address: "${account.receipt.contract_address}"
+///     expected_error: 20
+/// ```
+///
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Scenario {
+    pub name: String,
+    /// Variables set before the first step.
+    #[serde(default)]
+    pub variables: BTreeMap<String, Value>,
+    /// Nodes to run the scenario against, every node by default.
+    #[serde(default)]
+    pub nodes: Option<Vec<String>>,
+    pub steps: Vec<Step>,
+}
+
+///
+/// A single call of a scenario. Strings of its params may reference variables as `${name}`.
+///
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Step {
+    /// Name used in reports, the index of the step by default.
+    #[serde(default)]
+    pub name: Option<String>,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+    /// Variables to set from the response, as JSON paths. The first match is captured.
+    #[serde(default)]
+    pub capture: BTreeMap<String, String>,
+    #[serde(default, rename = "assert")]
+    pub assertions: Vec<Assertion>,
+    /// Whether every node must answer like the first node, in name order.
+    #[serde(default = "default_compare")]
+    pub compare: bool,
+    /// JSON paths into the response left out of the comparison.
+    #[serde(default)]
+    pub ignore: Vec<String>,
+    /// Error code every node must answer with, instead of a result.
+    #[serde(default)]
+    pub expected_error: Option<i64>,
+}
+
+fn default_compare() -> bool {
+    true
+}
+
+///
+/// A check on the response of a step. `equals` may reference variables like params.
+///
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Assertion {
+    pub path: String,
+    #[serde(default)]
+    pub equals: Option<Value>,
+    #[serde(default)]
+    pub exists: Option<bool>,
+}
+
+///
+/// Parses a scenario file, as TOML if its extension is `.toml` and as YAML otherwise.
+///
+pub fn load_scenario(path: &str) -> anyhow::Result<Scenario> {
+    let content = fs::read_to_string(path)?;
+
+    if Path::new(path)
+        .extension()
+        .is_some_and(|extension| extension == "toml")
+    {
+        toml::from_str(&content).map_err(|e| anyhow!("Invalid scenario {path}: {e}"))
+    } else {
+        serde_yaml::from_str(&content).map_err(|e| anyhow!("Invalid scenario {path}: {e}"))
+    }
+}
+
+///
+/// Parses every `.yaml`, `.yml` and `.toml` scenario of a directory, in file name order.
+///
+pub fn load_scenarios(dir: &str) -> anyhow::Result<Vec<Scenario>> {
+    let mut paths = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path
+            .extension()
+            .is_some_and(|extension| ["yaml", "yml", "toml"].iter().any(|e| extension == *e))
+        {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|path| load_scenario(&path.to_string_lossy()))
+        .collect()
+}
+
+///
+/// Replaces the `${name}` references of every string in `value`, where `${name.key[0]}` reads a
+/// field of a captured object. A string made of a single reference takes the variable as is, so
+/// that numbers and objects keep their type.
+///
+pub fn substitute(value: &Value, variables: &HashMap<String, Value>) -> anyhow::Result<Value> {
+    let lookup = |reference: &str| -> anyhow::Result<Value> {
+        let (name, path) = match reference.find(['.', '[']) {
+            Some(index) => reference.split_at(index),
+            None => (reference, ""),
+        };
+        let variable = variables
+            .get(name)
+            .ok_or_else(|| anyhow!("Unknown variable '{name}'"))?;
+        json_path::select_one(variable, &format!("${path}")).cloned()
+    };
+
+    match value {
+        Value::String(s) => {
+            if let Some(name) = s
+                .strip_prefix("${")
+                .and_then(|s| s.strip_suffix('}'))
+                .filter(|name| !name.contains("${"))
+            {
+                return lookup(name);
+            }
+
+            let mut result = String::new();
+            let mut rest = s.as_str();
+            while let Some(start) = rest.find("${") {
+                let end = rest[start..]
+                    .find('}')
+                    .ok_or_else(|| anyhow!("Unterminated variable in '{s}'"))?;
+                result.push_str(&rest[..start]);
+                match lookup(&rest[start + 2..start + end])? {
+                    Value::String(variable) => result.push_str(&variable),
+                    variable => result.push_str(&variable.to_string()),
+                }
+                rest = &rest[start + end + 1..];
+            }
+            result.push_str(rest);
+
+            Ok(Value::String(result))
+        }
+        Value::Array(values) => values
+            .iter()
+            .map(|value| substitute(value, variables))
+            .collect::<anyhow::Result<_>>()
+            .map(Value::Array),
+        Value::Object(map) => map
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), substitute(value, variables)?)))
+            .collect::<anyhow::Result<_>>()
+            .map(Value::Object),
+        _ => Ok(value.clone()),
+    }
+}
+
+///
+/// Runs the assertions and captures of a step on the response of a node.
+///
+fn check_step(
+    step: &Step,
+    response: &Value,
+    variables: &mut HashMap<String, Value>,
+) -> anyhow::Result<()> {
+    if let Some(code) = step.expected_error {
+        let actual = response["error"]["code"].as_i64();
+        if actual != Some(code) {
+            return Err(anyhow!("expected error {code}, got {response}"));
+        }
+    } else if let Some(error) = response.get("error") {
+        return Err(anyhow!("unexpected error {error}"));
+    }
+
+    for assertion in &step.assertions {
+        let selected = json_path::select(response, &assertion.path)?;
+
+        if let Some(exists) = assertion.exists {
+            if exists == selected.is_empty() {
+                return Err(anyhow!(
+                    "expected {} to {}exist",
+                    assertion.path,
+                    if exists { "" } else { "not " }
+                ));
+            }
+        }
+
+        if let Some(expected) = &assertion.equals {
+            let expected = canonicalize(&substitute(expected, variables)?);
+            let actual = json_path::select_one(response, &assertion.path)?;
+            if canonicalize(actual) != expected {
+                return Err(anyhow!(
+                    "expected {} to equal {expected}, got {actual}",
+                    assertion.path
+                ));
+            }
+        }
+    }
+
+    for (name, path) in &step.capture {
+        let value = json_path::select(response, path)?
+            .first()
+            .map(|value| (*value).clone())
+            .ok_or_else(|| anyhow!("capture '{name}' matched nothing at {path}"))?;
+        variables.insert(name.clone(), value);
+    }
+
+    Ok(())
+}
+
+///
+/// Runs a scenario against its nodes.
+///
+/// Every step is sent to every node with the variables captured on that same node, then its
+/// assertions are checked and, unless disabled, its response is compared with the response of
+/// the first node in name order. A node failing a step is reported once and skips the
+/// remaining steps, since their variables may be missing.
+///
+pub async fn run_scenario<T: JsonRpcTransport + Sync>(
+    transports: &HashMap<String, T>,
+    scenario: &Scenario,
+) -> Vec<CaseFailure> {
+    let mut nodes: Vec<&String> = transports
+        .keys()
+        .filter(|node| {
+            scenario
+                .nodes
+                .as_ref()
+                .map_or(true, |nodes| nodes.contains(node))
+        })
+        .collect();
+    nodes.sort();
+
+    let initial: HashMap<String, Value> = scenario.variables.clone().into_iter().collect();
+    let mut variables: HashMap<&String, HashMap<String, Value>> =
+        nodes.iter().map(|node| (*node, initial.clone())).collect();
+
+    let mut failures = vec![];
+    let mut failed = HashSet::new();
+
+    for (index, step) in scenario.steps.iter().enumerate() {
+        let case = format!(
+            "{}: {}",
+            scenario.name,
+            step.name
+                .clone()
+                .unwrap_or_else(|| format!("step {}", index + 1))
+        );
+        let mut reference: Option<(&str, Value)> = None;
+
+        for node in &nodes {
+            if failed.contains(node) {
+                continue;
+            }
+            let node_variables = variables.get_mut(node).expect("Node without variables");
+
+            let result = async {
+                let params = substitute(&step.params, node_variables)?;
+                let response = send(&transports[*node], &step.method, &params).await?;
+                check_step(step, &response, node_variables)?;
+
+                let mut response = canonicalize(&response);
+                if step.compare {
+                    for path in &step.ignore {
+                        json_path::remove(&mut response, path)?;
+                    }
+                }
+                Ok::<_, anyhow::Error>(response)
+            }
+            .await;
+
+            let response = match result {
+                Ok(response) => response,
+                Err(e) => {
+                    failures.push(CaseFailure {
+                        case: case.clone(),
+                        node: node.to_string(),
+                        message: e.to_string(),
+                    });
+                    failed.insert(*node);
+                    continue;
+                }
+            };
+
+            if !step.compare {
+                continue;
+            }
+
+            match &reference {
+                None => reference = Some((node.as_str(), response)),
+                Some((reference_node, expected)) => {
+                    if let Some(path) = first_difference(expected, &response, "$") {
+                        failures.push(CaseFailure {
+                            case: case.clone(),
+                            node: node.to_string(),
+                            message: format!("response differs from {reference_node} at {path}"),
+                        });
+                        failed.insert(*node);
+                    }
+                }
+            }
+        }
+    }
+
+    failures
+}
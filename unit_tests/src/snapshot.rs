@@ -0,0 +1,150 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use serde_json::{json, Value};
+use starknet_core::utils::starknet_keccak;
+use starknet_providers::jsonrpc::{JsonRpcMethod, JsonRpcTransport};
+
+use crate::transport::corpus::{method_name, response_to_value};
+
+///
+/// Environment variable which, when set to `1`, writes every snapshot with the current response
+/// instead of comparing against it.
+///
+pub const UPDATE_SNAPSHOTS_ENV: &str = "DITTO_UPDATE_SNAPSHOTS";
+
+///
+/// Normalizes a response so that formatting differences do not break snapshots: hex strings are
+/// lowercased and stripped of leading zeros, and object keys are sorted, so that snapshots and
+/// their paths do not depend on whether `serde_json` preserves key order.
+///
+pub fn canonicalize(value: &Value) -> Value {
+    match value {
+        Value::String(s) => match s.strip_prefix("0x") {
+            Some(digits) if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_hexdigit()) => {
+                let digits = digits.trim_start_matches('0').to_lowercase();
+                if digits.is_empty() {
+                    Value::String(String::from("0x0"))
+                } else {
+                    Value::String(format!("0x{digits}"))
+                }
+            }
+            _ => value.clone(),
+        },
+        Value::Array(values) => Value::Array(values.iter().map(canonicalize).collect()),
+        Value::Object(map) => {
+            let mut fields: Vec<_> = map.iter().collect();
+            fields.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                fields
+                    .into_iter()
+                    .map(|(key, value)| (key.clone(), canonicalize(value)))
+                    .collect(),
+            )
+        }
+        _ => value.clone(),
+    }
+}
+
+///
+/// Returns the path of the snapshot of a request:
+/// `unit_tests/snapshots/<network>/<method>/<params-hash>.json`.
+///
+pub fn snapshot_path(network: &str, method: &str, params: &Value) -> PathBuf {
+    let params_hash: String = starknet_keccak(canonicalize(params).to_string().as_bytes())
+        .to_bytes_be()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("snapshots")
+        .join(network)
+        .join(method)
+        .join(format!("{}.json", &params_hash[..16]))
+}
+
+///
+/// Returns the JSON path of the first difference between two values, if any.
+///
+pub fn first_difference(expected: &Value, actual: &Value, path: &str) -> Option<String> {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => expected
+            .keys()
+            .chain(actual.keys().filter(|key| !expected.contains_key(*key)))
+            .find_map(|key| match (expected.get(key), actual.get(key)) {
+                (Some(expected), Some(actual)) => {
+                    first_difference(expected, actual, &format!("{path}.{key}"))
+                }
+                _ => Some(format!("{path}.{key}")),
+            }),
+        (Value::Array(expected), Value::Array(actual)) if expected.len() == actual.len() => {
+            expected
+                .iter()
+                .zip(actual)
+                .enumerate()
+                .find_map(|(index, (expected, actual))| {
+                    first_difference(expected, actual, &format!("{path}[{index}]"))
+                })
+        }
+        _ if expected == actual => None,
+        _ => Some(path.to_string()),
+    }
+}
+
+///
+/// Sends a request to a single node and compares its canonicalized response, result or error,
+/// with the stored golden file.
+///
+/// A missing snapshot is an error, so that a fresh checkout never passes without comparing
+/// anything. With [`UPDATE_SNAPSHOTS_ENV`] set to `1`, snapshots are written or rewritten with
+/// the current response instead, to be reviewed and committed.
+///
+pub async fn check_snapshot<T: JsonRpcTransport + Sync>(
+    network: &str,
+    transport: &T,
+    method: JsonRpcMethod,
+    params: Value,
+) -> anyhow::Result<()> {
+    let name = method_name(&method)?;
+    let response = transport
+        .send_request::<_, Value>(method, params.clone())
+        .await
+        .map_err(|e| anyhow!("Transport error: {e}"))?;
+
+    let mut response = response_to_value(&response);
+    if let Value::Object(map) = &mut response {
+        map.remove("id");
+        map.remove("jsonrpc");
+    }
+    let actual = json!({
+        "method": name,
+        "params": canonicalize(&params),
+        "response": canonicalize(&response),
+    });
+
+    let path = snapshot_path(network, &name, &params);
+    if env::var(UPDATE_SNAPSHOTS_ENV).as_deref() == Ok("1") {
+        fs::create_dir_all(path.parent().expect("Snapshot path has a parent"))?;
+        fs::write(&path, serde_json::to_string_pretty(&actual)? + "\n")?;
+        return Ok(());
+    }
+    if !path.exists() {
+        return Err(anyhow!(
+            "{name} has no snapshot at {}, set {UPDATE_SNAPSHOTS_ENV}=1 to write it",
+            path.display()
+        ));
+    }
+
+    let expected: Value = serde_json::from_str(&fs::read_to_string(&path)?)?;
+    match first_difference(&expected, &actual, "$") {
+        None => Ok(()),
+        Some(difference) => Err(anyhow!(
+            "{name} differs from snapshot {} at {difference}, set {UPDATE_SNAPSHOTS_ENV}=1 to \
+             rewrite it after review",
+            path.display()
+        )),
+    }
+}
@@ -0,0 +1,90 @@
+use std::env;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use starknet_providers::jsonrpc::{JsonRpcMethod, JsonRpcResponse};
+
+///
+/// Environment variable overriding the path of the corpus file.
+///
+pub const CORPUS_ENV: &str = "DITTO_CORPUS";
+
+///
+/// Default corpus file, relative to the `unit_tests` crate like `secret.json`.
+///
+pub const DEFAULT_CORPUS: &str = "../corpus.jsonl";
+
+static CORPUS_LOCK: Mutex<()> = Mutex::new(());
+
+///
+/// A single JSON-RPC exchange with a node, stored as one line of the corpus.
+///
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CorpusEntry {
+    pub node: String,
+    pub method: String,
+    pub params: Value,
+    pub response: Value,
+    pub latency_ms: u64,
+    pub timestamp: u64,
+}
+
+///
+/// Returns the path of the corpus file.
+///
+pub fn corpus_path() -> String {
+    env::var(CORPUS_ENV).unwrap_or_else(|_| String::from(DEFAULT_CORPUS))
+}
+
+///
+/// Returns the name of a JSON-RPC method, such as `starknet_getBlockWithTxs`.
+///
+pub fn method_name(method: &JsonRpcMethod) -> serde_json::Result<String> {
+    match serde_json::to_value(method)? {
+        Value::String(name) => Ok(name),
+        other => Ok(other.to_string()),
+    }
+}
+
+///
+/// Converts a JSON-RPC response back into the raw object sent by the node.
+///
+pub fn response_to_value(response: &JsonRpcResponse<Value>) -> Value {
+    match response {
+        JsonRpcResponse::Success { id, result } => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "result": result,
+        }),
+        JsonRpcResponse::Error { id, error } => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": {
+                "code": error.code,
+                "message": error.message,
+                "data": error.data,
+            },
+        }),
+    }
+}
+
+///
+/// Appends an entry to the corpus file, creating it if needed.
+///
+/// Each entry is written as a single line while holding a lock, so that tests running in
+/// parallel never interleave their entries.
+///
+pub fn append(path: &str, entry: &CorpusEntry) -> std::io::Result<()> {
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+
+    let _lock = CORPUS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(line.as_bytes())
+}
@@ -0,0 +1,103 @@
+pub mod corpus;
+pub mod recording;
+pub mod replay;
+pub mod suite;
+
+use std::{convert::Infallible, error::Error, fmt};
+
+use anyhow::anyhow;
+use serde_json::Value;
+use starknet_providers::jsonrpc::{JsonRpcMethod, JsonRpcResponse, JsonRpcTransport};
+
+///
+/// Error returned by the transports wrapping a node connection.
+///
+#[derive(Debug)]
+pub enum TransportError<E> {
+    /// The wrapped transport failed to reach the node.
+    Transport(E),
+    /// A request or response could not be converted to or from JSON.
+    Json(serde_json::Error),
+    /// The corpus file could not be read or written.
+    Io(std::io::Error),
+    /// A replayed request is missing from the corpus.
+    NotRecorded {
+        node: String,
+        method: String,
+        params: String,
+    },
+}
+
+impl TransportError<Infallible> {
+    ///
+    /// Converts an error which cannot come from a wrapped transport.
+    ///
+    pub fn widen<E>(self) -> TransportError<E> {
+        match self {
+            TransportError::Transport(e) => match e {},
+            TransportError::Json(e) => TransportError::Json(e),
+            TransportError::Io(e) => TransportError::Io(e),
+            TransportError::NotRecorded {
+                node,
+                method,
+                params,
+            } => TransportError::NotRecorded {
+                node,
+                method,
+                params,
+            },
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for TransportError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransportError::Transport(e) => write!(f, "Transport error: {e}"),
+            TransportError::Json(e) => write!(f, "JSON error: {e}"),
+            TransportError::Io(e) => write!(f, "Corpus error: {e}"),
+            TransportError::NotRecorded {
+                node,
+                method,
+                params,
+            } => write!(f, "Not recorded: {method} on {node} with params {params}"),
+        }
+    }
+}
+
+impl<E: Error> Error for TransportError<E> {}
+
+impl<E> From<serde_json::Error> for TransportError<E> {
+    fn from(e: serde_json::Error) -> Self {
+        TransportError::Json(e)
+    }
+}
+
+impl<E> From<std::io::Error> for TransportError<E> {
+    fn from(e: std::io::Error) -> Self {
+        TransportError::Io(e)
+    }
+}
+
+///
+/// Sends a JSON-RPC request and returns its raw result.
+///
+/// The typed provider drops fields it does not know about, such as the header commitments some
+/// nodes expose, so those are read from the raw response instead.
+///
+pub async fn raw_request<T: JsonRpcTransport + Sync>(
+    transport: &T,
+    method: JsonRpcMethod,
+    params: Value,
+) -> anyhow::Result<Value> {
+    match transport
+        .send_request::<_, Value>(method, params)
+        .await
+        .map_err(|e| anyhow!("Transport error: {e}"))?
+    {
+        JsonRpcResponse::Success { result, .. } => Ok(result),
+        JsonRpcResponse::Error { error, .. } => {
+            Err(anyhow!("{} (code {})", error.message, error.code))
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use starknet_providers::jsonrpc::{JsonRpcMethod, JsonRpcResponse, JsonRpcTransport};
+
+use super::corpus::{append, corpus_path, method_name, response_to_value, CorpusEntry};
+use super::TransportError;
+
+///
+/// Transport which forwards every request to a node and appends the exchange to a JSONL
+/// corpus, along with the node name, latency and timestamp.
+///
+/// Requests which fail before reaching the node are not recorded.
+///
+pub struct RecordingTransport<T> {
+    node: String,
+    inner: T,
+    path: String,
+}
+
+impl<T> RecordingTransport<T> {
+    ///
+    /// Wraps `inner`, recording to the corpus file given by [`corpus_path`].
+    ///
+    pub fn new(node: &str, inner: T) -> Self {
+        Self::with_path(node, inner, &corpus_path())
+    }
+
+    pub fn with_path(node: &str, inner: T, path: &str) -> Self {
+        Self {
+            node: node.to_string(),
+            inner,
+            path: path.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl<T> JsonRpcTransport for RecordingTransport<T>
+where
+    T: JsonRpcTransport + Send + Sync,
+    T::Error: 'static,
+{
+    type Error = TransportError<T::Error>;
+
+    async fn send_request<P, R>(
+        &self,
+        method: JsonRpcMethod,
+        params: P,
+    ) -> Result<JsonRpcResponse<R>, Self::Error>
+    where
+        P: Serialize + Send + Sync,
+        R: DeserializeOwned,
+    {
+        let name = method_name(&method)?;
+        let params = serde_json::to_value(params)?;
+
+        let start = Instant::now();
+        let response = self
+            .inner
+            .send_request::<_, Value>(method, params.clone())
+            .await
+            .map_err(TransportError::Transport)?;
+        let latency = start.elapsed();
+
+        let response = response_to_value(&response);
+        append(
+            &self.path,
+            &CorpusEntry {
+                node: self.node.clone(),
+                method: name,
+                params,
+                response: response.clone(),
+                latency_ms: latency.as_millis() as u64,
+                timestamp: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|timestamp| timestamp.as_secs())
+                    .unwrap_or_default(),
+            },
+        )?;
+
+        Ok(serde_json::from_value(response)?)
+    }
+}
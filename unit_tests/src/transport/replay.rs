@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use starknet_providers::jsonrpc::{JsonRpcMethod, JsonRpcResponse, JsonRpcTransport};
+
+use super::corpus::{corpus_path, method_name, CorpusEntry};
+use super::TransportError;
+
+///
+/// Key under which a recorded response is stored: node name, method and canonical params.
+///
+pub type CorpusKey = (String, String, String);
+
+type Corpus = HashMap<CorpusKey, Value>;
+
+///
+/// Corpora already loaded, by path, so each test binary only parses a corpus file once.
+///
+static CORPORA: Lazy<Mutex<HashMap<String, Arc<Corpus>>>> = Lazy::new(Default::default);
+
+///
+/// Serializes a value with the keys of every object sorted, so that the same params give the
+/// same string whatever the order of their fields, and whichever `serde_json` features are
+/// enabled.
+///
+pub fn canonical_json(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut fields: Vec<(&String, &Value)> = map.iter().collect();
+            fields.sort_by(|(a, _), (b, _)| a.cmp(b));
+            let fields: Vec<String> = fields
+                .into_iter()
+                .map(|(key, value)| {
+                    format!("{}:{}", Value::from(key.as_str()), canonical_json(value))
+                })
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        Value::Array(values) => {
+            let values: Vec<String> = values.iter().map(canonical_json).collect();
+            format!("[{}]", values.join(","))
+        }
+        other => other.to_string(),
+    }
+}
+
+pub fn corpus_key(node: &str, method: &str, params: &Value) -> CorpusKey {
+    (node.to_string(), method.to_string(), canonical_json(params))
+}
+
+///
+/// Parses a corpus file. When a request was recorded several times, the latest response wins.
+///
+pub fn load_corpus(path: &str) -> Result<Corpus, TransportError<std::convert::Infallible>> {
+    let mut corpus = HashMap::new();
+
+    for line in fs::read_to_string(path)?.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let entry: CorpusEntry = serde_json::from_str(line)?;
+        corpus.insert(
+            corpus_key(&entry.node, &entry.method, &entry.params),
+            entry.response,
+        );
+    }
+
+    Ok(corpus)
+}
+
+///
+/// Transport which answers every request from a recorded corpus, without any network access.
+///
+/// Requests missing from the corpus fail with [`TransportError::NotRecorded`].
+///
+pub struct ReplayTransport {
+    node: String,
+    corpus: Arc<Corpus>,
+}
+
+impl ReplayTransport {
+    ///
+    /// Replays the responses of `node` from the corpus file given by [`corpus_path`].
+    ///
+    pub fn new(node: &str) -> Self {
+        Self::with_path(node, &corpus_path())
+    }
+
+    ///
+    /// Replays the responses of `node` from the corpus file at `path`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the corpus cannot be read, since no test can run without it.
+    ///
+    pub fn with_path(node: &str, path: &str) -> Self {
+        let mut corpora = CORPORA.lock().unwrap_or_else(|e| e.into_inner());
+        let corpus = corpora
+            .entry(path.to_string())
+            .or_insert_with(|| {
+                Arc::new(
+                    load_corpus(path)
+                        .unwrap_or_else(|e| panic!("Could not load corpus at {path}: {e}")),
+                )
+            })
+            .clone();
+
+        Self {
+            node: node.to_string(),
+            corpus,
+        }
+    }
+}
+
+#[async_trait]
+impl JsonRpcTransport for ReplayTransport {
+    type Error = TransportError<std::convert::Infallible>;
+
+    async fn send_request<P, R>(
+        &self,
+        method: JsonRpcMethod,
+        params: P,
+    ) -> Result<JsonRpcResponse<R>, Self::Error>
+    where
+        P: Serialize + Send + Sync,
+        R: DeserializeOwned,
+    {
+        let method = method_name(&method)?;
+        let params = serde_json::to_value(params)?;
+
+        match self.corpus.get(&corpus_key(&self.node, &method, &params)) {
+            Some(response) => Ok(serde_json::from_value(response.clone())?),
+            None => Err(TransportError::NotRecorded {
+                node: self.node.clone(),
+                method,
+                params: params.to_string(),
+            }),
+        }
+    }
+}
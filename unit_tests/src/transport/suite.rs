@@ -0,0 +1,79 @@
+use std::env;
+
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Serialize};
+use starknet_providers::jsonrpc::{
+    HttpTransport, HttpTransportError, JsonRpcMethod, JsonRpcResponse, JsonRpcTransport,
+};
+use url::Url;
+
+use super::recording::RecordingTransport;
+use super::replay::ReplayTransport;
+use super::TransportError;
+
+///
+/// Environment variable selecting how the suite reaches nodes: `live` (default), `record` or
+/// `replay`.
+///
+pub const MODE_ENV: &str = "DITTO_MODE";
+
+///
+/// Transport used by the test fixtures, so that the whole suite can be recorded to or replayed
+/// from a corpus without changing any test.
+///
+pub enum SuiteTransport {
+    Live(HttpTransport),
+    Record(RecordingTransport<HttpTransport>),
+    Replay(ReplayTransport),
+}
+
+impl SuiteTransport {
+    ///
+    /// Builds the transport of `node` for the mode selected by [`MODE_ENV`].
+    ///
+    /// # Panics
+    ///
+    /// Panics on an unknown mode, or on an invalid url outside of replay mode.
+    ///
+    pub fn new(node: &str, url: &str) -> Self {
+        let http = || {
+            HttpTransport::new(
+                Url::parse(url).unwrap_or_else(|_| panic!("Error parsing {node} node url")),
+            )
+        };
+
+        match env::var(MODE_ENV).as_deref() {
+            Err(_) | Ok("live") => SuiteTransport::Live(http()),
+            Ok("record") => SuiteTransport::Record(RecordingTransport::new(node, http())),
+            Ok("replay") => SuiteTransport::Replay(ReplayTransport::new(node)),
+            Ok(mode) => panic!("Unknown {MODE_ENV} '{mode}', expected live, record or replay"),
+        }
+    }
+}
+
+#[async_trait]
+impl JsonRpcTransport for SuiteTransport {
+    type Error = TransportError<HttpTransportError>;
+
+    async fn send_request<P, R>(
+        &self,
+        method: JsonRpcMethod,
+        params: P,
+    ) -> Result<JsonRpcResponse<R>, Self::Error>
+    where
+        P: Serialize + Send + Sync,
+        R: DeserializeOwned,
+    {
+        match self {
+            SuiteTransport::Live(transport) => transport
+                .send_request(method, params)
+                .await
+                .map_err(TransportError::Transport),
+            SuiteTransport::Record(transport) => transport.send_request(method, params).await,
+            SuiteTransport::Replay(transport) => transport
+                .send_request(method, params)
+                .await
+                .map_err(TransportError::widen),
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use std::sync::Arc;
+
+use starknet_core::types::{
+    BroadcastedDeclareTransactionV3, BroadcastedDeployAccountTransactionV3,
+    BroadcastedInvokeTransactionV3, DataAvailabilityMode, FieldElement, FlattenedSierraClass,
+    ResourceBounds, ResourceBoundsMapping,
+};
+use starknet_core::utils::get_contract_address;
+use starknet_signers::SigningKey;
+
+use crate::hash::transaction::{declare_v3_hash, deploy_account_v3_hash, invoke_v3_hash};
+
+///
+/// Fee fields of a v3 transaction: resource bounds, tip, paymaster data and data availability
+/// modes.
+///
+/// The default has zero bounds, no tip, no paymaster and L1 data availability, which is enough
+/// for fee estimation and simulation.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct V3Fees {
+    pub resource_bounds: ResourceBoundsMapping,
+    pub tip: u64,
+    pub paymaster_data: Vec<FieldElement>,
+    pub nonce_data_availability_mode: DataAvailabilityMode,
+    pub fee_data_availability_mode: DataAvailabilityMode,
+}
+
+impl Default for V3Fees {
+    fn default() -> Self {
+        Self::l1_gas(0, 0)
+    }
+}
+
+impl V3Fees {
+    ///
+    /// Fees bounded by L1 gas only, as L2 gas is not charged yet.
+    ///
+    pub fn l1_gas(max_amount: u64, max_price_per_unit: u128) -> Self {
+        Self {
+            resource_bounds: ResourceBoundsMapping {
+                l1_gas: ResourceBounds {
+                    max_amount,
+                    max_price_per_unit,
+                },
+                l2_gas: ResourceBounds {
+                    max_amount: 0,
+                    max_price_per_unit: 0,
+                },
+            },
+            tip: 0,
+            paymaster_data: vec![],
+            nonce_data_availability_mode: DataAvailabilityMode::L1,
+            fee_data_availability_mode: DataAvailabilityMode::L1,
+        }
+    }
+
+    pub fn tip(mut self, tip: u64) -> Self {
+        self.tip = tip;
+        self
+    }
+
+    pub fn paymaster_data(mut self, paymaster_data: Vec<FieldElement>) -> Self {
+        self.paymaster_data = paymaster_data;
+        self
+    }
+
+    pub fn data_availability_modes(
+        mut self,
+        nonce_data_availability_mode: DataAvailabilityMode,
+        fee_data_availability_mode: DataAvailabilityMode,
+    ) -> Self {
+        self.nonce_data_availability_mode = nonce_data_availability_mode;
+        self.fee_data_availability_mode = fee_data_availability_mode;
+        self
+    }
+}
+
+///
+/// Signs a transaction hash, returning the `[r, s]` signature expected by accounts.
+///
+pub fn sign(signing_key: &SigningKey, hash: FieldElement) -> Vec<FieldElement> {
+    let signature = signing_key
+        .sign(&hash)
+        .expect("Error while signing the transaction hash");
+    vec![signature.r, signature.s]
+}
+
+///
+/// Builds an unsigned v3 invoke transaction.
+///
+pub fn invoke_v3(
+    sender_address: FieldElement,
+    calldata: Vec<FieldElement>,
+    nonce: FieldElement,
+    fees: &V3Fees,
+    is_query: bool,
+) -> BroadcastedInvokeTransactionV3 {
+    BroadcastedInvokeTransactionV3 {
+        sender_address,
+        calldata,
+        signature: vec![],
+        nonce,
+        resource_bounds: fees.resource_bounds.clone(),
+        tip: fees.tip,
+        paymaster_data: fees.paymaster_data.clone(),
+        account_deployment_data: vec![],
+        nonce_data_availability_mode: fees.nonce_data_availability_mode,
+        fee_data_availability_mode: fees.fee_data_availability_mode,
+        is_query,
+    }
+}
+
+///
+/// Builds an unsigned v3 declare transaction for a Sierra class.
+///
+pub fn declare_v3(
+    sender_address: FieldElement,
+    contract_class: Arc<FlattenedSierraClass>,
+    compiled_class_hash: FieldElement,
+    nonce: FieldElement,
+    fees: &V3Fees,
+    is_query: bool,
+) -> BroadcastedDeclareTransactionV3 {
+    BroadcastedDeclareTransactionV3 {
+        sender_address,
+        compiled_class_hash,
+        signature: vec![],
+        nonce,
+        contract_class,
+        resource_bounds: fees.resource_bounds.clone(),
+        tip: fees.tip,
+        paymaster_data: fees.paymaster_data.clone(),
+        account_deployment_data: vec![],
+        nonce_data_availability_mode: fees.nonce_data_availability_mode,
+        fee_data_availability_mode: fees.fee_data_availability_mode,
+        is_query,
+    }
+}
+
+///
+/// Builds an unsigned v3 deploy account transaction.
+///
+pub fn deploy_account_v3(
+    class_hash: FieldElement,
+    contract_address_salt: FieldElement,
+    constructor_calldata: Vec<FieldElement>,
+    nonce: FieldElement,
+    fees: &V3Fees,
+    is_query: bool,
+) -> BroadcastedDeployAccountTransactionV3 {
+    BroadcastedDeployAccountTransactionV3 {
+        signature: vec![],
+        nonce,
+        contract_address_salt,
+        constructor_calldata,
+        class_hash,
+        resource_bounds: fees.resource_bounds.clone(),
+        tip: fees.tip,
+        paymaster_data: fees.paymaster_data.clone(),
+        nonce_data_availability_mode: fees.nonce_data_availability_mode,
+        fee_data_availability_mode: fees.fee_data_availability_mode,
+        is_query,
+    }
+}
+
+///
+/// Returns the address a deploy account transaction deploys to.
+///
+pub fn deployed_address(tx: &BroadcastedDeployAccountTransactionV3) -> FieldElement {
+    get_contract_address(
+        tx.contract_address_salt,
+        tx.class_hash,
+        &tx.constructor_calldata,
+        FieldElement::ZERO,
+    )
+}
+
+///
+/// Signs an invoke v3 transaction for the network identified by `chain_id`, replacing its
+/// signature.
+///
+pub fn sign_invoke_v3(
+    tx: &mut BroadcastedInvokeTransactionV3,
+    signing_key: &SigningKey,
+    chain_id: FieldElement,
+) {
+    tx.signature = sign(signing_key, invoke_v3_hash(tx, chain_id));
+}
+
+///
+/// Signs a declare v3 transaction for the network identified by `chain_id`, replacing its
+/// signature.
+///
+pub fn sign_declare_v3(
+    tx: &mut BroadcastedDeclareTransactionV3,
+    signing_key: &SigningKey,
+    chain_id: FieldElement,
+) {
+    tx.signature = sign(signing_key, declare_v3_hash(tx, chain_id));
+}
+
+///
+/// Signs a deploy account v3 transaction for the network identified by `chain_id`, replacing its
+/// signature.
+///
+pub fn sign_deploy_account_v3(
+    tx: &mut BroadcastedDeployAccountTransactionV3,
+    signing_key: &SigningKey,
+    chain_id: FieldElement,
+) {
+    tx.signature = sign(signing_key, deploy_account_v3_hash(tx, chain_id));
+}
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use serde_json::json;
+use starknet_core::types::{BlockWithReceipts, FieldElement};
+use starknet_providers::jsonrpc::{JsonRpcMethod, JsonRpcTransport};
+
+use crate::hash::block::{block_hash, BlockHashScheme};
+use crate::transport::raw_request;
+
+use super::commitment::check_block_hash_from_commitments;
+use super::Mismatch;
+
+async fn get_chain_id<T: JsonRpcTransport + Sync>(transport: &T) -> anyhow::Result<FieldElement> {
+    let chain_id = raw_request(transport, JsonRpcMethod::ChainId, json!([])).await?;
+    Ok(serde_json::from_value(chain_id)?)
+}
+
+async fn get_block<T: JsonRpcTransport + Sync>(
+    transport: &T,
+    block_number: u64,
+) -> anyhow::Result<BlockWithReceipts> {
+    let params = json!({ "block_id": { "block_number": block_number } });
+    let block = raw_request(transport, JsonRpcMethod::GetBlockWithReceipts, params).await?;
+    Ok(serde_json::from_value(block)?)
+}
+
+///
+/// Outcome of [`verify_block_hashes`].
+///
+#[derive(Clone, Debug, Default)]
+pub struct BlockHashes {
+    pub mismatches: Vec<Mismatch>,
+    /// Number of block hashes recomputed and compared, over every node.
+    pub compared: usize,
+    /// Blocks whose hash cannot be recomputed from what a node serves, and why.
+    pub skipped: Vec<String>,
+}
+
+///
+/// Recomputes the hash of every block in `blocks` from the content served by each node, and
+/// compares it with the `block_hash` that node returns.
+///
+/// The block hash commits to the header, transactions, signatures and events, so a match shows
+/// that a node serves those exactly as they were sequenced. v0.13.2 blocks are hashed from the
+/// commitments rebuilt by [`check_block_hash_from_commitments`], and skipped when the node
+/// does not serve all of them. Blocks from v0.13.4 onward commit to L2 gas prices, which RPC
+/// v0.7 does not expose, and are skipped. Blocks which cannot be hashed locally otherwise are
+/// reported as mismatches.
+///
+pub async fn verify_block_hashes<T: JsonRpcTransport + Sync>(
+    transports: &HashMap<String, T>,
+    blocks: RangeInclusive<u64>,
+) -> BlockHashes {
+    let mut hashes = BlockHashes::default();
+
+    for (node, transport) in transports {
+        let chain_id = match get_chain_id(transport).await {
+            Ok(chain_id) => chain_id,
+            Err(e) => {
+                hashes.mismatches.push(Mismatch {
+                    node: node.to_string(),
+                    block_number: *blocks.start(),
+                    request: String::from("chainId"),
+                    expected: String::from("a chain id"),
+                    actual: format!("error ({e})"),
+                });
+                continue;
+            }
+        };
+
+        for block_number in blocks.clone() {
+            let mismatch = |expected: String, actual: String| Mismatch {
+                node: node.to_string(),
+                block_number,
+                request: String::from("block_hash"),
+                expected,
+                actual,
+            };
+
+            let block = match get_block(transport, block_number).await {
+                Ok(block) => block,
+                Err(e) => {
+                    hashes.mismatches.push(mismatch(
+                        String::from("an accepted block"),
+                        format!("error ({e})"),
+                    ));
+                    continue;
+                }
+            };
+
+            match BlockHashScheme::for_block(block.block_number, &block.starknet_version) {
+                BlockHashScheme::V0_13_4 => {
+                    hashes.skipped.push(format!(
+                        "[{node}] block {block_number}: v{} commits to L2 gas prices",
+                        block.starknet_version
+                    ));
+                    continue;
+                }
+                BlockHashScheme::V0_13_2 => {
+                    match check_block_hash_from_commitments(node, transport, block_number).await {
+                        Ok(mismatch) => {
+                            hashes.compared += 1;
+                            hashes.mismatches.extend(mismatch);
+                        }
+                        Err(reason) => hashes
+                            .skipped
+                            .push(format!("[{node}] block {block_number}: {reason}")),
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+
+            hashes.compared += 1;
+            match block_hash(&block, chain_id) {
+                Ok(expected) if expected == block.block_hash => {}
+                Ok(expected) => hashes.mismatches.push(mismatch(
+                    format!("{expected:#x}"),
+                    format!("{:#x}", block.block_hash),
+                )),
+                Err(e) => hashes.mismatches.push(mismatch(
+                    format!("{:#x}", block.block_hash),
+                    format!("a block which cannot be hashed locally ({e})"),
+                )),
+            }
+        }
+    }
+
+    hashes
+}
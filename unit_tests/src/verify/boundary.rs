@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use starknet_core::types::{BlockId, FieldElement, StarknetError, StateDiff};
+use starknet_providers::{Provider, ProviderError};
+
+use super::{compare_felt, expect_error, get_state_diff, Mismatch};
+
+///
+/// A state change introduced at a given block, which every node must observe at that block
+/// and not before it.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Boundary {
+    Deployment {
+        address: FieldElement,
+        class_hash: FieldElement,
+    },
+    Declaration {
+        class_hash: FieldElement,
+    },
+    Nonce {
+        address: FieldElement,
+        nonce: FieldElement,
+        /// The contract is deployed in the same block, so it does not exist before it.
+        deployed: bool,
+    },
+}
+
+///
+/// Generates the boundaries introduced by a block state diff.
+///
+pub fn boundaries(state_diff: &StateDiff) -> Vec<Boundary> {
+    let deployments = state_diff
+        .deployed_contracts
+        .iter()
+        .map(|deployed| Boundary::Deployment {
+            address: deployed.address,
+            class_hash: deployed.class_hash,
+        });
+    let declarations = state_diff
+        .declared_classes
+        .iter()
+        .map(|declared| declared.class_hash)
+        .chain(state_diff.deprecated_declared_classes.iter().copied())
+        .map(|class_hash| Boundary::Declaration { class_hash });
+    let nonces = state_diff
+        .nonces
+        .iter()
+        .map(|nonce_update| Boundary::Nonce {
+            address: nonce_update.contract_address,
+            nonce: nonce_update.nonce,
+            deployed: state_diff
+                .deployed_contracts
+                .iter()
+                .any(|deployed| deployed.address == nonce_update.contract_address),
+        });
+
+    deployments.chain(declarations).chain(nonces).collect()
+}
+
+impl Boundary {
+    ///
+    /// Checks a node on both sides of the block at which this boundary was introduced.
+    ///
+    pub async fn check<P: Provider + Sync>(
+        &self,
+        node: &str,
+        client: &P,
+        block_number: u64,
+    ) -> Vec<Mismatch> {
+        let block_id = BlockId::Number(block_number);
+        let previous = block_number.checked_sub(1);
+        let mut mismatches = vec![];
+
+        match *self {
+            Boundary::Deployment {
+                address,
+                class_hash,
+            } => {
+                let request = format!("getClassHashAt({address:#x})");
+                if let Some(previous) = previous {
+                    let actual = client
+                        .get_class_hash_at(BlockId::Number(previous), address)
+                        .await;
+                    mismatches.extend(expect_error(
+                        node,
+                        previous,
+                        request.clone(),
+                        StarknetError::ContractNotFound,
+                        actual,
+                    ));
+                }
+
+                let actual = client.get_class_hash_at(block_id, address).await;
+                mismatches.extend(compare_felt(
+                    node,
+                    block_number,
+                    request,
+                    class_hash,
+                    actual,
+                ));
+            }
+            Boundary::Declaration { class_hash } => {
+                let request = format!("getClass({class_hash:#x})");
+                if let Some(previous) = previous {
+                    let actual = client
+                        .get_class(BlockId::Number(previous), class_hash)
+                        .await;
+                    mismatches.extend(expect_error(
+                        node,
+                        previous,
+                        request.clone(),
+                        StarknetError::ClassHashNotFound,
+                        actual,
+                    ));
+                }
+
+                if let Err(e) = client.get_class(block_id, class_hash).await {
+                    mismatches.push(Mismatch {
+                        node: node.to_string(),
+                        block_number,
+                        request,
+                        expected: String::from("a contract class"),
+                        actual: format!("error ({e})"),
+                    });
+                }
+            }
+            Boundary::Nonce {
+                address,
+                nonce,
+                deployed,
+            } => {
+                let request = format!("getNonce({address:#x})");
+                if let Some(previous) = previous {
+                    let actual = client.get_nonce(BlockId::Number(previous), address).await;
+                    let mismatch = |expected: String, actual: String| Mismatch {
+                        node: node.to_string(),
+                        block_number: previous,
+                        request: request.clone(),
+                        expected,
+                        actual,
+                    };
+
+                    match actual {
+                        Ok(actual) if deployed => mismatches.push(mismatch(
+                            format!("{:?}", StarknetError::ContractNotFound),
+                            format!("{actual:#x}"),
+                        )),
+                        Ok(actual) if actual >= nonce => mismatches.push(mismatch(
+                            format!("a nonce lower than {nonce:#x}"),
+                            format!("{actual:#x}"),
+                        )),
+                        Ok(_) => {}
+                        Err(ProviderError::StarknetError(StarknetError::ContractNotFound))
+                            if deployed => {}
+                        Err(e) => {
+                            let expected = match deployed {
+                                true => format!("{:?}", StarknetError::ContractNotFound),
+                                false => format!("a nonce lower than {nonce:#x}"),
+                            };
+                            mismatches.push(mismatch(expected, format!("error ({e})")));
+                        }
+                    }
+                }
+
+                let actual = client.get_nonce(block_id, address).await;
+                mismatches.extend(compare_felt(node, block_number, request, nonce, actual));
+            }
+        }
+
+        mismatches
+    }
+}
+
+///
+/// Generates the boundaries of every block in `blocks` from the state diffs served by
+/// `reference`, and checks them against every node.
+///
+pub async fn verify_boundaries<R, P>(
+    reference: &R,
+    clients: &HashMap<String, P>,
+    blocks: RangeInclusive<u64>,
+) -> anyhow::Result<Vec<Mismatch>>
+where
+    R: Provider + Sync,
+    P: Provider + Sync,
+{
+    let mut mismatches = vec![];
+
+    for block_number in blocks {
+        let state_diff = get_state_diff(reference, block_number).await?;
+
+        for boundary in boundaries(&state_diff) {
+            for (node, client) in clients {
+                mismatches.extend(boundary.check(node, client, block_number).await);
+            }
+        }
+    }
+
+    Ok(mismatches)
+}
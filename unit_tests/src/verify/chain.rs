@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use starknet_core::types::{BlockId, FieldElement, MaybePendingBlockWithTxHashes};
+use starknet_providers::Provider;
+
+use super::Mismatch;
+
+///
+/// Walks the headers of `blocks` on a single node and returns the first broken link, if any.
+///
+/// A link is broken when a block is missing or pending, when its number is not the one requested,
+/// or when its `parent_hash` differs from the `block_hash` of the previous block. The genesis
+/// block must have a zero parent hash.
+///
+pub async fn first_broken_link<P: Provider + Sync>(
+    node: &str,
+    client: &P,
+    blocks: RangeInclusive<u64>,
+) -> Option<Mismatch> {
+    let mut previous_hash = None;
+
+    for block_number in blocks {
+        let mismatch = |request: &str, expected: String, actual: String| Mismatch {
+            node: node.to_string(),
+            block_number,
+            request: request.to_string(),
+            expected,
+            actual,
+        };
+
+        let block = match client
+            .get_block_with_tx_hashes(BlockId::Number(block_number))
+            .await
+        {
+            Ok(MaybePendingBlockWithTxHashes::Block(block)) => block,
+            Ok(MaybePendingBlockWithTxHashes::PendingBlock(_)) => {
+                return Some(mismatch(
+                    "getBlockWithTxHashes",
+                    String::from("an accepted block"),
+                    String::from("a pending block"),
+                ))
+            }
+            Err(e) => {
+                return Some(mismatch(
+                    "getBlockWithTxHashes",
+                    String::from("an accepted block"),
+                    format!("error ({e})"),
+                ))
+            }
+        };
+
+        if block.block_number != block_number {
+            return Some(mismatch(
+                "block_number",
+                block_number.to_string(),
+                block.block_number.to_string(),
+            ));
+        }
+
+        let expected_parent = previous_hash.or((block_number == 0).then_some(FieldElement::ZERO));
+        if let Some(expected_parent) = expected_parent {
+            if block.parent_hash != expected_parent {
+                return Some(mismatch(
+                    "parent_hash",
+                    format!("{expected_parent:#x}"),
+                    format!("{:#x}", block.parent_hash),
+                ));
+            }
+        }
+
+        previous_hash = Some(block.block_hash);
+    }
+
+    None
+}
+
+///
+/// Checks the parent hash chain of `blocks` on every node, reporting the first broken link of
+/// each node.
+///
+/// This only relies on the headers returned by each node, so it does not need a reference node.
+///
+pub async fn verify_chain_linkage<P: Provider + Sync>(
+    clients: &HashMap<String, P>,
+    blocks: RangeInclusive<u64>,
+) -> Vec<Mismatch> {
+    let mut mismatches = vec![];
+
+    for (node, client) in clients {
+        mismatches.extend(first_broken_link(node, client, blocks.clone()).await);
+    }
+
+    mismatches
+}
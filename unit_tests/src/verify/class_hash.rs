@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use starknet_core::types::{BlockId, FieldElement};
+use starknet_providers::Provider;
+
+use crate::hash::class::class_hash;
+
+use super::{get_state_diff, Mismatch};
+
+///
+/// Fetches a class from a node and checks that it hashes to the requested class hash.
+///
+pub async fn check_class_hash<P: Provider + Sync>(
+    node: &str,
+    client: &P,
+    block_number: u64,
+    expected: FieldElement,
+) -> Option<Mismatch> {
+    let actual = match client
+        .get_class(BlockId::Number(block_number), expected)
+        .await
+    {
+        Ok(class) => match class_hash(&class) {
+            Ok(actual) if actual == expected => return None,
+            Ok(actual) => format!("a class hashing to {actual:#x}"),
+            Err(e) => format!("a class which cannot be hashed ({e})"),
+        },
+        Err(e) => format!("error ({e})"),
+    };
+
+    Some(Mismatch {
+        node: node.to_string(),
+        block_number,
+        request: format!("getClass({expected:#x})"),
+        expected: format!("a class hashing to {expected:#x}"),
+        actual,
+    })
+}
+
+///
+/// Hashes every class declared in `blocks`, as served by each node at its declaration block.
+///
+/// Declared classes are taken from the state diffs of the `reference` node. A class which
+/// hashes to the requested class hash shows the node stored it byte for byte.
+///
+pub async fn verify_class_hashes<R, P>(
+    reference: &R,
+    clients: &HashMap<String, P>,
+    blocks: RangeInclusive<u64>,
+) -> anyhow::Result<Vec<Mismatch>>
+where
+    R: Provider + Sync,
+    P: Provider + Sync,
+{
+    let mut mismatches = vec![];
+
+    for block_number in blocks {
+        let state_diff = get_state_diff(reference, block_number).await?;
+        let class_hashes = state_diff
+            .declared_classes
+            .iter()
+            .map(|declared| declared.class_hash)
+            .chain(state_diff.deprecated_declared_classes.iter().copied());
+
+        for class_hash in class_hashes {
+            for (node, client) in clients {
+                mismatches.extend(check_class_hash(node, client, block_number, class_hash).await);
+            }
+        }
+    }
+
+    Ok(mismatches)
+}
@@ -0,0 +1,349 @@
+use std::collections::{BTreeMap, HashMap};
+use std::ops::RangeInclusive;
+
+use anyhow::anyhow;
+use serde_json::{json, Value};
+use starknet_core::types::{BlockWithReceipts, FieldElement, StateDiff};
+use starknet_providers::jsonrpc::{JsonRpcMethod, JsonRpcTransport};
+
+use crate::hash::block::{block_events, block_hash_v0_13_2, BlockCommitments, BlockHashScheme};
+use crate::hash::commitment::{
+    event_commitment_poseidon, receipt_commitment, state_diff_commitment, state_diff_length,
+    transaction_commitment_poseidon, ReceiptGas,
+};
+use crate::transport::raw_request;
+
+use super::events::receipt_events;
+use super::Mismatch;
+
+pub const TRANSACTION_COMMITMENT: &str = "transaction_commitment";
+pub const EVENT_COMMITMENT: &str = "event_commitment";
+pub const RECEIPT_COMMITMENT: &str = "receipt_commitment";
+pub const STATE_DIFF_COMMITMENT: &str = "state_diff_commitment";
+pub const STATE_DIFF_LENGTH: &str = "state_diff_length";
+
+const COMMITMENTS: [&str; 5] = [
+    TRANSACTION_COMMITMENT,
+    EVENT_COMMITMENT,
+    RECEIPT_COMMITMENT,
+    STATE_DIFF_COMMITMENT,
+    STATE_DIFF_LENGTH,
+];
+
+fn parse_felt(value: &Value) -> Option<FieldElement> {
+    match value {
+        Value::String(hex) => FieldElement::from_hex_be(hex).ok(),
+        Value::Number(number) => number.as_u64().map(FieldElement::from),
+        _ => None,
+    }
+}
+
+///
+/// Reads the gas consumed by a transaction from its raw receipt. Nodes following RPC v0.7 only
+/// report the gas used for data availability, in which case `None` is returned.
+///
+fn receipt_gas(receipt: &Value) -> Option<ReceiptGas> {
+    let resources = receipt.get("execution_resources")?;
+    Some(ReceiptGas {
+        l1_gas: parse_felt(resources.get("l1_gas")?)?,
+        l1_data_gas: parse_felt(resources.get("l1_data_gas")?)?,
+    })
+}
+
+///
+/// Outcome of [`verify_commitments`].
+///
+#[derive(Clone, Debug, Default)]
+pub struct CommitmentChecks {
+    pub mismatches: Vec<Mismatch>,
+    /// Number of commitments and block hashes compared, over every node and block.
+    pub compared: usize,
+    /// What could not be verified for a node and block, such as the receipt commitment of a
+    /// node which neither exposes it nor reports the gas consumed by each transaction.
+    pub unverifiable: Vec<String>,
+}
+
+///
+/// Returns the commitments a block hash needs which are not in `known`.
+///
+fn missing_commitments(known: &BTreeMap<&'static str, FieldElement>) -> Vec<&'static str> {
+    COMMITMENTS
+        .into_iter()
+        .filter(|name| !known.contains_key(name))
+        .collect()
+}
+
+///
+/// Commitments of a block, both as rebuilt locally and as exposed in the raw header.
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Commitments {
+    pub computed: BTreeMap<&'static str, FieldElement>,
+    pub header: BTreeMap<&'static str, FieldElement>,
+}
+
+///
+/// Rebuilds the commitments of a v0.13.2 block from the `getBlockWithReceipts` and
+/// `getStateUpdate` responses of a node.
+///
+/// The receipt commitment needs the total gas consumed by every transaction, so it is only
+/// rebuilt when the node reports it.
+///
+pub async fn get_commitments<T: JsonRpcTransport + Sync>(
+    transport: &T,
+    block_number: u64,
+) -> anyhow::Result<(BlockWithReceipts, Commitments)> {
+    let params = json!({ "block_id": { "block_number": block_number } });
+    let raw_block = raw_request(
+        transport,
+        JsonRpcMethod::GetBlockWithReceipts,
+        params.clone(),
+    )
+    .await?;
+    let raw_state_update = raw_request(transport, JsonRpcMethod::GetStateUpdate, params).await?;
+
+    let block: BlockWithReceipts = serde_json::from_value(raw_block.clone())?;
+    let state_diff: StateDiff = serde_json::from_value(
+        raw_state_update
+            .get("state_diff")
+            .cloned()
+            .ok_or_else(|| anyhow!("State update of block {block_number} has no state diff"))?,
+    )?;
+
+    if BlockHashScheme::for_block(block.block_number, &block.starknet_version)
+        != BlockHashScheme::V0_13_2
+    {
+        return Err(anyhow!(
+            "Block {block_number} ({}) does not commit to receipts and state diffs",
+            block.starknet_version
+        ));
+    }
+
+    let transactions: Vec<_> = block
+        .transactions
+        .iter()
+        .map(|transaction| {
+            (
+                receipt_events(&transaction.receipt).0,
+                &transaction.transaction,
+            )
+        })
+        .collect();
+
+    let mut computed = BTreeMap::from([
+        (
+            TRANSACTION_COMMITMENT,
+            transaction_commitment_poseidon(&transactions),
+        ),
+        (
+            EVENT_COMMITMENT,
+            event_commitment_poseidon(&block_events(&block)),
+        ),
+        (STATE_DIFF_COMMITMENT, state_diff_commitment(&state_diff)),
+        (
+            STATE_DIFF_LENGTH,
+            FieldElement::from(state_diff_length(&state_diff)),
+        ),
+    ]);
+
+    let gas_consumed: Option<Vec<ReceiptGas>> = raw_block["transactions"]
+        .as_array()
+        .map(|transactions| {
+            transactions
+                .iter()
+                .map(|transaction| receipt_gas(&transaction["receipt"]))
+                .collect()
+        })
+        .unwrap_or_default();
+    if let Some(gas) = gas_consumed {
+        let receipts: Vec<_> = block
+            .transactions
+            .iter()
+            .map(|transaction| &transaction.receipt)
+            .zip(gas)
+            .collect();
+        computed.insert(RECEIPT_COMMITMENT, receipt_commitment(&receipts));
+    }
+
+    let header = COMMITMENTS
+        .into_iter()
+        .filter_map(|name| Some((name, parse_felt(raw_block.get(name)?)?)))
+        .collect();
+
+    Ok((block, Commitments { computed, header }))
+}
+
+///
+/// Checks the commitments of a block against the block hash, once all of them are known.
+///
+fn check_block_hash(
+    node: &str,
+    block: &BlockWithReceipts,
+    commitments: &BTreeMap<&'static str, FieldElement>,
+) -> Option<Mismatch> {
+    let commitments = BlockCommitments {
+        transaction_commitment: *commitments.get(TRANSACTION_COMMITMENT)?,
+        event_commitment: *commitments.get(EVENT_COMMITMENT)?,
+        receipt_commitment: *commitments.get(RECEIPT_COMMITMENT)?,
+        state_diff_commitment: *commitments.get(STATE_DIFF_COMMITMENT)?,
+        state_diff_length: u64::try_from(*commitments.get(STATE_DIFF_LENGTH)?).ok()?,
+    };
+
+    let actual = match block_hash_v0_13_2(block, &commitments) {
+        Ok(expected) if expected == block.block_hash => return None,
+        Ok(expected) => format!("{expected:#x}"),
+        Err(e) => format!("a block which cannot be hashed locally ({e})"),
+    };
+
+    Some(Mismatch {
+        node: node.to_string(),
+        block_number: block.block_number,
+        request: String::from("block_hash from commitments"),
+        expected: format!("{:#x}", block.block_hash),
+        actual,
+    })
+}
+
+///
+/// Recomputes the hash of a v0.13.2 block from the commitments rebuilt from the data served by
+/// a node, completed by those of its header.
+///
+/// Returns `Err` with what is missing when the hash cannot be checked, as for nodes which
+/// neither expose the receipt commitment nor the gas consumed by each transaction.
+///
+pub async fn check_block_hash_from_commitments<T: JsonRpcTransport + Sync>(
+    node: &str,
+    transport: &T,
+    block_number: u64,
+) -> Result<Option<Mismatch>, String> {
+    let mismatch = |expected: String, actual: String| Mismatch {
+        node: node.to_string(),
+        block_number,
+        request: String::from("block_hash from commitments"),
+        expected,
+        actual,
+    };
+
+    let (block, commitments) = match get_commitments(transport, block_number).await {
+        Ok(commitments) => commitments,
+        Err(e) => {
+            return Ok(Some(mismatch(
+                String::from("a block and state update to commit to"),
+                format!("error ({e})"),
+            )))
+        }
+    };
+
+    let mut known = commitments.header;
+    known.extend(commitments.computed);
+    let missing = missing_commitments(&known);
+    if !missing.is_empty() {
+        return Err(format!("no {}", missing.join(", ")));
+    }
+
+    Ok(check_block_hash(node, &block, &known))
+}
+
+///
+/// Whether a node reports the gas consumed by each transaction in its receipts, which the
+/// receipt commitment needs. Only nodes following RPC v0.8 onward do.
+///
+pub async fn reports_receipt_gas<T: JsonRpcTransport + Sync>(
+    transport: &T,
+) -> anyhow::Result<bool> {
+    let version = raw_request(transport, JsonRpcMethod::SpecVersion, json!([])).await?;
+    let version = version
+        .as_str()
+        .ok_or_else(|| anyhow!("Invalid spec version {version}"))?;
+
+    let mut numbers = version.split('.').map(str::parse::<u64>);
+    match (numbers.next(), numbers.next()) {
+        (Some(Ok(major)), Some(Ok(minor))) => Ok((major, minor) >= (0, 8)),
+        _ => Err(anyhow!("Invalid spec version {version}")),
+    }
+}
+
+///
+/// Rebuilds the transaction, event, receipt and state diff commitments of `blocks` from the data
+/// served by each node.
+///
+/// Commitments are compared with the header of the node when it exposes them, and with the
+/// commitments rebuilt from the `reference` node otherwise. When every commitment is known, the
+/// block hash is recomputed from them as well. Otherwise, as with RPC v0.7 nodes which expose
+/// neither the header commitments nor the gas consumed by each transaction, the receipt
+/// commitment and block hash are reported as unverifiable. Blocks must be from v0.13.2 onward.
+///
+pub async fn verify_commitments<R, T>(
+    reference: &R,
+    transports: &HashMap<String, T>,
+    blocks: RangeInclusive<u64>,
+) -> anyhow::Result<CommitmentChecks>
+where
+    R: JsonRpcTransport + Sync,
+    T: JsonRpcTransport + Sync,
+{
+    let mut checks = CommitmentChecks::default();
+
+    for block_number in blocks {
+        let (_, reference_commitments) = get_commitments(reference, block_number).await?;
+
+        for (node, transport) in transports {
+            let (block, commitments) = match get_commitments(transport, block_number).await {
+                Ok(commitments) => commitments,
+                Err(e) => {
+                    checks.mismatches.push(Mismatch {
+                        node: node.to_string(),
+                        block_number,
+                        request: String::from("commitments"),
+                        expected: String::from("a block and state update to commit to"),
+                        actual: format!("error ({e})"),
+                    });
+                    continue;
+                }
+            };
+
+            for (name, computed) in &commitments.computed {
+                let expected = commitments
+                    .header
+                    .get(name)
+                    .or(reference_commitments.computed.get(name));
+                let Some(expected) = expected else {
+                    checks.unverifiable.push(format!(
+                        "[{node}] block {block_number}: {name} is neither in the header nor \
+                         rebuilt from the reference"
+                    ));
+                    continue;
+                };
+
+                checks.compared += 1;
+                if expected != computed {
+                    checks.mismatches.push(Mismatch {
+                        node: node.to_string(),
+                        block_number,
+                        request: name.to_string(),
+                        expected: format!("{expected:#x}"),
+                        actual: format!("{computed:#x}"),
+                    });
+                }
+            }
+
+            let mut known = commitments.header.clone();
+            known.extend(commitments.computed);
+            let missing = missing_commitments(&known);
+            if !missing.is_empty() {
+                checks.unverifiable.push(format!(
+                    "[{node}] block {block_number}: block hash, without {}",
+                    missing.join(", ")
+                ));
+                continue;
+            }
+
+            checks.compared += 1;
+            checks
+                .mismatches
+                .extend(check_block_hash(node, &block, &known));
+        }
+    }
+
+    Ok(checks)
+}
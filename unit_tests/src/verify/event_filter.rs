@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use starknet_core::types::{BlockId, BlockTag, EmittedEvent, EventFilter, FieldElement};
+use starknet_providers::Provider;
+
+use super::events::{
+    compare_events, emitted_events, get_block_with_receipts, get_event_pages,
+    get_event_pages_through, matches_filter,
+};
+use super::Mismatch;
+
+///
+/// A named `getEvents` filter shape.
+///
+#[derive(Clone, Debug)]
+pub struct FilterCase {
+    pub name: String,
+    pub filter: EventFilter,
+}
+
+fn case(
+    name: &str,
+    address: Option<FieldElement>,
+    keys: Option<Vec<Vec<FieldElement>>>,
+) -> FilterCase {
+    FilterCase {
+        name: name.to_string(),
+        filter: EventFilter {
+            from_block: None,
+            to_block: None,
+            address,
+            keys,
+        },
+    }
+}
+
+///
+/// Generates address and positional key filters from a list of events, without a block range.
+///
+/// Keys are picked from the events themselves so that every shape selects at least some of
+/// them, except for the shapes which are meant to select nothing.
+///
+pub fn key_filters(events: &[EmittedEvent]) -> Vec<FilterCase> {
+    let unknown_key = FieldElement::from_hex_be("0xd1770").unwrap();
+    let mut cases = vec![
+        case("no filter", None, None),
+        case("empty keys", None, Some(vec![])),
+        case("wildcard key", None, Some(vec![vec![]])),
+        case("unknown key", None, Some(vec![vec![unknown_key]])),
+    ];
+
+    if let Some(event) = events.iter().find(|event| !event.keys.is_empty()) {
+        let address = Some(event.from_address);
+        let first_key = event.keys[0];
+        cases.extend([
+            case("address", address, None),
+            case("first key", None, Some(vec![vec![first_key]])),
+            case(
+                "address and first key",
+                address,
+                Some(vec![vec![first_key]]),
+            ),
+            case(
+                "address and unknown key",
+                address,
+                Some(vec![vec![unknown_key]]),
+            ),
+        ]);
+
+        if let Some(other) = events
+            .iter()
+            .find(|other| other.keys.first().is_some_and(|key| *key != first_key))
+        {
+            let keys = vec![vec![first_key, other.keys[0]]];
+            cases.push(case("either first key", None, Some(keys)));
+        }
+    }
+
+    if let Some(event) = events.iter().find(|event| event.keys.len() >= 2) {
+        let keys = &event.keys;
+        let too_many_keys = keys
+            .iter()
+            .map(|key| vec![*key])
+            .chain([vec![unknown_key]])
+            .collect();
+        cases.extend([
+            case(
+                "wildcard then second key",
+                None,
+                Some(vec![vec![], vec![keys[1]]]),
+            ),
+            case(
+                "first and second key",
+                None,
+                Some(vec![vec![keys[0]], vec![keys[1]]]),
+            ),
+            case("trailing wildcard", None, Some(vec![vec![keys[0]], vec![]])),
+            case("more keys than emitted", None, Some(too_many_keys)),
+        ]);
+    }
+
+    cases
+}
+
+///
+/// Checks a matrix of event filters on every node against the same filters evaluated locally
+/// over the receipts served by `reference`.
+///
+/// Every address and key shape from [`key_filters`] is combined with block ranges given as
+/// numbers and hashes, and with the `latest` tag when the range ends at the chain tip.
+///
+pub async fn verify_filter_matrix<R, P>(
+    reference: &R,
+    clients: &HashMap<String, P>,
+    blocks: RangeInclusive<u64>,
+    chunk_size: u64,
+) -> anyhow::Result<Vec<Mismatch>>
+where
+    R: Provider + Sync,
+    P: Provider + Sync,
+{
+    let (from_block, to_block) = (*blocks.start(), *blocks.end());
+    let latest = reference.block_number().await?;
+
+    let mut events = vec![];
+    let mut hashes = HashMap::new();
+    for block_number in blocks {
+        let block = get_block_with_receipts(reference, block_number).await?;
+        hashes.insert(block_number, block.block_hash);
+        events.extend(emitted_events(&block));
+    }
+
+    let mut block_ranges = vec![
+        (
+            "numbers",
+            BlockId::Number(from_block),
+            BlockId::Number(to_block),
+        ),
+        (
+            "hashes",
+            BlockId::Hash(hashes[&from_block]),
+            BlockId::Hash(hashes[&to_block]),
+        ),
+        (
+            "number to hash",
+            BlockId::Number(from_block),
+            BlockId::Hash(hashes[&to_block]),
+        ),
+    ];
+    if to_block == latest {
+        block_ranges.push((
+            "number to latest",
+            BlockId::Number(from_block),
+            BlockId::Tag(BlockTag::Latest),
+        ));
+    }
+
+    let mut cases = vec![];
+    for shape in key_filters(&events) {
+        for (range, from, to) in &block_ranges {
+            cases.push(FilterCase {
+                name: format!("{} over {range}", shape.name),
+                filter: EventFilter {
+                    from_block: Some(*from),
+                    to_block: Some(*to),
+                    ..shape.filter.clone()
+                },
+            });
+        }
+    }
+
+    Ok(check_filter_cases(clients, &cases, &events, chunk_size, from_block, None).await)
+}
+
+///
+/// Checks the key shapes of [`key_filters`] on every node over `from_block` to the `latest`
+/// tag, keeping only the events of `blocks`, against the same filters evaluated locally over
+/// the receipts served by `reference`.
+///
+/// Unlike the `latest` cases of [`verify_filter_matrix`], `blocks` is fixed, so the check does
+/// not depend on the chain tip. Shapes selecting no event of `blocks` are left out, as nodes
+/// would have to scan the chain up to its tip to answer them.
+///
+pub async fn verify_latest_to_block<R, P>(
+    reference: &R,
+    clients: &HashMap<String, P>,
+    blocks: RangeInclusive<u64>,
+    chunk_size: u64,
+) -> anyhow::Result<Vec<Mismatch>>
+where
+    R: Provider + Sync,
+    P: Provider + Sync,
+{
+    let (from_block, to_block) = (*blocks.start(), *blocks.end());
+
+    let mut events = vec![];
+    for block_number in blocks {
+        let block = get_block_with_receipts(reference, block_number).await?;
+        events.extend(emitted_events(&block));
+    }
+
+    let cases: Vec<FilterCase> = key_filters(&events)
+        .into_iter()
+        .filter(|shape| {
+            events.iter().any(|event| {
+                matches_filter(event, shape.filter.address, shape.filter.keys.as_deref())
+            })
+        })
+        .map(|shape| FilterCase {
+            name: format!("{} over number to latest", shape.name),
+            filter: EventFilter {
+                from_block: Some(BlockId::Number(from_block)),
+                to_block: Some(BlockId::Tag(BlockTag::Latest)),
+                ..shape.filter
+            },
+        })
+        .collect();
+
+    let last_block = Some(to_block);
+    Ok(check_filter_cases(clients, &cases, &events, chunk_size, from_block, last_block).await)
+}
+
+///
+/// Compares the events each node selects for `cases` with the same filters evaluated over
+/// `events`. Pages are cut after `last_block` when it is given.
+///
+async fn check_filter_cases<P: Provider + Sync>(
+    clients: &HashMap<String, P>,
+    cases: &[FilterCase],
+    events: &[EmittedEvent],
+    chunk_size: u64,
+    from_block: u64,
+    last_block: Option<u64>,
+) -> Vec<Mismatch> {
+    let mut mismatches = vec![];
+    for filter_case in cases {
+        let expected: Vec<EmittedEvent> = events
+            .iter()
+            .filter(|event| {
+                matches_filter(
+                    event,
+                    filter_case.filter.address,
+                    filter_case.filter.keys.as_deref(),
+                )
+            })
+            .cloned()
+            .collect();
+
+        for (node, client) in clients {
+            let request = format!("getEvents({})", filter_case.name);
+            let pages = match last_block {
+                Some(last_block) => {
+                    get_event_pages_through(client, &filter_case.filter, chunk_size, last_block)
+                        .await
+                }
+                None => get_event_pages(client, &filter_case.filter, chunk_size).await,
+            };
+            match pages {
+                Ok(pages) => mismatches.extend(compare_events(node, &request, &pages, &expected)),
+                Err(e) => mismatches.push(Mismatch {
+                    node: node.to_string(),
+                    block_number: from_block,
+                    request,
+                    expected: format!("{} events", expected.len()),
+                    actual: format!("error ({e})"),
+                }),
+            }
+        }
+    }
+
+    mismatches
+}
@@ -0,0 +1,307 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::anyhow;
+use starknet_core::types::{
+    BlockId, BlockTag, BlockWithReceipts, EmittedEvent, Event, EventFilter, FieldElement,
+    MaybePendingBlockWithReceipts, MaybePendingBlockWithTxHashes, TransactionReceipt,
+};
+use starknet_providers::Provider;
+
+use super::Mismatch;
+
+///
+/// Most pages [`get_event_pages`] follows before giving up on a query.
+///
+pub const MAX_EVENT_PAGES: usize = 100_000;
+
+///
+/// Fetches every page of a `getEvents` query by following its continuation token.
+///
+/// Fails when a node returns a continuation token twice, or more than [`MAX_EVENT_PAGES`]
+/// pages, rather than paging forever.
+///
+pub async fn get_event_pages<P: Provider + Sync>(
+    client: &P,
+    filter: &EventFilter,
+    chunk_size: u64,
+) -> anyhow::Result<Vec<Vec<EmittedEvent>>> {
+    follow_event_pages(client, filter, chunk_size, None).await
+}
+
+///
+/// Fetches the pages of a `getEvents` query up to `last_block`, for filters ending at a tag
+/// such as `latest`. Paging stops at the first page holding a later event, and later events
+/// are left out of the pages.
+///
+pub async fn get_event_pages_through<P: Provider + Sync>(
+    client: &P,
+    filter: &EventFilter,
+    chunk_size: u64,
+    last_block: u64,
+) -> anyhow::Result<Vec<Vec<EmittedEvent>>> {
+    follow_event_pages(client, filter, chunk_size, Some(last_block)).await
+}
+
+async fn follow_event_pages<P: Provider + Sync>(
+    client: &P,
+    filter: &EventFilter,
+    chunk_size: u64,
+    last_block: Option<u64>,
+) -> anyhow::Result<Vec<Vec<EmittedEvent>>> {
+    let mut pages = vec![];
+    let mut tokens = HashSet::new();
+    let mut continuation_token = None;
+
+    loop {
+        let mut page = client
+            .get_events(filter.clone(), continuation_token, chunk_size)
+            .await?;
+
+        let before_last_block = |event: &EmittedEvent| match last_block {
+            Some(last_block) => event.block_number.is_some_and(|block| block <= last_block),
+            None => true,
+        };
+        let past_last_block = !page.events.iter().all(before_last_block);
+        page.events.retain(before_last_block);
+        pages.push(page.events);
+
+        match page.continuation_token {
+            None => return Ok(pages),
+            Some(_) if past_last_block => return Ok(pages),
+            Some(token) if !tokens.insert(token.clone()) => {
+                return Err(anyhow!(
+                    "continuation token '{token}' returned twice, after {} pages",
+                    pages.len()
+                ))
+            }
+            Some(_) if pages.len() >= MAX_EVENT_PAGES => {
+                return Err(anyhow!("more than {MAX_EVENT_PAGES} pages"))
+            }
+            Some(token) => continuation_token = Some(token),
+        }
+    }
+}
+
+///
+/// Checks whether an event is selected by an address and a list of positional keys.
+///
+/// Each inner list of `keys` holds the accepted values for the key at that position, an empty
+/// list accepting any value.
+///
+pub fn matches_filter(
+    event: &EmittedEvent,
+    address: Option<FieldElement>,
+    keys: Option<&[Vec<FieldElement>]>,
+) -> bool {
+    if address.is_some_and(|address| address != event.from_address) {
+        return false;
+    }
+
+    keys.unwrap_or_default()
+        .iter()
+        .enumerate()
+        .all(|(position, accepted)| {
+            accepted.is_empty()
+                || event
+                    .keys
+                    .get(position)
+                    .is_some_and(|key| accepted.contains(key))
+        })
+}
+
+///
+/// Resolves a block id to a block number on a given node.
+///
+pub async fn resolve_block_number<P: Provider + Sync>(
+    client: &P,
+    block_id: BlockId,
+) -> anyhow::Result<u64> {
+    match block_id {
+        BlockId::Number(block_number) => Ok(block_number),
+        BlockId::Tag(BlockTag::Latest) => Ok(client.block_number().await?),
+        BlockId::Hash(_) => match client.get_block_with_tx_hashes(block_id).await? {
+            MaybePendingBlockWithTxHashes::Block(block) => Ok(block.block_number),
+            MaybePendingBlockWithTxHashes::PendingBlock(_) => {
+                Err(anyhow!("Block hash resolved to a pending block"))
+            }
+        },
+        BlockId::Tag(BlockTag::Pending) => Err(anyhow!("Pending block has no block number")),
+    }
+}
+
+///
+/// Returns the hash of the transaction a receipt belongs to along with the events it emitted.
+///
+pub fn receipt_events(receipt: &TransactionReceipt) -> (FieldElement, &[Event]) {
+    match receipt {
+        TransactionReceipt::Invoke(receipt) => (receipt.transaction_hash, &receipt.events),
+        TransactionReceipt::L1Handler(receipt) => (receipt.transaction_hash, &receipt.events),
+        TransactionReceipt::Declare(receipt) => (receipt.transaction_hash, &receipt.events),
+        TransactionReceipt::Deploy(receipt) => (receipt.transaction_hash, &receipt.events),
+        TransactionReceipt::DeployAccount(receipt) => (receipt.transaction_hash, &receipt.events),
+    }
+}
+
+///
+/// Fetches an accepted block along with its transaction receipts.
+///
+pub async fn get_block_with_receipts<P: Provider + Sync>(
+    client: &P,
+    block_number: u64,
+) -> anyhow::Result<BlockWithReceipts> {
+    match client
+        .get_block_with_receipts(BlockId::Number(block_number))
+        .await?
+    {
+        MaybePendingBlockWithReceipts::Block(block) => Ok(block),
+        MaybePendingBlockWithReceipts::PendingBlock(_) => {
+            Err(anyhow!("Block {block_number} is pending"))
+        }
+    }
+}
+
+///
+/// Lists every event emitted in a block, in the form returned by `getEvents`.
+///
+pub fn emitted_events(block: &BlockWithReceipts) -> impl Iterator<Item = EmittedEvent> + '_ {
+    block.transactions.iter().flat_map(|transaction| {
+        let (transaction_hash, events) = receipt_events(&transaction.receipt);
+        events.iter().map(move |event| EmittedEvent {
+            from_address: event.from_address,
+            keys: event.keys.clone(),
+            data: event.data.clone(),
+            block_hash: Some(block.block_hash),
+            block_number: Some(block.block_number),
+            transaction_hash,
+        })
+    })
+}
+
+///
+/// Rebuilds the result of a `getEvents` query, in chain order, from the receipts returned by
+/// `getBlockWithReceipts`.
+///
+/// Both ends of the filter block range are required and cannot point to the pending block.
+///
+pub async fn events_from_receipts<P: Provider + Sync>(
+    client: &P,
+    filter: &EventFilter,
+) -> anyhow::Result<Vec<EmittedEvent>> {
+    let (Some(from_block), Some(to_block)) = (filter.from_block, filter.to_block) else {
+        return Err(anyhow!("Event filter must have a bounded block range"));
+    };
+    let from_block = resolve_block_number(client, from_block).await?;
+    let to_block = resolve_block_number(client, to_block).await?;
+
+    let mut events = vec![];
+    for block_number in from_block..=to_block {
+        let block = get_block_with_receipts(client, block_number).await?;
+        events.extend(
+            emitted_events(&block)
+                .filter(|event| matches_filter(event, filter.address, filter.keys.as_deref())),
+        );
+    }
+
+    Ok(events)
+}
+
+///
+/// Compares the concatenation of `getEvents` pages with the expected events and describes the
+/// first position at which they diverge, if any.
+///
+pub fn compare_events(
+    node: &str,
+    request: &str,
+    pages: &[Vec<EmittedEvent>],
+    expected: &[EmittedEvent],
+) -> Option<Mismatch> {
+    let actual: Vec<&EmittedEvent> = pages.iter().flatten().collect();
+    let position = match actual
+        .iter()
+        .zip(expected)
+        .position(|(actual, expected)| *actual != expected)
+    {
+        Some(position) => position,
+        None if actual.len() != expected.len() => actual.len().min(expected.len()),
+        None => return None,
+    };
+
+    let page = pages
+        .iter()
+        .scan(0, |count, page| {
+            *count += page.len();
+            Some(*count)
+        })
+        .position(|count| count > position)
+        .unwrap_or(pages.len());
+    let describe = |event: Option<&EmittedEvent>, total: usize| match event {
+        Some(event) => format!(
+            "event from {:#x} in transaction {:#x} ({total} events in total)",
+            event.from_address, event.transaction_hash
+        ),
+        None => format!("no more events ({total} events in total)"),
+    };
+
+    Some(Mismatch {
+        node: node.to_string(),
+        block_number: expected
+            .get(position)
+            .or(actual.get(position).copied())
+            .and_then(|event| event.block_number)
+            .unwrap_or_default(),
+        request: format!("{request}: event {position} on page {page}"),
+        expected: describe(expected.get(position), expected.len()),
+        actual: describe(actual.get(position).copied(), actual.len()),
+    })
+}
+
+///
+/// Pages through `filter` on every node with each of `chunk_sizes`, and checks that the union of
+/// the pages matches the events rebuilt from that node's receipts.
+///
+/// Events are compared in chain order, so any event dropped, repeated or reordered across page
+/// boundaries is reported.
+///
+pub async fn verify_pagination<P: Provider + Sync>(
+    clients: &HashMap<String, P>,
+    filter: &EventFilter,
+    chunk_sizes: &[u64],
+) -> Vec<Mismatch> {
+    let mut mismatches = vec![];
+    let block_number = match filter.from_block {
+        Some(BlockId::Number(block_number)) => block_number,
+        _ => 0,
+    };
+
+    for (node, client) in clients {
+        let expected = match events_from_receipts(client, filter).await {
+            Ok(expected) => expected,
+            Err(e) => {
+                mismatches.push(Mismatch {
+                    node: node.to_string(),
+                    block_number,
+                    request: String::from("getBlockWithReceipts"),
+                    expected: String::from("blocks with receipts"),
+                    actual: format!("error ({e})"),
+                });
+                continue;
+            }
+        };
+
+        for &chunk_size in chunk_sizes {
+            let request = format!("getEvents(chunk_size = {chunk_size})");
+            match get_event_pages(client, filter, chunk_size).await {
+                Ok(pages) => mismatches.extend(compare_events(node, &request, &pages, &expected)),
+                Err(e) => mismatches.push(Mismatch {
+                    node: node.to_string(),
+                    block_number,
+                    request,
+                    expected: format!("{} events", expected.len()),
+                    actual: format!("error ({e})"),
+                }),
+            }
+        }
+    }
+
+    mismatches
+}
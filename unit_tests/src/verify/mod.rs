@@ -0,0 +1,112 @@
+use std::fmt;
+
+use anyhow::anyhow;
+use starknet_core::types::{
+    BlockId, FieldElement, MaybePendingStateUpdate, StarknetError, StateDiff,
+};
+use starknet_providers::{Provider, ProviderError};
+
+pub mod block_hash;
+pub mod boundary;
+pub mod chain;
+pub mod class_hash;
+pub mod commitment;
+pub mod event_filter;
+pub mod events;
+pub mod state;
+pub mod transaction_hash;
+
+///
+/// A node response which does not match the value Ditto expected for a given request.
+///
+/// Verifiers collect these instead of panicking on the first error so that a single run
+/// reports every faulty read across all nodes.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    pub node: String,
+    pub block_number: u64,
+    pub request: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{}] block {}: {} returned {}, expected {}",
+            self.node, self.block_number, self.request, self.actual, self.expected
+        )
+    }
+}
+
+///
+/// Compares a felt returned by a node with its expected value.
+///
+/// Errors are reported as mismatches, since the expected value is known to exist.
+///
+pub fn compare_felt(
+    node: &str,
+    block_number: u64,
+    request: String,
+    expected: FieldElement,
+    actual: Result<FieldElement, ProviderError>,
+) -> Option<Mismatch> {
+    let actual = match actual {
+        Ok(actual) if actual == expected => return None,
+        Ok(actual) => format!("{actual:#x}"),
+        Err(e) => format!("error ({e})"),
+    };
+
+    Some(Mismatch {
+        node: node.to_string(),
+        block_number,
+        request,
+        expected: format!("{expected:#x}"),
+        actual,
+    })
+}
+
+///
+/// Checks that a node answered a request with the expected Starknet error.
+///
+pub fn expect_error<T>(
+    node: &str,
+    block_number: u64,
+    request: String,
+    expected: StarknetError,
+    actual: Result<T, ProviderError>,
+) -> Option<Mismatch> {
+    let actual = match actual {
+        Err(ProviderError::StarknetError(e)) if e == expected => return None,
+        Err(e) => format!("error ({e})"),
+        Ok(_) => String::from("a result"),
+    };
+
+    Some(Mismatch {
+        node: node.to_string(),
+        block_number,
+        request,
+        expected: format!("{expected:?}"),
+        actual,
+    })
+}
+
+///
+/// Fetches the state diff of an accepted block.
+///
+pub async fn get_state_diff<P: Provider + Sync>(
+    client: &P,
+    block_number: u64,
+) -> anyhow::Result<StateDiff> {
+    match client
+        .get_state_update(BlockId::Number(block_number))
+        .await?
+    {
+        MaybePendingStateUpdate::Update(state_update) => Ok(state_update.state_diff),
+        MaybePendingStateUpdate::PendingUpdate(_) => {
+            Err(anyhow!("State update for block {block_number} is pending"))
+        }
+    }
+}
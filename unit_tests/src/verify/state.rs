@@ -0,0 +1,187 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ops::RangeInclusive;
+
+use starknet_core::types::{BlockId, FieldElement, StateDiff};
+use starknet_providers::Provider;
+
+use super::{compare_felt, get_state_diff, Mismatch};
+
+///
+/// Expected contract state rebuilt by replaying consecutive state diffs.
+///
+/// Only the contracts passed to [`StateReplay::new`] are tracked. A value becomes known once a
+/// replayed diff writes it, or once the contract is deployed (its nonce is then 0). Storage slots
+/// which were never written since the start of the replay are not checked.
+///
+#[derive(Clone, Debug, Default)]
+pub struct StateReplay {
+    contracts: HashSet<FieldElement>,
+    pub storage: BTreeMap<(FieldElement, FieldElement), FieldElement>,
+    pub nonces: BTreeMap<FieldElement, FieldElement>,
+    pub class_hashes: BTreeMap<FieldElement, FieldElement>,
+}
+
+impl StateReplay {
+    pub fn new(contracts: impl IntoIterator<Item = FieldElement>) -> Self {
+        Self {
+            contracts: contracts.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    ///
+    /// Applies a single block state diff on top of the current state.
+    ///
+    pub fn apply(&mut self, state_diff: &StateDiff) {
+        for deployed in &state_diff.deployed_contracts {
+            if self.contracts.contains(&deployed.address) {
+                self.class_hashes
+                    .insert(deployed.address, deployed.class_hash);
+                self.nonces
+                    .entry(deployed.address)
+                    .or_insert(FieldElement::ZERO);
+            }
+        }
+
+        for replaced in &state_diff.replaced_classes {
+            if self.contracts.contains(&replaced.contract_address) {
+                self.class_hashes
+                    .insert(replaced.contract_address, replaced.class_hash);
+            }
+        }
+
+        for storage_diff in &state_diff.storage_diffs {
+            if self.contracts.contains(&storage_diff.address) {
+                for entry in &storage_diff.storage_entries {
+                    self.storage
+                        .insert((storage_diff.address, entry.key), entry.value);
+                }
+            }
+        }
+
+        for nonce_update in &state_diff.nonces {
+            if self.contracts.contains(&nonce_update.contract_address) {
+                self.nonces
+                    .insert(nonce_update.contract_address, nonce_update.nonce);
+            }
+        }
+    }
+
+    ///
+    /// Number of reads [`StateReplay::check`] compares on each node.
+    ///
+    pub fn reads(&self) -> usize {
+        self.storage.len() + self.nonces.len() + self.class_hashes.len()
+    }
+
+    ///
+    /// Queries `getStorageAt`, `getNonce` and `getClassHashAt` on a node for every known value
+    /// and returns the reads which differ from the replayed state.
+    ///
+    pub async fn check<P: Provider + Sync>(
+        &self,
+        node: &str,
+        client: &P,
+        block_number: u64,
+    ) -> Vec<Mismatch> {
+        let block_id = BlockId::Number(block_number);
+        let mut mismatches = vec![];
+
+        for (&(address, key), &expected) in &self.storage {
+            let actual = client.get_storage_at(address, key, block_id).await;
+            mismatches.extend(compare_felt(
+                node,
+                block_number,
+                format!("getStorageAt({address:#x}, {key:#x})"),
+                expected,
+                actual,
+            ));
+        }
+
+        for (&address, &expected) in &self.nonces {
+            let actual = client.get_nonce(block_id, address).await;
+            mismatches.extend(compare_felt(
+                node,
+                block_number,
+                format!("getNonce({address:#x})"),
+                expected,
+                actual,
+            ));
+        }
+
+        for (&address, &expected) in &self.class_hashes {
+            let actual = client.get_class_hash_at(block_id, address).await;
+            mismatches.extend(compare_felt(
+                node,
+                block_number,
+                format!("getClassHashAt({address:#x})"),
+                expected,
+                actual,
+            ));
+        }
+
+        mismatches
+    }
+}
+
+///
+/// Outcome of [`verify_state_reads`].
+///
+#[derive(Clone, Debug, Default)]
+pub struct StateReads {
+    pub mismatches: Vec<Mismatch>,
+    /// Number of reads compared with the replayed state, over every node and block.
+    pub compared: usize,
+}
+
+///
+/// Returns the contracts deployed, written to or whose nonce changed in the state diffs of
+/// `blocks`, as served by `reference`.
+///
+pub async fn touched_contracts<R: Provider + Sync>(
+    reference: &R,
+    blocks: RangeInclusive<u64>,
+) -> anyhow::Result<Vec<FieldElement>> {
+    let mut contracts = BTreeSet::new();
+
+    for block_number in blocks {
+        let state_diff = get_state_diff(reference, block_number).await?;
+        contracts.extend(state_diff.deployed_contracts.iter().map(|d| d.address));
+        contracts.extend(state_diff.storage_diffs.iter().map(|d| d.address));
+        contracts.extend(state_diff.nonces.iter().map(|n| n.contract_address));
+    }
+
+    Ok(contracts.into_iter().collect())
+}
+
+///
+/// Replays the state diffs of `blocks` for `contracts`, as served by `reference`, and checks
+/// the storage, nonce and class hash reads of every node against the replayed state at each
+/// block.
+///
+pub async fn verify_state_reads<R, P>(
+    reference: &R,
+    clients: &HashMap<String, P>,
+    contracts: &[FieldElement],
+    blocks: RangeInclusive<u64>,
+) -> anyhow::Result<StateReads>
+where
+    R: Provider + Sync,
+    P: Provider + Sync,
+{
+    let mut replay = StateReplay::new(contracts.iter().copied());
+    let mut reads = StateReads::default();
+
+    for block_number in blocks {
+        replay.apply(&get_state_diff(reference, block_number).await?);
+
+        for (node, client) in clients {
+            reads
+                .mismatches
+                .extend(replay.check(node, client, block_number).await);
+            reads.compared += replay.reads();
+        }
+    }
+
+    Ok(reads)
+}
@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use starknet_core::types::{BlockId, MaybePendingBlockWithTxs};
+use starknet_providers::Provider;
+
+use crate::hash::transaction::transaction_hashes;
+
+use super::Mismatch;
+
+///
+/// Recomputes the hash of every transaction in `blocks` from the fields served by each node, and
+/// compares it with the `transaction_hash` that node returns.
+///
+/// Each node is checked on its own, using the chain id it reports, so a node serving mangled
+/// calldata, signatures or resource bounds is caught even when no other node is available.
+///
+pub async fn verify_transaction_hashes<P: Provider + Sync>(
+    clients: &HashMap<String, P>,
+    blocks: RangeInclusive<u64>,
+) -> Vec<Mismatch> {
+    let mut mismatches = vec![];
+
+    for (node, client) in clients {
+        let chain_id = match client.chain_id().await {
+            Ok(chain_id) => chain_id,
+            Err(e) => {
+                mismatches.push(Mismatch {
+                    node: node.to_string(),
+                    block_number: *blocks.start(),
+                    request: String::from("chainId"),
+                    expected: String::from("a chain id"),
+                    actual: format!("error ({e})"),
+                });
+                continue;
+            }
+        };
+
+        for block_number in blocks.clone() {
+            let transactions = match client
+                .get_block_with_txs(BlockId::Number(block_number))
+                .await
+            {
+                Ok(MaybePendingBlockWithTxs::Block(block)) => block.transactions,
+                Ok(MaybePendingBlockWithTxs::PendingBlock(_)) => {
+                    mismatches.push(Mismatch {
+                        node: node.to_string(),
+                        block_number,
+                        request: String::from("getBlockWithTxs"),
+                        expected: String::from("an accepted block"),
+                        actual: String::from("a pending block"),
+                    });
+                    continue;
+                }
+                Err(e) => {
+                    mismatches.push(Mismatch {
+                        node: node.to_string(),
+                        block_number,
+                        request: String::from("getBlockWithTxs"),
+                        expected: String::from("an accepted block"),
+                        actual: format!("error ({e})"),
+                    });
+                    continue;
+                }
+            };
+
+            for (index, transaction) in transactions.iter().enumerate() {
+                let served = *transaction.transaction_hash();
+                let expected = transaction_hashes(transaction, chain_id);
+
+                if !expected.contains(&served) {
+                    mismatches.push(Mismatch {
+                        node: node.to_string(),
+                        block_number,
+                        request: format!("transaction_hash of transaction {index}"),
+                        expected: format!("{:#x}", expected[0]),
+                        actual: format!("{served:#x}"),
+                    });
+                }
+            }
+        }
+    }
+
+    mismatches
+}
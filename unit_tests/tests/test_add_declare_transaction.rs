@@ -0,0 +1,112 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::assert_matches::assert_matches;
+
+use macro_utils::TestConfig;
+use starknet_core::types::{BroadcastedTransaction, FieldElement, TransactionReceipt};
+use unit_tests::declare::{load_legacy, load_sierra};
+use unit_tests::devnet::DevnetHarness;
+
+/// Test for the `add_declare_transaction` RPC method
+/// Submit a new class declaration transaction
+///
+/// # Arguments
+/// * `declare_transaction` - A declare transaction, v1 for a legacy class or v2 for a Sierra
+///    class, with following fields:
+///       * `sender_address` - The address of the account declaring the class
+///       * `contract_class` - The class to declare
+///       * `compiled_class_hash` - The hash of the CASM compilation of a Sierra class
+///       * `max_fee` - The maximal fee willing to be paid
+///       * `signature` - The transaction signature
+///       * `nonce` - The nonce of the transaction
+///
+/// # Returns
+/// * `result` - The result of the transaction submission, with the transaction hash and the
+///    hash of the declared class
+///
+/// # Errors
+/// * `class_already_declared` - If the class is already declared
+/// * `compilation_failed` - If the Sierra class cannot be compiled
+/// * `compiled_class_hash_mismatch` - If the compiled class hash does not match the class
+/// * `contract_class_size_is_too_large` - If the class is too large
+///
+/// These tests declare the classes of `unit_tests/artifacts/` on the devnet configured in
+/// `../secret.json`, each through a different node so that the write API of every node is
+/// exercised. A class can only be declared once, so they are skipped on a devnet which already
+/// declared them, as well as when no devnet is configured.
+
+///
+/// Max fee of declare transactions, which cost more than transfers.
+///
+const MAX_FEE: u64 = 100_000_000_000_000_000;
+
+///
+/// Unit test for `starknet_addDeclareTransaction`
+///
+/// purpose: declare a Sierra class with a v2 transaction and a legacy class with a v1
+/// transaction, through different nodes.
+/// success case: both declarations succeed, and every node following the devnet knows the
+/// classes.
+///
+#[rstest]
+#[tokio::test]
+async fn work_add_declare_transaction(config: TestConfig) {
+    let harness = match DevnetHarness::from_test_config(&config).await {
+        Ok(harness) => harness,
+        Err(reason) => {
+            println!("{reason}");
+            return;
+        }
+    };
+
+    let artifacts = [
+        load_sierra(
+            "artifacts/minimal_contract.sierra.json",
+            "artifacts/minimal_contract.casm.json",
+        )
+        .expect("Failed to load Sierra artifacts"),
+        load_legacy("artifacts/empty_contract_legacy.json")
+            .expect("Failed to load legacy artifact"),
+    ];
+
+    let nodes: Vec<&String> = harness.nodes.keys().collect();
+    for (artifact, submitter) in artifacts.iter().zip(nodes.iter().cycle()) {
+        let class_hash = artifact.class_hash();
+        let declared = harness
+            .is_declared(class_hash)
+            .await
+            .expect("Error while checking the class");
+        if declared {
+            println!("Class {class_hash:#x} is already declared on the devnet, skipping");
+            continue;
+        }
+
+        let transaction = harness
+            .builder(submitter, 0)
+            .await
+            .expect("Error while building the transaction")
+            .max_fee(FieldElement::from(MAX_FEE))
+            .build_declare(artifact)
+            .expect("Error while building the declare transaction");
+
+        let transaction_hash = harness
+            .submit(submitter, BroadcastedTransaction::Declare(transaction))
+            .await
+            .unwrap_or_else(|e| panic!("[{submitter}] Error while declaring {class_hash:#x}: {e}"));
+
+        for node in harness.nodes.keys() {
+            let receipt = harness
+                .wait_for_acceptance(node, transaction_hash)
+                .await
+                .unwrap_or_else(|e| panic!("{e}"));
+            assert_matches!(receipt.receipt, TransactionReceipt::Declare(_), "{node}");
+        }
+        assert!(harness
+            .is_declared(class_hash)
+            .await
+            .expect("Error while checking the class"));
+    }
+}
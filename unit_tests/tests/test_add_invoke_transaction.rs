@@ -1,194 +1,230 @@
-// #![feature(assert_matches)]
-
-// mod common;
-// use common::*;
-// use starknet::accounts::{Account, Call, ExecutionEncoding, SingleOwnerAccount};
-// use starknet::signers::{LocalWallet, SigningKey};
-// use starknet_core::chain_id;
-// use starknet_core::types::{
-//     BroadcastedInvokeTransaction, FieldElement, StarknetError, TransactionStatus,
-// };
-// use starknet_providers::{
-//     jsonrpc::{HttpTransport, JsonRpcClient},
-//     Provider, ProviderError,
-// };
-// use std::assert_matches::assert_matches;
-// use std::thread;
-// use std::time::Duration;
-
-// /// Test for the `add_invoke_transaction` Deoxys RPC method
-// /// Submit a new transaction to be added to the chain
-// ///
-// /// # Arguments
-// /// * `invoke_transaction` - An invoke transaction,
-// ///     with following fields:
-// ///         * `type` - INVOKE
-// ///         * `sender_address` - The address of the sender
-// ///         * `calldata` - The calldata to send
-// ///         * `max_fee` - The maximum fees sender is willing to pay
-// ///         * `version` - The version of the transaction
-// ///         * `signature` - The transaction signature
-// ///         * `nonce` - The nonce of the transaction
-// ///
-// /// # Returns
-// /// * `result` - The result of the transaction submission, with the transaction hash that has been submitted
-// ///
-// /// # Errors
-// /// * `invalid_transaction_nonce` - If the transaction nonce is invalid
-// /// * `insufficient_account_balance` - If the account balance is insufficient
-// /// * `insufficient_max_fee` - If the max fee is insufficient
-// /// * `invalid_transaction_nonce` - If the transaction nonce is invalid
-// /// * `validation_failure` - If the transaction validation fails
-// /// * `non_account` - If the sender address is not a valid account
-// /// * `duplicate_transaction` - If a transaction with same params already exists
-// /// * `unsupported_transaction_version` - If the transaction version is not supported
-// /// * `unexpected_error` - If an unexpected error occurs
-
-// /// Following tests runs using V1 Invoke Transaction (params follow starknet-rs implementation)
-
-// /// Invoke transaction method is used to trigger a transaction by using a valid account and the execute method.
-// /// When used, its change the state (write), in opposition to the "call" method which is read-only.
-
-// pub const TESTNET: &str = "sepolia";
-
-// fn get_account(
-//     provider: JsonRpcClient<HttpTransport>,
-//     address: FieldElement,
-//     chain_id: FieldElement,
-//     exec_encoding: ExecutionEncoding,
-// ) -> SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet> {
-//     let signer = LocalWallet::from(SigningKey::from_secret_scalar(
-//         FieldElement::from_hex_be("YOUR_PRIVATE_KEY_IN_HEX_HERE").unwrap(),
-//     ));
-
-//     let account = SingleOwnerAccount::new(provider, signer, address, chain_id, exec_encoding);
-
-//     account
-// }
-
-// #[ignore = "For this one, you need to submit a valid account (private key) and address"]
-// #[rstest]
-// #[tokio::test]
-// async fn fail_if_param_(deoxys: JsonRpcClient<HttpTransport>) {
-//     let account = get_account(
-//         deoxys,
-//         FieldElement::from_hex_be("YOUR_ADDRESS_IN_HEX_HERE").unwrap(),
-//         FieldElement::from_hex_be(TESTNET).unwrap(),
-//         ExecutionEncoding::New,
-//     );
-
-//     // This part is for the intern call so we dont manage it at this level
-//     // let invalid_invoke_transaction = BroadcastedInvokeTransaction {
-//     //     sender_address: FieldElement::from_hex_be("valid_address").unwrap(),
-//     //     calldata: vec![FieldElement::from_hex_be("calldata_array").unwrap()],
-//     //     max_fee: FieldElement::from_hex_be("0x0ffffffff").unwrap(),
-//     //     signature: vec![FieldElement::from_hex_be("signature_array").unwrap()],
-//     //     nonce: FieldElement::from_hex_be("0x000000").unwrap(), //here nonce is invalid
-//     //     is_query: false,
-//     // };
-//     let invalid_invoke_transaction = Call {
-//         to: FieldElement::from_hex_be("contract_address").unwrap(),
-//         selector: FieldElement::from_hex_be("selector").unwrap(), //use transfert here for example
-//         calldata: vec![FieldElement::from_hex_be("calldata_array").unwrap()],
-//     };
-
-//     let invalid_transactions = vec![invalid_invoke_transaction];
-
-//     let execution = account.execute(invalid_transactions);
-//     let invoked_tx_hash = execution.send().await.unwrap().transaction_hash;
-
-//     // let response_deoxys = deoxys
-//     //     .add_invoke_transaction(invalid_invoke_transaction)
-//     //     .await;
-
-//     assert_matches!(
-//         response_deoxys,
-//         Err(ProviderError::StarknetError(
-//             StarknetError::InvalidTransactionNonce
-//         ))
-//     );
-// }
-
-// #[ignore = "For this one, you need to submit a valid account (private key) and address"]
-// #[rstest]
-// #[tokio::test]
-// async fn fail_if_insufficient_max_fee(deoxys: JsonRpcClient<HttpTransport>) {
-//     let invalid_invoke_transaction = BroadcastedInvokeTransaction {
-//         sender_address: FieldElement::from_hex_be("valid_address").unwrap(),
-//         calldata: vec![FieldElement::from_hex_be("calldata_array").unwrap()],
-//         max_fee: FieldElement::from_hex_be("0x000000").unwrap(), //here max_fee is insufficient
-//         signature: vec![FieldElement::from_hex_be("signature_array").unwrap()],
-//         nonce: FieldElement::from_hex_be("0x01").unwrap(),
-//         is_query: false,
-//     };
-
-//     let response_deoxys = deoxys
-//         .add_invoke_transaction(invalid_invoke_transaction)
-//         .await;
-
-//     assert_matches!(
-//         response_deoxys,
-//         Err(ProviderError::StarknetError(
-//             StarknetError::InsufficientMaxFee
-//         ))
-//     );
-// }
-
-// #[ignore = "For this one, you need to submit a valid account (private key) and address"]
-// #[rstest]
-// #[tokio::test]
-// async fn fail_if_bad_calldata(deoxys: JsonRpcClient<HttpTransport>) {
-//     let invalid_invoke_transaction = BroadcastedInvokeTransaction {
-//         sender_address: FieldElement::from_hex_be("valid_address").unwrap(),
-//         calldata: vec![FieldElement::from_hex_be("0x000000").unwrap()], //here calldata is invalid
-//         max_fee: FieldElement::from_hex_be("0x0ffffffff").unwrap(),
-//         signature: vec![FieldElement::from_hex_be("signature_array").unwrap()],
-//         nonce: FieldElement::from_hex_be("0x01").unwrap(),
-//         is_query: false,
-//     };
-
-//     let response_deoxys = deoxys
-//         .add_invoke_transaction(invalid_invoke_transaction)
-//         .await;
-
-//     assert_matches!(
-//         response_deoxys,
-//         Err(ProviderError::StarknetError(
-//             StarknetError::ValidationFailure
-//         ))
-//     );
-// }
-
-// #[ignore = "For this one, you need to submit a valid account (private key) and address"]
-// #[rstest]
-// #[tokio::test]
-// async fn works_ok_with_valid_params(deoxys: JsonRpcClient<HttpTransport>) {
-//     let valid_invoke_transaction = BroadcastedInvokeTransaction {
-//         sender_address: FieldElement::from_hex_be("valid_address").unwrap(),
-//         calldata: vec![FieldElement::from_hex_be("calldata_array").unwrap()],
-//         max_fee: FieldElement::from_hex_be("0x0ffffffff").unwrap(),
-//         signature: vec![FieldElement::from_hex_be("signature_array").unwrap()],
-//         nonce: FieldElement::from_hex_be("0x01").unwrap(),
-//         is_query: false,
-//     };
-
-//     //Here we added a valid transaction
-//     let response_deoxys = deoxys
-//         .add_invoke_transaction(valid_invoke_transaction)
-//         .await;
-
-//     //Now, if the transaction is valid, the rpc call response contain the transaction hash
-//     let transaction_submitted_hash = response_deoxys
-//         .expect("Transaction submition failed")
-//         .transaction_hash;
-
-//     //Wait for the transaction to be added to the chain
-//     thread::sleep(Duration::from_secs(15));
-
-//     //Let's check the transaction status
-//     let transaction_status = deoxys
-//         .get_transaction_status(transaction_submitted_hash)
-//         .await;
-
-//     assert_matches!(transaction_status.unwrap(), TransactionStatus::Received);
-// }
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::assert_matches::assert_matches;
+
+use macro_utils::TestConfig;
+use starknet_accounts::Call;
+use starknet_core::types::{FieldElement, StarknetError, TransactionReceipt};
+use starknet_core::utils::get_selector_from_name;
+use starknet_providers::ProviderError;
+use unit_tests::builder::{Mutation, TransactionVersion};
+use unit_tests::devnet::DevnetHarness;
+
+/// Test for the `add_invoke_transaction` RPC method
+/// Submit a new transaction to be added to the chain
+///
+/// # Arguments
+/// * `invoke_transaction` - An invoke transaction,
+///     with following fields:
+///         * `type` - INVOKE
+///         * `sender_address` - The address of the sender
+///         * `calldata` - The calldata to send
+///         * `max_fee` - The maximum fees sender is willing to pay
+///         * `version` - The version of the transaction
+///         * `signature` - The transaction signature
+///         * `nonce` - The nonce of the transaction
+///
+/// # Returns
+/// * `result` - The result of the transaction submission, with the transaction hash that has been submitted
+///
+/// # Errors
+/// * `invalid_transaction_nonce` - If the transaction nonce is invalid
+/// * `insufficient_account_balance` - If the account balance is insufficient
+/// * `insufficient_max_fee` - If the max fee is insufficient
+/// * `validation_failure` - If the transaction validation fails
+/// * `non_account` - If the sender address is not a valid account
+/// * `duplicate_transaction` - If a transaction with same params already exists
+/// * `unsupported_transaction_version` - If the transaction version is not supported
+/// * `unexpected_error` - If an unexpected error occurs
+///
+/// These tests send real transactions from the accounts predeployed by the devnet configured
+/// in `../secret.json`, through the write API of the devnet and of every node following it.
+/// They are skipped when no devnet is configured.
+
+///
+/// Predeployed accounts sending the transactions of each test. Tests run in parallel, and the
+/// nonce is checked before the signature and the balance, so tests sharing an account could
+/// get `INVALID_TRANSACTION_NONCE` instead of the error they expect.
+///
+const WORK_ACCOUNT: usize = 0;
+const INVALID_NONCE_ACCOUNT: usize = 1;
+const BAD_SIGNATURE_ACCOUNT: usize = 2;
+const INSUFFICIENT_BALANCE_ACCOUNT: usize = 3;
+
+///
+/// Predeployed account receiving every transfer, which never sends any transaction.
+///
+const RECIPIENT_ACCOUNT: usize = 4;
+
+///
+/// A transfer of 1 wei of Ether to the recipient account.
+///
+fn transfer(harness: &DevnetHarness) -> Call {
+    Call {
+        to: FieldElement::from_hex_be(mainnet::contract::STARKGATE_ETHER).unwrap(),
+        selector: get_selector_from_name("transfer").unwrap(),
+        calldata: vec![
+            harness.accounts[RECIPIENT_ACCOUNT].address,
+            FieldElement::ONE,
+            FieldElement::ZERO,
+        ],
+    }
+}
+
+///
+/// Unit test for `starknet_addInvokeTransaction`
+///
+/// purpose: send v1 and v3 transfers through every node.
+/// success case: every transfer is accepted and succeeds.
+///
+#[rstest]
+#[tokio::test]
+async fn work_add_invoke_transaction(config: TestConfig) {
+    let harness = match DevnetHarness::from_test_config(&config).await {
+        Ok(harness) => harness,
+        Err(reason) => {
+            println!("{reason}");
+            return;
+        }
+    };
+
+    for node in harness.nodes.keys() {
+        for version in [TransactionVersion::V1, TransactionVersion::V3] {
+            let transaction = harness
+                .builder(node, WORK_ACCOUNT)
+                .await
+                .expect("Error while building the transaction")
+                .calls(vec![transfer(&harness)])
+                .version(version)
+                .build();
+
+            let transaction_hash = harness
+                .submit(node, transaction)
+                .await
+                .unwrap_or_else(|e| panic!("{node}: error while sending {version:?} invoke: {e}"));
+            let receipt = harness
+                .wait_for_acceptance(node, transaction_hash)
+                .await
+                .unwrap_or_else(|e| panic!("{e}"));
+
+            assert_matches!(receipt.receipt, TransactionReceipt::Invoke(_));
+        }
+    }
+}
+
+///
+/// Unit test for `starknet_addInvokeTransaction`
+///
+/// purpose: send a transfer with an already used nonce.
+/// success case: every node rejects it with `INVALID_TRANSACTION_NONCE`.
+///
+#[rstest]
+#[tokio::test]
+async fn fail_add_invoke_transaction_invalid_nonce(config: TestConfig) {
+    let harness = match DevnetHarness::from_test_config(&config).await {
+        Ok(harness) => harness,
+        Err(reason) => {
+            println!("{reason}");
+            return;
+        }
+    };
+
+    for node in harness.nodes.keys() {
+        let transaction = harness
+            .builder(node, INVALID_NONCE_ACCOUNT)
+            .await
+            .expect("Error while building the transaction")
+            .calls(vec![transfer(&harness)])
+            .mutate(Mutation::WrongNonce)
+            .build();
+
+        let response = harness.submit(node, transaction).await;
+
+        assert_matches!(
+            response,
+            Err(ProviderError::StarknetError(
+                StarknetError::InvalidTransactionNonce
+            )),
+            "{node}"
+        );
+    }
+}
+
+///
+/// Unit test for `starknet_addInvokeTransaction`
+///
+/// purpose: send a transfer whose signature does not match.
+/// success case: every node rejects it with `VALIDATION_FAILURE`.
+///
+#[rstest]
+#[tokio::test]
+async fn fail_add_invoke_transaction_bad_signature(config: TestConfig) {
+    let harness = match DevnetHarness::from_test_config(&config).await {
+        Ok(harness) => harness,
+        Err(reason) => {
+            println!("{reason}");
+            return;
+        }
+    };
+
+    for node in harness.nodes.keys() {
+        let transaction = harness
+            .builder(node, BAD_SIGNATURE_ACCOUNT)
+            .await
+            .expect("Error while building the transaction")
+            .calls(vec![transfer(&harness)])
+            .mutate(Mutation::BadSignature)
+            .build();
+
+        let response = harness.submit(node, transaction).await;
+
+        assert_matches!(
+            response,
+            Err(ProviderError::StarknetError(
+                StarknetError::ValidationFailure(_)
+            )),
+            "{node}"
+        );
+    }
+}
+
+///
+/// Unit test for `starknet_addInvokeTransaction`
+///
+/// purpose: send a transfer whose max fee exceeds the account balance.
+/// success case: every node rejects it with `INSUFFICIENT_ACCOUNT_BALANCE`.
+///
+#[rstest]
+#[tokio::test]
+async fn fail_add_invoke_transaction_insufficient_balance(config: TestConfig) {
+    let harness = match DevnetHarness::from_test_config(&config).await {
+        Ok(harness) => harness,
+        Err(reason) => {
+            println!("{reason}");
+            return;
+        }
+    };
+
+    for node in harness.nodes.keys() {
+        let transaction = harness
+            .builder(node, INSUFFICIENT_BALANCE_ACCOUNT)
+            .await
+            .expect("Error while building the transaction")
+            .calls(vec![transfer(&harness)])
+            .mutate(Mutation::OversizedFee)
+            .build();
+
+        let response = harness.submit(node, transaction).await;
+
+        assert_matches!(
+            response,
+            Err(ProviderError::StarknetError(
+                StarknetError::InsufficientAccountBalance
+            )),
+            "{node}"
+        );
+    }
+}
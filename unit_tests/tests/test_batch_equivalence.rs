@@ -0,0 +1,140 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde_json::json;
+use unit_tests::batch::{cases_from_corpus, check_batch_equivalence, compare_batch};
+use unit_tests::cases::{load_cases, RequestCase};
+use unit_tests::mock::{MockResponse, MockServer};
+use unit_tests::transport::corpus::corpus_path;
+use unit_tests::transport::suite::MODE_ENV;
+
+///
+/// Batch versus single request equivalence tests.
+///
+/// The same requests are sent to each node once as individual calls and once as a single
+/// JSON-RPC batch. Both must give the same responses, mapped to the right ids, in any order.
+///
+
+fn case(method: &str) -> RequestCase {
+    RequestCase {
+        name: None,
+        method: String::from(method),
+        params: json!([]),
+        ignore: vec![],
+        expected_error: None,
+        nodes: None,
+    }
+}
+
+///
+/// Unit test for batch equivalence
+///
+/// purpose: send the regression cases individually and as a batch to every node.
+/// success case: both give the same responses on every node.
+///
+#[rstest]
+#[tokio::test]
+async fn work_batch_regression_cases(urls: HashMap<String, String>) {
+    if std::env::var(MODE_ENV).as_deref() == Ok("replay") {
+        println!("Raw HTTP requests cannot be replayed");
+        return;
+    }
+    let cases = load_cases("cases/regressions.jsonl").expect("Error while loading request cases");
+
+    for (node, url) in &urls {
+        let failures = check_batch_equivalence(url, &cases)
+            .await
+            .unwrap_or_else(|e| panic!("{node}: {e}"));
+
+        for failure in &failures {
+            println!("[{node}] {failure}");
+        }
+        assert!(failures.is_empty(), "{node}: batch responses differ");
+    }
+}
+
+///
+/// Unit test for batch equivalence
+///
+/// purpose: send every distinct call of the recorded corpus individually and as a batch.
+/// success case: both give the same responses on every node.
+///
+#[rstest]
+#[tokio::test]
+async fn work_batch_corpus(urls: HashMap<String, String>) {
+    let path = corpus_path();
+    if std::env::var(MODE_ENV).as_deref() == Ok("replay") || !Path::new(&path).exists() {
+        println!("No corpus at {path}, or raw HTTP requests cannot be replayed");
+        return;
+    }
+    let cases = cases_from_corpus(&path).expect("Error while loading the corpus");
+
+    for (node, url) in &urls {
+        let failures = check_batch_equivalence(url, &cases)
+            .await
+            .unwrap_or_else(|e| panic!("{node}: {e}"));
+
+        for failure in &failures {
+            println!("[{node}] {failure}");
+        }
+        assert!(failures.is_empty(), "{node}: batch responses differ");
+    }
+}
+
+///
+/// Unit test for batch equivalence
+///
+/// purpose: compare a batch answered in reverse order with the individual responses.
+/// success case: reordering is tolerated, while a wrong id is reported.
+///
+#[rstest]
+fn work_batch_reordered() {
+    let cases = vec![case("starknet_chainId"), case("starknet_blockNumber")];
+    let singles = vec![
+        json!({ "jsonrpc": "2.0", "id": 0, "result": "0x534e5f4d41494e" }),
+        json!({ "jsonrpc": "2.0", "id": 1, "result": 100 }),
+    ];
+
+    let reordered = json!([
+        { "jsonrpc": "2.0", "id": 1, "result": 100 },
+        { "jsonrpc": "2.0", "id": 0, "result": "0x534E5F4D41494E" },
+    ]);
+    assert!(compare_batch(&cases, &singles, &reordered).is_empty());
+
+    let wrong_id = json!([
+        { "jsonrpc": "2.0", "id": 0, "result": "0x534e5f4d41494e" },
+        { "jsonrpc": "2.0", "id": 7, "result": 100 },
+    ]);
+    assert_eq!(compare_batch(&cases, &singles, &wrong_id).len(), 2);
+}
+
+///
+/// Unit test for batch equivalence
+///
+/// purpose: check a mock node whose answer changes between the single and the batched call.
+/// success case: the differing request is reported.
+///
+#[rstest]
+#[tokio::test]
+async fn fail_batch_differs() {
+    let server = MockServer::start().await;
+    server.on_result("starknet_chainId", json!("0x534e5f4d41494e"));
+    let calls = AtomicU64::new(0);
+    server.on("starknet_blockNumber", move |_| {
+        MockResponse::Result(json!(calls.fetch_add(1, Ordering::SeqCst)))
+    });
+
+    let cases = vec![case("starknet_chainId"), case("starknet_blockNumber")];
+    let failures = check_batch_equivalence(&server.url(), &cases)
+        .await
+        .unwrap();
+
+    assert_eq!(failures.len(), 1, "Unexpected failures: {failures:?}");
+    assert!(failures[0].starts_with("starknet_blockNumber"));
+}
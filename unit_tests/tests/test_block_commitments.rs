@@ -0,0 +1,76 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::collections::HashMap;
+
+use unit_tests::verify::commitment::verify_commitments;
+
+///
+/// Test for the commitments of blocks from v0.13.2 onward.
+///
+/// The transaction, event, receipt and state diff commitments are rebuilt from the
+/// `starknet_getBlockWithReceipts` and `starknet_getStateUpdate` responses of every node. They
+/// are compared with the header commitments of the node where it exposes them, and with the
+/// commitments rebuilt from Pathfinder otherwise. What cannot be verified, such as the receipt
+/// commitment of RPC v0.7 nodes, is printed.
+///
+
+///
+/// Unit test for block commitments
+///
+/// purpose: rebuild the commitments of the first v0.13.2 blocks.
+/// success case: commitments are compared, and every commitment matches.
+///
+#[rstest]
+#[tokio::test]
+async fn work_commitments_0_13_2(transports: HashMap<String, SuiteTransport>) {
+    let pathfinder = &transports[mainnet::network::PATHFINDER];
+    let block_number = mainnet::block::BLOCK_0_13_2;
+
+    let checks = verify_commitments(pathfinder, &transports, block_number..=block_number + 5)
+        .await
+        .expect("Error while rebuilding reference commitments");
+
+    for unverifiable in &checks.unverifiable {
+        println!("Not verifiable: {unverifiable}");
+    }
+    assert!(checks.compared > 0, "No commitment was compared");
+    for mismatch in &checks.mismatches {
+        println!("{mismatch}");
+    }
+    assert!(
+        checks.mismatches.is_empty(),
+        "Block commitments do not match"
+    );
+}
+
+///
+/// Unit test for block commitments
+///
+/// purpose: rebuild the commitments of a range of blocks after v0.13.2.
+/// success case: commitments are compared, and every commitment matches.
+///
+#[rstest]
+#[tokio::test]
+async fn work_commitments_after_0_13_2(transports: HashMap<String, SuiteTransport>) {
+    let pathfinder = &transports[mainnet::network::PATHFINDER];
+    let block_number = mainnet::block::BLOCK_0_13_2 + 10000;
+
+    let checks = verify_commitments(pathfinder, &transports, block_number..=block_number + 5)
+        .await
+        .expect("Error while rebuilding reference commitments");
+
+    for unverifiable in &checks.unverifiable {
+        println!("Not verifiable: {unverifiable}");
+    }
+    assert!(checks.compared > 0, "No commitment was compared");
+    for mismatch in &checks.mismatches {
+        println!("{mismatch}");
+    }
+    assert!(
+        checks.mismatches.is_empty(),
+        "Block commitments do not match"
+    );
+}
@@ -4,10 +4,7 @@ use starknet_core::types::BlockHashAndNumber;
 
 use std::collections::HashMap;
 
-use starknet_providers::{
-    jsonrpc::{HttpTransport, JsonRpcClient},
-    Provider,
-};
+use starknet_providers::{JsonRpcClient, Provider};
 
 use colored::*;
 
@@ -19,7 +16,7 @@ use colored::*;
 ///
 #[rstest]
 #[tokio::test]
-async fn work_existing_block(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_existing_block(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
     let juno = &clients[mainnet::network::JUNO];
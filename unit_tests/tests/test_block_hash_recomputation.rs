@@ -0,0 +1,119 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::collections::HashMap;
+
+use unit_tests::hash::block::BlockHashScheme;
+use unit_tests::verify::block_hash::{verify_block_hashes, BlockHashes};
+use unit_tests::verify::commitment::reports_receipt_gas;
+
+///
+/// Test for the block hashes returned by `starknet_getBlockWithReceipts`.
+///
+/// Each block hash is recomputed locally from the header, transactions and receipts served by
+/// every node, using the hashing scheme of the Starknet version the block was sequenced with,
+/// and compared with the hash that node returns. Blocks from v0.13.4 onward are skipped, as
+/// their hash commits to L2 gas prices which RPC v0.7 does not expose.
+///
+
+///
+/// Prints the blocks which were skipped, and checks that hashes were compared and all match.
+///
+fn assert_hashes(hashes: &BlockHashes) {
+    for skipped in &hashes.skipped {
+        println!("Skipped: {skipped}");
+    }
+    assert!(hashes.compared > 0, "No block hash was compared");
+    for mismatch in &hashes.mismatches {
+        println!("{mismatch}");
+    }
+    assert!(hashes.mismatches.is_empty(), "Block hashes do not match");
+}
+
+///
+/// Unit test for local block hash recomputation
+///
+/// purpose: recompute the hashes of blocks from before v0.7.0, which include the chain id.
+/// success case: every recomputed hash matches.
+///
+#[rstest]
+#[tokio::test]
+async fn work_block_hash_before_0_7_0(transports: HashMap<String, SuiteTransport>) {
+    assert_hashes(&verify_block_hashes(&transports, 0..=20).await);
+}
+
+///
+/// Unit test for local block hash recomputation
+///
+/// purpose: recompute the hashes of blocks sequenced with every version from v0.7.0 to v0.13.1.
+/// success case: every recomputed hash matches.
+///
+#[rstest]
+#[tokio::test]
+async fn work_block_hash_across_versions(transports: HashMap<String, SuiteTransport>) {
+    let upgrades = [
+        mainnet::block::BLOCK_0_7_0,
+        mainnet::block::BLOCK_0_9_1,
+        mainnet::block::BLOCK_0_10_0,
+        mainnet::block::BLOCK_0_10_3,
+        mainnet::block::BLOCK_0_11_0,
+        mainnet::block::BLOCK_0_11_1,
+        mainnet::block::BLOCK_0_12_0,
+        mainnet::block::BLOCK_0_12_3,
+        mainnet::block::BLOCK_0_13_0,
+        mainnet::block::BLOCK_0_13_1,
+    ];
+
+    for block_number in upgrades {
+        assert_hashes(&verify_block_hashes(&transports, block_number - 1..=block_number + 1).await);
+    }
+}
+
+///
+/// Unit test for local block hash recomputation
+///
+/// purpose: recompute the hashes of the first v0.13.2 blocks from their commitments, on the
+/// nodes which report the gas consumed by each transaction that the receipt commitment needs.
+/// success case: every recomputed hash matches.
+///
+#[rstest]
+#[tokio::test]
+async fn work_block_hash_0_13_2(transports: HashMap<String, SuiteTransport>) {
+    let mut nodes = HashMap::new();
+    for (node, transport) in transports {
+        match reports_receipt_gas(&transport).await {
+            Ok(true) => {
+                nodes.insert(node, transport);
+            }
+            Ok(false) => println!("Skipping {node}: RPC before v0.8 reports no receipt gas"),
+            Err(e) => panic!("Error while reading the spec version of {node}: {e}"),
+        }
+    }
+    if nodes.is_empty() {
+        println!("Skipping: no node reports the gas needed by the receipt commitment");
+        return;
+    }
+
+    let block_number = mainnet::block::BLOCK_0_13_2;
+    assert_hashes(&verify_block_hashes(&nodes, block_number..=block_number + 2).await);
+}
+
+///
+/// Unit test for local block hash recomputation
+///
+/// purpose: select the hashing scheme of blocks from their Starknet version.
+/// success case: v0.13.2 and v0.13.3 share a scheme, and v0.13.4 onward uses the next one.
+///
+#[rstest]
+fn work_block_hash_scheme_for_version() {
+    let block_number = mainnet::block::BLOCK_0_13_2;
+    let scheme = |version: &str| BlockHashScheme::for_block(block_number, version);
+
+    assert_eq!(scheme("0.13.1.1"), BlockHashScheme::V0_11_1);
+    assert_eq!(scheme("0.13.2"), BlockHashScheme::V0_13_2);
+    assert_eq!(scheme("0.13.3"), BlockHashScheme::V0_13_2);
+    assert_eq!(scheme("0.13.4"), BlockHashScheme::V0_13_4);
+    assert_eq!(scheme("0.14.0"), BlockHashScheme::V0_13_4);
+}
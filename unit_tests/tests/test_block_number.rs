@@ -4,10 +4,7 @@ use common::*;
 use std::collections::HashMap;
 
 use colored::*;
-use starknet_providers::{
-    jsonrpc::{HttpTransport, JsonRpcClient},
-    Provider,
-}; // Add this import for colored output
+use starknet_providers::{JsonRpcClient, Provider}; // Add this import for colored output
 
 ///
 /// Unit test for `starknet_blockNumber`
@@ -17,7 +14,7 @@ use starknet_providers::{
 ///
 #[rstest]
 #[tokio::test]
-async fn work_existing_block(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_existing_block(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
     let juno = &clients[mainnet::network::JUNO];
@@ -9,10 +9,7 @@ use starknet_core::{
     types::{BlockId, BlockTag, ContractErrorData, FieldElement, FunctionCall, StarknetError},
     utils::get_selector_from_name,
 };
-use starknet_providers::{
-    jsonrpc::{HttpTransport, JsonRpcError},
-    JsonRpcClient, Provider,
-};
+use starknet_providers::{jsonrpc::JsonRpcError, JsonRpcClient, Provider};
 
 ///
 /// Unit test for `starknet_call`
@@ -22,7 +19,7 @@ use starknet_providers::{
 ///
 #[rstest]
 #[tokio::test]
-async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
@@ -73,7 +70,7 @@ async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<HttpTran
 ///
 #[rstest]
 #[tokio::test]
-async fn fail_non_existing_contract(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn fail_non_existing_contract(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
@@ -126,7 +123,7 @@ async fn fail_non_existing_contract(clients: HashMap<String, JsonRpcClient<HttpT
 #[rstest]
 #[tokio::test]
 async fn fail_invalid_contract_entry_point_selector(
-    clients: HashMap<String, JsonRpcClient<HttpTransport>>,
+    clients: HashMap<String, JsonRpcClient<SuiteTransport>>,
 ) {
     let deoxys = &clients[mainnet::network::DEOXYS];
 
@@ -163,7 +160,7 @@ async fn fail_invalid_contract_entry_point_selector(
 ///
 #[rstest]
 #[tokio::test]
-async fn fail_missing_contract_call_data(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn fail_missing_contract_call_data(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
@@ -219,7 +216,7 @@ async fn fail_missing_contract_call_data(clients: HashMap<String, JsonRpcClient<
 ///
 #[rstest]
 #[tokio::test]
-async fn fail_invalid_contract_call_data(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn fail_invalid_contract_call_data(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
 
     let response_deoxys = deoxys
@@ -248,7 +245,7 @@ async fn fail_invalid_contract_call_data(clients: HashMap<String, JsonRpcClient<
 ///
 #[rstest]
 #[tokio::test]
-async fn fail_too_many_call_data(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn fail_too_many_call_data(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
 
     let response_deoxys = deoxys
@@ -291,7 +288,7 @@ async fn fail_too_many_call_data(clients: HashMap<String, JsonRpcClient<HttpTran
 ///
 #[rstest]
 #[tokio::test]
-async fn work_correct_call(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_correct_call(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
@@ -333,7 +330,7 @@ async fn work_correct_call(clients: HashMap<String, JsonRpcClient<HttpTransport>
 ///
 #[rstest]
 #[tokio::test]
-async fn work_correct_call_with_args(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_correct_call_with_args(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
@@ -377,7 +374,7 @@ async fn work_correct_call_with_args(clients: HashMap<String, JsonRpcClient<Http
 ///
 #[rstest]
 #[tokio::test]
-async fn work_with_multiple_args(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_with_multiple_args(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
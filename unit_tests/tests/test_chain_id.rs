@@ -1,7 +1,7 @@
 mod common;
 use common::*;
 
-use starknet_providers::{jsonrpc::HttpTransport, JsonRpcClient, Provider};
+use starknet_providers::{JsonRpcClient, Provider};
 use std::collections::HashMap;
 
 ///
@@ -12,7 +12,7 @@ use std::collections::HashMap;
 ///
 #[rstest]
 #[tokio::test]
-async fn chain_id(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn chain_id(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
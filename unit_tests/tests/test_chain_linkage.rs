@@ -0,0 +1,99 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::collections::HashMap;
+
+use starknet_providers::{JsonRpcClient, Provider};
+use unit_tests::verify::chain::verify_chain_linkage;
+
+///
+/// Test for the parent hash chain returned by `starknet_getBlockWithTxHashes`.
+///
+/// Headers are fetched over a range of blocks from each node, which must have contiguous block
+/// numbers and a `parent_hash` equal to the `block_hash` of the previous block. Only the first
+/// broken link of each node is reported.
+///
+
+///
+/// Unit test for parent hash chain linkage
+///
+/// purpose: walk the first blocks of the chain.
+/// success case: every node links back to genesis.
+///
+#[rstest]
+#[tokio::test]
+async fn work_linkage_from_genesis(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
+    let mismatches = verify_chain_linkage(&clients, 0..=100).await;
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+    assert!(mismatches.is_empty(), "Broken parent hash chain");
+}
+
+///
+/// Unit test for parent hash chain linkage
+///
+/// purpose: walk across every Starknet version upgrade, where block hashing changed.
+/// success case: every node keeps a linked chain across the upgrade.
+///
+#[rstest]
+#[tokio::test]
+async fn work_linkage_across_versions(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
+    let upgrades = [
+        mainnet::block::BLOCK_0_9_1,
+        mainnet::block::BLOCK_0_10_0,
+        mainnet::block::BLOCK_0_10_1,
+        mainnet::block::BLOCK_0_10_2,
+        mainnet::block::BLOCK_0_10_3,
+        mainnet::block::BLOCK_0_11_0,
+        mainnet::block::BLOCK_0_11_0_2,
+        mainnet::block::BLOCK_0_11_1,
+        mainnet::block::BLOCK_0_11_2,
+        mainnet::block::BLOCK_0_12_0,
+        mainnet::block::BLOCK_0_12_1,
+        mainnet::block::BLOCK_0_12_2,
+        mainnet::block::BLOCK_0_12_3,
+        mainnet::block::BLOCK_0_13_0,
+        mainnet::block::BLOCK_0_13_1,
+    ];
+
+    let mut mismatches = vec![];
+    for block_number in upgrades {
+        mismatches
+            .extend(verify_chain_linkage(&clients, block_number - 5..=block_number + 5).await);
+    }
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+    assert!(mismatches.is_empty(), "Broken parent hash chain");
+}
+
+///
+/// Unit test for parent hash chain linkage
+///
+/// purpose: walk the most recent blocks synced by every node.
+/// success case: every node keeps a linked chain up to its tip.
+///
+#[rstest]
+#[tokio::test]
+async fn work_linkage_near_tip(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
+    let mut tip = u64::MAX;
+    for client in clients.values() {
+        let block_number = client
+            .block_number()
+            .await
+            .expect("Error while getting the block number");
+        tip = tip.min(block_number);
+    }
+
+    let mismatches = verify_chain_linkage(&clients, tip.saturating_sub(100)..=tip).await;
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+    assert!(mismatches.is_empty(), "Broken parent hash chain");
+}
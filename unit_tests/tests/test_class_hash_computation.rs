@@ -0,0 +1,84 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::collections::HashMap;
+
+use starknet_providers::JsonRpcClient;
+use unit_tests::verify::class_hash::verify_class_hashes;
+
+///
+/// Test for the contract classes returned by `starknet_getClass`.
+///
+/// Classes declared over a block range are listed from the state diffs served by Pathfinder.
+/// Each class is fetched from every node at its declaration block and hashed locally: Poseidon
+/// over the entry points, ABI and program for Sierra classes, and the Cairo 0 hinted class hash
+/// of the decompressed program for legacy classes.
+///
+
+///
+/// Unit test for local class hash computation
+///
+/// purpose: hash legacy classes declared before Cairo 1 was introduced.
+/// success case: every class hashes to its class hash.
+///
+#[rstest]
+#[tokio::test]
+async fn work_class_hash_legacy(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
+    let pathfinder = &clients[mainnet::network::PATHFINDER];
+    let block_number = mainnet::block::BLOCK_0_10_0;
+
+    let mismatches = verify_class_hashes(pathfinder, &clients, block_number..=block_number + 50)
+        .await
+        .expect("Error while listing declared classes");
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+    assert!(mismatches.is_empty(), "Class hashes do not match");
+}
+
+///
+/// Unit test for local class hash computation
+///
+/// purpose: hash Sierra and legacy classes declared after Cairo 1 was introduced.
+/// success case: every class hashes to its class hash.
+///
+#[rstest]
+#[tokio::test]
+async fn work_class_hash_sierra(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
+    let pathfinder = &clients[mainnet::network::PATHFINDER];
+    let block_number = mainnet::block::BLOCK_0_11_1;
+
+    let mismatches = verify_class_hashes(pathfinder, &clients, block_number..=block_number + 50)
+        .await
+        .expect("Error while listing declared classes");
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+    assert!(mismatches.is_empty(), "Class hashes do not match");
+}
+
+///
+/// Unit test for local class hash computation
+///
+/// purpose: hash classes declared with v0.13.1, including DECLARE v3 transactions.
+/// success case: every class hashes to its class hash.
+///
+#[rstest]
+#[tokio::test]
+async fn work_class_hash_recent(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
+    let pathfinder = &clients[mainnet::network::PATHFINDER];
+    let block_number = mainnet::block::BLOCK_0_13_1;
+
+    let mismatches = verify_class_hashes(pathfinder, &clients, block_number..=block_number + 50)
+        .await
+        .expect("Error while listing declared classes");
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+    assert!(mismatches.is_empty(), "Class hashes do not match");
+}
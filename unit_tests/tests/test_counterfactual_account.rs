@@ -0,0 +1,184 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use macro_utils::TestConfig;
+use starknet_core::chain_id;
+use starknet_core::types::{BlockId, BlockTag, BroadcastedDeployAccountTransaction, FieldElement};
+use starknet_core::utils::get_contract_address;
+use starknet_crypto::poseidon_hash_many;
+use starknet_providers::Provider;
+use unit_tests::account::{AccountKind, CounterfactualAccount};
+use unit_tests::builder::{NetworkProfile, TransactionVersion};
+use unit_tests::devnet::{DevnetHarness, DEVNET};
+use unit_tests::hash::transaction::{deploy_account_v1_hash, deploy_account_v3_hash};
+
+///
+/// Tests for the deployment of counterfactual accounts, whose address is computed before the
+/// deploy account transaction is sent.
+///
+
+fn verifies(public_key: FieldElement, hash: FieldElement, signature: &[FieldElement]) -> bool {
+    starknet_crypto::verify(&public_key, &hash, &signature[0], &signature[1]).unwrap_or(false)
+}
+
+///
+/// Unit test for counterfactual accounts
+///
+/// purpose: compute the address of every account kind.
+/// success case: the address derives from the class, salt and constructor calldata, and
+/// differs between account kinds.
+///
+#[rstest]
+fn work_counterfactual_address() {
+    let kinds = [
+        AccountKind::OpenZeppelin,
+        AccountKind::Argent,
+        AccountKind::Braavos,
+    ];
+    let addresses: Vec<FieldElement> = kinds
+        .iter()
+        .map(|kind| {
+            let account = CounterfactualAccount::new(*kind, "0x1", FieldElement::TWO);
+            assert_eq!(
+                account.address(),
+                get_contract_address(
+                    FieldElement::TWO,
+                    kind.class_hash(),
+                    &account.constructor_calldata(),
+                    FieldElement::ZERO
+                )
+            );
+            account.address()
+        })
+        .collect();
+
+    assert_ne!(addresses[0], addresses[1]);
+    assert_ne!(addresses[0], addresses[2]);
+    assert_ne!(addresses[1], addresses[2]);
+}
+
+///
+/// Unit test for counterfactual accounts
+///
+/// purpose: build the v1 and v3 deploy account transactions of an OpenZeppelin account.
+/// success case: both are signed by the account key.
+///
+#[rstest]
+fn work_deploy_transaction() {
+    let account = CounterfactualAccount::generate(AccountKind::OpenZeppelin);
+    let builder = account
+        .transaction_builder(NetworkProfile::sepolia())
+        .query(false);
+
+    let BroadcastedDeployAccountTransaction::V1(tx) = account.deploy_transaction(&builder) else {
+        panic!("Expected a v1 deploy account");
+    };
+    assert_eq!(tx.constructor_calldata, vec![account.public_key()]);
+    assert!(verifies(
+        account.public_key(),
+        deploy_account_v1_hash(&tx, chain_id::SEPOLIA),
+        &tx.signature
+    ));
+
+    let builder = builder.version(TransactionVersion::V3);
+    let BroadcastedDeployAccountTransaction::V3(tx) = account.deploy_transaction(&builder) else {
+        panic!("Expected a v3 deploy account");
+    };
+    assert!(verifies(
+        account.public_key(),
+        deploy_account_v3_hash(&tx, chain_id::SEPOLIA),
+        &tx.signature
+    ));
+}
+
+///
+/// Unit test for counterfactual accounts
+///
+/// purpose: build the deploy account transaction of a Braavos account.
+/// success case: the signature carries the implementation class and chain id, signed by the
+/// account key.
+///
+#[rstest]
+fn work_braavos_deploy_transaction() {
+    let account = CounterfactualAccount::new(AccountKind::Braavos, "0x1", FieldElement::TWO);
+    let builder = account.transaction_builder(NetworkProfile::mainnet());
+
+    let BroadcastedDeployAccountTransaction::V1(tx) = account.deploy_transaction(&builder) else {
+        panic!("Expected a v1 deploy account");
+    };
+    assert_eq!(tx.signature.len(), 15);
+    assert!(verifies(
+        account.public_key(),
+        deploy_account_v1_hash(&tx, chain_id::MAINNET),
+        &tx.signature[..2]
+    ));
+
+    let aux_data = &tx.signature[2..13];
+    assert_eq!(
+        aux_data[0],
+        FieldElement::from_hex_be(mainnet::contract::CLASS_BRAAVOS_ACCOUNT).unwrap()
+    );
+    assert_eq!(aux_data[10], chain_id::MAINNET);
+    assert!(verifies(
+        account.public_key(),
+        poseidon_hash_many(aux_data),
+        &tx.signature[13..]
+    ));
+}
+
+///
+/// Unit test for the counterfactual deployment flow
+///
+/// purpose: fund, deploy and wait for a fresh account of every kind on the configured devnet.
+/// success case: the deploy account transaction succeeds and the account holds its class.
+/// Kinds whose class is not declared on the devnet are skipped: Argent and Braavos need a
+/// devnet forking mainnet or sepolia.
+///
+#[rstest]
+#[case::open_zeppelin(AccountKind::OpenZeppelin)]
+#[case::argent(AccountKind::Argent)]
+#[case::braavos(AccountKind::Braavos)]
+#[tokio::test]
+async fn work_deploy_on_devnet(config: TestConfig, #[case] kind: AccountKind) {
+    let harness = match DevnetHarness::from_test_config(&config).await {
+        Ok(harness) => harness,
+        Err(reason) => {
+            println!("{reason}");
+            return;
+        }
+    };
+    let declared = harness
+        .is_declared(kind.class_hash())
+        .await
+        .expect("Error while checking the account class");
+    if !declared {
+        println!("{kind:?} account class is not declared, start the devnet with --fork-network");
+        return;
+    }
+
+    let devnet = harness.node(DEVNET);
+    let account = CounterfactualAccount::generate(kind);
+    let builder = account
+        .transaction_builder(harness.profile.clone())
+        .max_fee(FieldElement::from(10_000_000_000_000_000u64))
+        .query(false);
+
+    account
+        .deploy_on_devnet(devnet, &harness.url, &builder, 1_000_000_000_000_000_000)
+        .await
+        .expect("Error while deploying the account");
+
+    let class_hash = devnet
+        .get_class_hash_at(BlockId::Tag(BlockTag::Latest), account.address())
+        .await
+        .expect("Error while getting the account class hash");
+    let expected = match kind {
+        AccountKind::Braavos => {
+            FieldElement::from_hex_be(mainnet::contract::CLASS_BRAAVOS_ACCOUNT).unwrap()
+        }
+        kind => kind.class_hash(),
+    };
+    assert_eq!(class_hash, expected);
+}
@@ -0,0 +1,279 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::collections::HashMap;
+
+use starknet_core::chain_id;
+use starknet_core::types::{
+    BlockId, BroadcastedDeclareTransaction, BroadcastedTransaction, DeclareTransaction,
+    DeclareTransactionV1, DeclareTransactionV2, FieldElement, SimulationFlag,
+    SimulationFlagForEstimateFee, Transaction,
+};
+use starknet_providers::{JsonRpcClient, Provider};
+use starknet_signers::SigningKey;
+use unit_tests::builder::{NetworkProfile, TestAccount, TransactionBuilder, TransactionVersion};
+use unit_tests::declare::{load_legacy, load_sierra, ClassArtifact};
+use unit_tests::hash::class::sierra_class_hash;
+use unit_tests::hash::transaction::{
+    declare_v1_hash, declare_v2_hash, declare_v3_hash, transaction_hashes,
+};
+
+///
+/// Tests for declare transactions built from the compiled artifacts under
+/// `unit_tests/artifacts/`. The legacy class may already be declared on mainnet, in which
+/// case nodes must only agree on the error.
+///
+
+const SIERRA: &str = "artifacts/minimal_contract.sierra.json";
+const CASM: &str = "artifacts/minimal_contract.casm.json";
+const LEGACY: &str = "artifacts/empty_contract_legacy.json";
+
+fn builder() -> TransactionBuilder {
+    TransactionBuilder::new(
+        NetworkProfile::mainnet(),
+        TestAccount::new(mainnet::contract::CONTRACT_ACCOUNT).signing_key("0x1"),
+    )
+    .nonce(FieldElement::TWO)
+    .max_fee(FieldElement::from(1_000_000u32))
+    .query(false)
+}
+
+fn verifies(hash: FieldElement, signature: &[FieldElement]) -> bool {
+    let public_key = SigningKey::from_secret_scalar(FieldElement::ONE)
+        .verifying_key()
+        .scalar();
+
+    starknet_crypto::verify(&public_key, &hash, &signature[0], &signature[1]).unwrap_or(false)
+}
+
+///
+/// Unit test for class artifacts
+///
+/// purpose: load a Sierra class and its CASM compilation.
+/// success case: the class hash of the flattened class matches the one computed by Ditto.
+///
+#[rstest]
+fn work_load_sierra() {
+    let artifact = load_sierra(SIERRA, CASM).expect("Failed to load Sierra artifacts");
+
+    let ClassArtifact::Sierra { class, .. } = &artifact else {
+        panic!("Expected a Sierra class");
+    };
+    assert_eq!(
+        artifact.class_hash(),
+        sierra_class_hash(class).expect("Failed to compute the Sierra class hash")
+    );
+}
+
+///
+/// Unit test for class artifacts
+///
+/// purpose: load artifacts which do not exist or are not classes.
+/// success case: loading fails.
+///
+#[rstest]
+fn fail_load_artifacts() {
+    assert!(load_sierra("artifacts/missing.sierra.json", CASM).is_err());
+    assert!(load_sierra(SIERRA, SIERRA).is_err());
+    assert!(load_legacy(SIERRA).is_err());
+}
+
+///
+/// Unit test for declare transactions
+///
+/// purpose: declare a Sierra class with v2 and v3 transactions.
+/// success case: the v2 hash matches the hash of the sequenced transaction, and both
+/// signatures verify.
+///
+#[rstest]
+fn work_declare_sierra() {
+    let artifact = load_sierra(SIERRA, CASM).expect("Failed to load Sierra artifacts");
+
+    let BroadcastedDeclareTransaction::V2(tx) = builder()
+        .build_declare(&artifact)
+        .expect("Error while building the declare transaction")
+    else {
+        panic!("Expected a v2 declare");
+    };
+    let hash = declare_v2_hash(&tx, chain_id::MAINNET);
+    let sequenced = Transaction::Declare(DeclareTransaction::V2(DeclareTransactionV2 {
+        transaction_hash: hash,
+        sender_address: tx.sender_address,
+        compiled_class_hash: tx.compiled_class_hash,
+        max_fee: tx.max_fee,
+        signature: tx.signature.clone(),
+        nonce: tx.nonce,
+        class_hash: artifact.class_hash(),
+    }));
+    assert_eq!(
+        transaction_hashes(&sequenced, chain_id::MAINNET),
+        vec![hash]
+    );
+    assert!(verifies(hash, &tx.signature));
+
+    let BroadcastedDeclareTransaction::V3(tx) = builder()
+        .version(TransactionVersion::V3)
+        .build_declare(&artifact)
+        .expect("Error while building the declare transaction")
+    else {
+        panic!("Expected a v3 declare");
+    };
+    assert!(verifies(
+        declare_v3_hash(&tx, chain_id::MAINNET),
+        &tx.signature
+    ));
+}
+
+///
+/// Unit test for declare transactions
+///
+/// purpose: declare a legacy class with a v1 transaction.
+/// success case: the hash matches the hash of the sequenced transaction, and the signature
+/// verifies.
+///
+#[rstest]
+fn work_declare_legacy() {
+    let artifact = load_legacy(LEGACY).expect("Failed to load legacy artifact");
+
+    let BroadcastedDeclareTransaction::V1(tx) = builder()
+        .build_declare(&artifact)
+        .expect("Error while building the declare transaction")
+    else {
+        panic!("Expected a v1 declare");
+    };
+    let hash = declare_v1_hash(&tx, artifact.class_hash(), chain_id::MAINNET);
+    let sequenced = Transaction::Declare(DeclareTransaction::V1(DeclareTransactionV1 {
+        transaction_hash: hash,
+        sender_address: tx.sender_address,
+        max_fee: tx.max_fee,
+        signature: tx.signature.clone(),
+        nonce: tx.nonce,
+        class_hash: artifact.class_hash(),
+    }));
+    assert_eq!(
+        transaction_hashes(&sequenced, chain_id::MAINNET),
+        vec![hash]
+    );
+    assert!(verifies(hash, &tx.signature));
+}
+
+///
+/// Unit test for declare transactions
+///
+/// purpose: declare a legacy class with a v3 transaction.
+/// success case: the builder refuses to build it.
+///
+#[rstest]
+fn fail_declare_legacy_v3() {
+    let artifact = load_legacy(LEGACY).expect("Failed to load legacy artifact");
+
+    assert!(builder()
+        .version(TransactionVersion::V3)
+        .build_declare(&artifact)
+        .is_err());
+}
+
+///
+/// Declare transactions of every supported kind: the Sierra class with v2 and v3 transactions
+/// and the legacy class with a v1 transaction, query-only so they can only be estimated or
+/// simulated. They are sent with the nonce of the account at `block_id`.
+///
+async fn declare_transactions(
+    client: &JsonRpcClient<SuiteTransport>,
+    block_id: BlockId,
+) -> Vec<(&'static str, BroadcastedTransaction)> {
+    let sierra = load_sierra(SIERRA, CASM).expect("Failed to load Sierra artifacts");
+    let legacy = load_legacy(LEGACY).expect("Failed to load legacy artifact");
+    let builder = builder().query(true);
+    let nonce = client
+        .get_nonce(block_id, builder.address())
+        .await
+        .expect("Error while getting the nonce of the account");
+    let builder = builder.nonce(nonce);
+
+    [
+        ("v2", builder.build_declare(&sierra)),
+        (
+            "v3",
+            builder
+                .clone()
+                .version(TransactionVersion::V3)
+                .build_declare(&sierra),
+        ),
+        ("v1", builder.build_declare(&legacy)),
+    ]
+    .into_iter()
+    .map(|(version, tx)| {
+        let tx = tx.expect("Error while building the declare transaction");
+        (version, BroadcastedTransaction::Declare(tx))
+    })
+    .collect()
+}
+
+///
+/// Unit test for `starknet_estimateFee` with declare transactions
+///
+/// purpose: estimate the fee of declaring the compiled artifacts.
+/// success case: every node gives the same estimate, and Sierra classes can be declared.
+///
+#[rstest]
+#[tokio::test]
+async fn work_estimate_fee_declare(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
+    let deoxys = &clients[mainnet::network::DEOXYS];
+    let pathfinder = &clients[mainnet::network::PATHFINDER];
+    let block_id = BlockId::Number(mainnet::block::BLOCK_0_13_1);
+
+    for (version, transaction) in declare_transactions(deoxys, block_id).await {
+        let flags = vec![SimulationFlagForEstimateFee::SkipValidate];
+        let response_deoxys = deoxys
+            .estimate_fee(vec![transaction.clone()], flags.clone(), block_id)
+            .await;
+        let response_pathfinder = pathfinder
+            .estimate_fee(vec![transaction], flags, block_id)
+            .await;
+
+        if version != "v1" {
+            assert!(response_deoxys.is_ok(), "{version}: {response_deoxys:?}");
+        }
+        assert_eq!(
+            format!("{response_deoxys:?}"),
+            format!("{response_pathfinder:?}"),
+            "{version}"
+        );
+    }
+}
+
+///
+/// Unit test for `starknet_simulateTransactions` with declare transactions
+///
+/// purpose: simulate declaring the compiled artifacts.
+/// success case: every node gives the same trace, and Sierra classes can be declared.
+///
+#[rstest]
+#[tokio::test]
+async fn work_simulate_declare(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
+    let deoxys = &clients[mainnet::network::DEOXYS];
+    let pathfinder = &clients[mainnet::network::PATHFINDER];
+    let block_id = BlockId::Number(mainnet::block::BLOCK_0_13_1);
+    let flags = [SimulationFlag::SkipValidate, SimulationFlag::SkipFeeCharge];
+
+    for (version, transaction) in declare_transactions(deoxys, block_id).await {
+        let response_deoxys = deoxys
+            .simulate_transactions(block_id, &[transaction.clone()], &flags)
+            .await;
+        let response_pathfinder = pathfinder
+            .simulate_transactions(block_id, &[transaction], &flags)
+            .await;
+
+        if version != "v1" {
+            assert!(response_deoxys.is_ok(), "{version}: {response_deoxys:?}");
+        }
+        assert_eq!(
+            format!("{response_deoxys:?}"),
+            format!("{response_pathfinder:?}"),
+            "{version}"
+        );
+    }
+}
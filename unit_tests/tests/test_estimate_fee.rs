@@ -7,20 +7,22 @@ use starknet_core::types::{
     BlockId, BlockTag, FieldElement, SimulationFlagForEstimateFee, StarknetError,
 };
 use starknet_providers::{
-    jsonrpc::{HttpTransport, JsonRpcClient, JsonRpcError},
+    jsonrpc::{JsonRpcClient, JsonRpcError},
     Provider,
 };
 use std::collections::HashMap;
-use unit_tests::{BadTransactionFactory, OkTransactionFactory, TransactionFactory};
+use unit_tests::builder::TransactionBuilder;
 
 //TODO(Tbelleng : Add Simulation Flag to params)
 #[rstest]
 #[tokio::test]
 #[ignore = "Fix failing unwrap due to empty constant"]
-async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
 
-    let ok_invoke_transaction = OkTransactionFactory::build(Some(FieldElement::ZERO));
+    let ok_invoke_transaction = TransactionBuilder::transfer()
+        .nonce(FieldElement::ZERO)
+        .build();
     let simulation_flag = vec![SimulationFlagForEstimateFee::SkipValidate];
 
     let response_deoxys = deoxys
@@ -50,11 +52,11 @@ async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<HttpTran
 #[tokio::test]
 #[ignore = "Fix failing unwrap due to empty constant"]
 async fn fail_if_one_txn_cannot_be_executed(
-    clients: HashMap<String, JsonRpcClient<HttpTransport>>,
+    clients: HashMap<String, JsonRpcClient<SuiteTransport>>,
 ) {
     let deoxys = &clients[mainnet::network::PATHFINDER];
 
-    let bad_invoke_transaction = BadTransactionFactory::build(None);
+    let bad_invoke_transaction = TransactionBuilder::invalid().build();
     let simulate_flag = vec![SimulationFlagForEstimateFee::SkipValidate];
 
     let response_deoxys = deoxys
@@ -81,19 +83,31 @@ async fn fail_if_one_txn_cannot_be_executed(
 #[rstest]
 #[tokio::test]
 #[ignore = "Fix failing unwrap due to empty constant"]
-async fn works_ok(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn works_ok(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
     let block_number = get_block_setting();
 
-    let ok_deoxys_invoke = OkTransactionFactory::build(Some(FieldElement::ZERO));
-    let ok_deoxys_invoke_1 = OkTransactionFactory::build(Some(FieldElement::ONE));
-    let ok_deoxys_invoke_2 = OkTransactionFactory::build(Some(FieldElement::TWO));
-
-    let ok_pathfinder_invoke = OkTransactionFactory::build(Some(FieldElement::ZERO));
-    let ok_pathfinder_invoke_1 = OkTransactionFactory::build(Some(FieldElement::ONE));
-    let ok_pathfinder_invoke_2 = OkTransactionFactory::build(Some(FieldElement::TWO));
+    let ok_deoxys_invoke = TransactionBuilder::transfer()
+        .nonce(FieldElement::ZERO)
+        .build();
+    let ok_deoxys_invoke_1 = TransactionBuilder::transfer()
+        .nonce(FieldElement::ONE)
+        .build();
+    let ok_deoxys_invoke_2 = TransactionBuilder::transfer()
+        .nonce(FieldElement::TWO)
+        .build();
+
+    let ok_pathfinder_invoke = TransactionBuilder::transfer()
+        .nonce(FieldElement::ZERO)
+        .build();
+    let ok_pathfinder_invoke_1 = TransactionBuilder::transfer()
+        .nonce(FieldElement::ONE)
+        .build();
+    let ok_pathfinder_invoke_2 = TransactionBuilder::transfer()
+        .nonce(FieldElement::TWO)
+        .build();
 
     let simulate_flag = vec![SimulationFlagForEstimateFee::SkipValidate];
 
@@ -5,10 +5,7 @@ use common::*;
 use starknet_core::types::{
     BlockId, BlockTag, ContractErrorData, EthAddress, FieldElement, MsgFromL1, StarknetError,
 };
-use starknet_providers::{
-    jsonrpc::{HttpTransport, JsonRpcClient},
-    Provider,
-};
+use starknet_providers::{JsonRpcClient, Provider};
 
 /// Test for the `get_state_update` Deoxys RPC method
 /// # Arguments
@@ -44,7 +41,7 @@ pub fn get_message_from_l1(
 
 #[rstest]
 #[tokio::test]
-async fn fail_non_existing_block(deoxys: JsonRpcClient<HttpTransport>) {
+async fn fail_non_existing_block(deoxys: JsonRpcClient<SuiteTransport>) {
     let payload_message: Vec<FieldElement> = vec![];
     let contract_address = FieldElement::from_hex_be(
         "0x049D36570D4e46f48e99674bd3fcc84644DdD6b96F7C741B1562B82f9e004dC7",
@@ -79,7 +76,7 @@ async fn fail_non_existing_block(deoxys: JsonRpcClient<HttpTransport>) {
 // Care, Juno and Pathfinder error differ on this one
 #[rstest]
 #[tokio::test]
-async fn fail_contract_not_found(deoxys: JsonRpcClient<HttpTransport>) {
+async fn fail_contract_not_found(deoxys: JsonRpcClient<SuiteTransport>) {
     let unknown_contract_address =
         FieldElement::from_hex_be("0x4269DEADBEEF").expect("Invalid Contract Address");
     let payload_message: Vec<FieldElement> = vec![];
@@ -120,7 +117,7 @@ async fn fail_contract_not_found(deoxys: JsonRpcClient<HttpTransport>) {
 
 #[rstest]
 #[tokio::test]
-async fn fail_contract_error(deoxys: JsonRpcClient<HttpTransport>) {
+async fn fail_contract_error(deoxys: JsonRpcClient<SuiteTransport>) {
     //On this test, the contract address must be valid,
     //but the from_address, entry_point_selector or the payload must be invalid
     let payload_message: Vec<FieldElement> = vec![];
@@ -162,8 +159,8 @@ async fn fail_contract_error(deoxys: JsonRpcClient<HttpTransport>) {
 #[rstest]
 #[tokio::test]
 async fn estimate_message_fee_works_ok(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     let block_number = get_block_setting();
 
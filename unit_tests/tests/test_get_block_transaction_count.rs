@@ -6,17 +6,14 @@ use common::*;
 
 use rstest::rstest;
 use starknet_core::types::{BlockId, BlockTag, FieldElement, StarknetError};
-use starknet_providers::{
-    jsonrpc::{HttpTransport, JsonRpcClient},
-    Provider,
-};
+use starknet_providers::{JsonRpcClient, Provider};
 use std::collections::HashMap;
 use std::sync::Arc;
 use unit_tests::constants::{DEOXYS, PATHFINDER};
 
 #[rstest]
 #[tokio::test]
-async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
 
     let response_deoxys = deoxys
@@ -43,7 +40,7 @@ async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<HttpTran
 #[rstest]
 #[tokio::test]
 #[ignore = "Slash this ignore when Deoxys is fully synced"]
-async fn work_with_latest_block(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_with_latest_block(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
     let juno = &clients[mainnet::network::JUNO];
@@ -71,9 +68,9 @@ async fn work_with_latest_block(clients: HashMap<String, JsonRpcClient<HttpTrans
 }
 
 async fn work_with_block(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
-    juno: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
+    juno: JsonRpcClient<SuiteTransport>,
     block_number: u64,
 ) {
     let block_number = BlockId::Number(block_number);
@@ -101,16 +98,16 @@ async fn work_with_block(
 #[rstest]
 #[tokio::test]
 async fn work_with_block_1(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
-    juno: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
+    juno: JsonRpcClient<SuiteTransport>,
 ) {
     work_with_block(deoxys, pathfinder, juno, 1).await;
 }
 
 #[rstest]
 #[tokio::test]
-async fn work_with_block_1_hash(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_with_block_1_hash(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
     let juno = &clients[mainnet::network::JUNO];
@@ -145,9 +142,9 @@ async fn work_with_block_1_hash(clients: HashMap<String, JsonRpcClient<HttpTrans
 #[rstest]
 #[tokio::test]
 async fn work_with_block_5066(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
-    juno: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
+    juno: JsonRpcClient<SuiteTransport>,
 ) {
     work_with_block(deoxys, pathfinder, juno, 1).await;
 }
@@ -155,16 +152,16 @@ async fn work_with_block_5066(
 #[rstest]
 #[tokio::test]
 async fn work_with_block_100_000(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
-    juno: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
+    juno: JsonRpcClient<SuiteTransport>,
 ) {
     work_with_block(deoxys, pathfinder, juno, 100_000).await;
 }
 
 #[rstest]
 #[tokio::test]
-async fn work_with_block_100_000_hash(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_with_block_100_000_hash(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
     let juno = &clients[mainnet::network::JUNO];
@@ -198,7 +195,10 @@ async fn work_with_block_100_000_hash(clients: HashMap<String, JsonRpcClient<Htt
 
 #[rstest]
 #[tokio::test]
-async fn work_loop(deoxys: JsonRpcClient<HttpTransport>, pathfinder: JsonRpcClient<HttpTransport>) {
+async fn work_loop(
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
+) {
     let arc_deoxys = Arc::new(deoxys);
     let arc_pathfinder = Arc::new(pathfinder);
     let parallels_queries = 10;
@@ -9,14 +9,11 @@ use std::sync::Arc;
 use tokio::task::JoinSet;
 
 use starknet_core::types::{BlockId, BlockTag, FieldElement, StarknetError};
-use starknet_providers::{
-    jsonrpc::{HttpTransport, JsonRpcClient},
-    Provider,
-};
+use starknet_providers::{JsonRpcClient, Provider};
 
 #[rstest]
 #[tokio::test]
-async fn fail_non_existing_block(deoxys: JsonRpcClient<HttpTransport>) {
+async fn fail_non_existing_block(deoxys: JsonRpcClient<SuiteTransport>) {
     let response_deoxys = deoxys
         .get_block_with_receipts(BlockId::Hash(FieldElement::ZERO))
         .await;
@@ -39,8 +36,8 @@ async fn fail_non_existing_block(deoxys: JsonRpcClient<HttpTransport>) {
 #[rstest]
 #[tokio::test]
 async fn works_ok_for_block_1(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     let block_number = BlockId::Number(1);
 
@@ -53,8 +50,8 @@ async fn works_ok_for_block_1(
 #[rstest]
 #[tokio::test]
 async fn works_ok_for_block_with_l1_handler_tx(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     let block_number = BlockId::Number(192); //got the first L1HandlerTx at block 192
 
@@ -67,8 +64,8 @@ async fn works_ok_for_block_with_l1_handler_tx(
 #[rstest]
 #[tokio::test]
 async fn works_ok_for_block_5000(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     let block_number = BlockId::Number(5000);
 
@@ -81,8 +78,8 @@ async fn works_ok_for_block_5000(
 #[rstest]
 #[tokio::test]
 async fn works_ok_for_block_10000(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     let block_number = BlockId::Number(10000);
 
@@ -95,8 +92,8 @@ async fn works_ok_for_block_10000(
 #[rstest]
 #[tokio::test]
 async fn works_ok_for_block_100000(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     let block_number = BlockId::Number(100000);
 
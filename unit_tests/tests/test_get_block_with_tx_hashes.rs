@@ -10,10 +10,7 @@ use std::sync::Arc;
 
 use serde_json::Value;
 use starknet_core::types::{BlockId, BlockTag, FieldElement, StarknetError};
-use starknet_providers::{
-    jsonrpc::{HttpTransport, JsonRpcClient},
-    Provider,
-};
+use starknet_providers::{JsonRpcClient, Provider};
 use unit_tests::constants::DEOXYS;
 
 // Define a recursive function to compare JSON values and print differences
@@ -92,7 +89,7 @@ fn compare_json_values(path: &str, value1: &Value, value2: &Value) -> bool {
 ///
 #[rstest]
 #[tokio::test]
-async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
 
     let response_deoxys = deoxys
@@ -125,7 +122,7 @@ async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<HttpTran
 /// Be aware that this test can fail due to the last moments of a block being validated
 #[rstest]
 #[tokio::test]
-async fn work_existing_block(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_existing_block(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
@@ -191,7 +188,7 @@ async fn work_existing_block(clients: HashMap<String, JsonRpcClient<HttpTranspor
 #[rstest]
 #[tokio::test]
 #[ignore = "Pending fails some times when called on the cusp of being accepted, need virtual sequencer"]
-async fn work_pending_block(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_pending_block(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
@@ -218,8 +215,8 @@ async fn work_pending_block(clients: HashMap<String, JsonRpcClient<HttpTransport
 }
 
 async fn work_with_block(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
     _block_number: u64,
 ) {
     let block_number = get_block_setting();
@@ -277,8 +274,8 @@ async fn work_with_block(
 #[rstest]
 #[tokio::test]
 async fn work_with_block_1(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     work_with_block(deoxys, pathfinder, 1).await;
 }
@@ -287,8 +284,8 @@ async fn work_with_block_1(
 #[rstest]
 #[tokio::test]
 async fn work_with_block_3800(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     work_with_block(deoxys, pathfinder, 3000).await;
 }
@@ -297,8 +294,8 @@ async fn work_with_block_3800(
 #[rstest]
 #[tokio::test]
 async fn work_with_block_5066(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     work_with_block(deoxys, pathfinder, 5066).await;
 }
@@ -307,15 +304,18 @@ async fn work_with_block_5066(
 #[rstest]
 #[tokio::test]
 async fn work_with_block_1500(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     work_with_block(deoxys, pathfinder, 1500).await;
 }
 
 #[rstest]
 #[tokio::test]
-async fn work_loop(deoxys: JsonRpcClient<HttpTransport>, pathfinder: JsonRpcClient<HttpTransport>) {
+async fn work_loop(
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
+) {
     let arc_deoxys = Arc::new(deoxys);
     let arc_pathfinder = Arc::new(pathfinder);
     let parallels_queries = 10;
@@ -388,8 +388,8 @@ async fn work_loop(deoxys: JsonRpcClient<HttpTransport>, pathfinder: JsonRpcClie
 #[tokio::test]
 #[ignore = "Slash this ignore when Deoxys node is fully synced, but it may not works at all bc of computation"]
 async fn work_ok_with_pending_block(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     let mut set = tokio::task::JoinSet::new();
     let arc_deoxys = Arc::new(deoxys);
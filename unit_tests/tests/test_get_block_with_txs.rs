@@ -5,10 +5,7 @@ use colored::*;
 use common::*;
 use serde_json::Value;
 use starknet_core::types::{BlockId, BlockTag, FieldElement, StarknetError};
-use starknet_providers::{
-    jsonrpc::{HttpTransport, JsonRpcClient},
-    Provider,
-};
+use starknet_providers::{JsonRpcClient, Provider};
 use std::collections::HashMap;
 use std::sync::Arc;
 use unit_tests::constants::DEOXYS;
@@ -83,7 +80,7 @@ fn compare_json_values(path: &str, value1: &Value, value2: &Value) -> bool {
 
 #[rstest]
 #[tokio::test]
-async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
 
     let response_deoxys = deoxys
@@ -110,7 +107,7 @@ async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<HttpTran
 #[rstest]
 #[tokio::test]
 #[ignore = "fix with latest block"]
-async fn work_with_latest_block(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_with_latest_block(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
@@ -157,8 +154,8 @@ async fn work_with_latest_block(clients: HashMap<String, JsonRpcClient<HttpTrans
 }
 
 async fn work_with_block(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
     block_number: u64,
 ) {
     let response_deoxys = deoxys
@@ -201,15 +198,15 @@ async fn work_with_block(
 #[rstest]
 #[tokio::test]
 async fn work_with_block_1(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     work_with_block(deoxys, pathfinder, 1).await;
 }
 
 #[rstest]
 #[tokio::test]
-async fn work_with_block_one_hash(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_with_block_one_hash(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
@@ -260,8 +257,8 @@ async fn work_with_block_one_hash(clients: HashMap<String, JsonRpcClient<HttpTra
 #[rstest]
 #[tokio::test]
 async fn work_with_block_100_000(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     work_with_block(deoxys, pathfinder, 100_000).await;
 }
@@ -269,7 +266,7 @@ async fn work_with_block_100_000(
 #[rstest]
 #[tokio::test]
 async fn work_with_block_one_hundred_thousand_hash(
-    clients: HashMap<String, JsonRpcClient<HttpTransport>>,
+    clients: HashMap<String, JsonRpcClient<SuiteTransport>>,
 ) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
@@ -322,8 +319,8 @@ async fn work_with_block_one_hundred_thousand_hash(
 #[rstest]
 #[tokio::test]
 async fn work_with_block_3800(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     work_with_block(deoxys, pathfinder, 3800).await;
 }
@@ -332,8 +329,8 @@ async fn work_with_block_3800(
 #[rstest]
 #[tokio::test]
 async fn work_with_block_5066(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     work_with_block(deoxys, pathfinder, 5066).await;
 }
@@ -341,15 +338,18 @@ async fn work_with_block_5066(
 #[rstest]
 #[tokio::test]
 async fn work_with_block_1500(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     work_with_block(deoxys, pathfinder, 1500).await;
 }
 
 #[rstest]
 #[tokio::test]
-async fn work_loop(deoxys: JsonRpcClient<HttpTransport>, pathfinder: JsonRpcClient<HttpTransport>) {
+async fn work_loop(
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
+) {
     let arc_deoxys = Arc::new(deoxys);
     let arc_pathfinder = Arc::new(pathfinder);
     let parallels_queries = 10;
@@ -6,13 +6,13 @@ use base64::decode;
 use common::*;
 
 use starknet_core::types::{BlockId, ContractClass, FieldElement, StarknetError};
-use starknet_providers::{jsonrpc::HttpTransport, JsonRpcClient, Provider, ProviderError};
+use starknet_providers::{JsonRpcClient, Provider, ProviderError};
 use std::collections::HashMap;
 
 #[rstest]
 #[tokio::test]
 async fn fail_non_existing_block(
-    clients: HashMap<String, JsonRpcClient<HttpTransport>>,
+    clients: HashMap<String, JsonRpcClient<SuiteTransport>>,
 ) -> Result<()> {
     let deoxys = &clients[mainnet::network::DEOXYS];
 
@@ -35,7 +35,7 @@ async fn fail_non_existing_block(
 
 #[rstest]
 #[tokio::test]
-async fn fail_non_existing_class_hash(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn fail_non_existing_class_hash(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
 
     let unknown_contract_class_hash =
@@ -66,7 +66,7 @@ async fn fail_non_existing_class_hash(clients: HashMap<String, JsonRpcClient<Htt
 #[rstest]
 #[tokio::test]
 async fn work_ok_retrieving_class_for_contract_version_0(
-    clients: HashMap<String, JsonRpcClient<HttpTransport>>,
+    clients: HashMap<String, JsonRpcClient<SuiteTransport>>,
 ) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
@@ -107,7 +107,7 @@ async fn work_ok_retrieving_class_for_contract_version_0(
 #[rstest]
 #[tokio::test]
 async fn work_ok_retrieving_class_for_contract_version_1(
-    clients: HashMap<String, JsonRpcClient<HttpTransport>>,
+    clients: HashMap<String, JsonRpcClient<SuiteTransport>>,
 ) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
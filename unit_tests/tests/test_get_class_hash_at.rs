@@ -6,10 +6,7 @@ use common::*;
 use std::collections::HashMap;
 
 use starknet_core::types::{BlockId, BlockTag, FieldElement, StarknetError};
-use starknet_providers::{
-    jsonrpc::{HttpTransport, JsonRpcError},
-    JsonRpcClient, Provider,
-};
+use starknet_providers::{jsonrpc::JsonRpcError, JsonRpcClient, Provider};
 
 ///
 /// Unit test for `starknet_getClassHashAt`
@@ -19,7 +16,7 @@ use starknet_providers::{
 ///
 #[rstest]
 #[tokio::test]
-async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
 
     let response_deoxys = deoxys
@@ -54,7 +51,7 @@ async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<HttpTran
 ///
 #[rstest]
 #[tokio::test]
-async fn fail_non_existing_contract(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn fail_non_existing_contract(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
 
     let response_deoxys = deoxys
@@ -85,7 +82,7 @@ async fn fail_non_existing_contract(clients: HashMap<String, JsonRpcClient<HttpT
 ///
 #[rstest]
 #[tokio::test]
-async fn work_block_latest(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_block_latest(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
@@ -116,7 +113,7 @@ async fn work_block_latest(clients: HashMap<String, JsonRpcClient<HttpTransport>
 #[rstest]
 #[tokio::test]
 #[ignore = "Pending fails some times when called on the cusp of being accepted, need virtual sequencer"]
-async fn work_block_pending(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_block_pending(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
@@ -7,10 +7,7 @@ use anyhow::anyhow;
 use common::*;
 use starknet::macros::{felt_hex, selector};
 use starknet_core::types::{BlockId, EventFilter, EventsPage, FieldElement};
-use starknet_providers::{
-    jsonrpc::{HttpTransport, JsonRpcError},
-    JsonRpcClient, Provider, ProviderError,
-};
+use starknet_providers::{jsonrpc::JsonRpcError, JsonRpcClient, Provider, ProviderError};
 use tokio::task::JoinSet;
 
 ///
@@ -37,7 +34,7 @@ use tokio::task::JoinSet;
 #[rstest]
 #[tokio::test]
 #[logging]
-async fn fail_invalid_block_number(deoxys: JsonRpcClient<HttpTransport>) {
+async fn fail_invalid_block_number(deoxys: JsonRpcClient<SuiteTransport>) {
     let keys: Vec<Vec<FieldElement>> = vec![vec![selector!("transaction_executed")]];
     let block_nu: u64 = u64::MAX;
     let block_range: u64 = 100;
@@ -71,7 +68,7 @@ async fn fail_invalid_block_number(deoxys: JsonRpcClient<HttpTransport>) {
 #[rstest]
 #[tokio::test]
 #[logging]
-async fn fail_invalid_keys(deoxys: JsonRpcClient<HttpTransport>) {
+async fn fail_invalid_keys(deoxys: JsonRpcClient<SuiteTransport>) {
     let keys: Vec<Vec<FieldElement>> = vec![vec![selector!("")]];
     let block_nu: u64 = 50000;
     let block_range: u64 = 100;
@@ -96,7 +93,7 @@ async fn fail_invalid_keys(deoxys: JsonRpcClient<HttpTransport>) {
 #[rstest]
 #[tokio::test]
 #[logging]
-async fn fail_invalid_block_range(deoxys: JsonRpcClient<HttpTransport>) {
+async fn fail_invalid_block_range(deoxys: JsonRpcClient<SuiteTransport>) {
     let keys: Vec<Vec<FieldElement>> = vec![vec![selector!("")]];
     let block_nu: u64 = 50000;
     let block_range: u64 = 0;
@@ -132,8 +129,8 @@ async fn fail_invalid_block_range(deoxys: JsonRpcClient<HttpTransport>) {
 #[tokio::test]
 #[logging]
 async fn work_valid_call_no_selector(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     let keys: Vec<Vec<FieldElement>> = vec![vec![selector!("transaction_executed")]];
     let block_nu: u64 = 50000;
@@ -167,8 +164,8 @@ async fn work_valid_call_no_selector(
 #[tokio::test]
 #[logging]
 async fn work_valid_call_single_selector(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     // event type to retrieve
     let keys: Vec<Vec<FieldElement>> = vec![vec![selector!("transaction_executed")]];
@@ -203,8 +200,8 @@ async fn work_valid_call_single_selector(
 #[tokio::test]
 #[logging]
 async fn work_valid_call_multiple_selector(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     let keys: Vec<Vec<FieldElement>> = vec![vec![
         selector!("transaction_executed"),
@@ -231,7 +228,7 @@ async fn work_valid_call_multiple_selector(
 }
 
 async fn get_events(
-    client: &JsonRpcClient<HttpTransport>,
+    client: &JsonRpcClient<SuiteTransport>,
     keys: &[Vec<FieldElement>],
     block_nu: u64,
     block_range: u64,
@@ -260,7 +257,7 @@ async fn get_events(
 }
 
 async fn deep_check_events(
-    deoxys: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
     response_deoxys: EventsPage,
     keys: Vec<Vec<FieldElement>>,
     block_hash: FieldElement,
@@ -0,0 +1,111 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::collections::HashMap;
+
+use starknet_providers::{JsonRpcClient, Provider};
+use unit_tests::verify::event_filter::{verify_filter_matrix, verify_latest_to_block};
+
+///
+/// Test for `starknet_getEvents` filter semantics.
+///
+/// A matrix of filters is generated from the events of a block range: address filtering,
+/// positional keys with wildcards (empty inner arrays) and block ranges given as numbers, hashes
+/// or tags. Every filter is evaluated locally over the receipts served by Pathfinder, and the
+/// result is compared with the `getEvents` response of every node.
+///
+
+///
+/// Unit test for `starknet_getEvents` filter semantics
+///
+/// purpose: check the filter matrix on a single historical block.
+/// success case: every node selects exactly the events of the local filter.
+///
+#[rstest]
+#[tokio::test]
+async fn work_filter_matrix_single_block(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
+    let pathfinder = &clients[mainnet::network::PATHFINDER];
+
+    let mismatches = verify_filter_matrix(pathfinder, &clients, 50000..=50000, 1000)
+        .await
+        .expect("Error while generating event filters");
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+    assert!(mismatches.is_empty(), "Event filters do not match");
+}
+
+///
+/// Unit test for `starknet_getEvents` filter semantics
+///
+/// purpose: check the filter matrix over a range of v0.13.1 blocks.
+/// success case: every node selects exactly the events of the local filter.
+///
+#[rstest]
+#[tokio::test]
+async fn work_filter_matrix_block_range(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
+    let pathfinder = &clients[mainnet::network::PATHFINDER];
+    let block_number = mainnet::block::BLOCK_0_13_1;
+
+    let mismatches =
+        verify_filter_matrix(pathfinder, &clients, block_number..=block_number + 2, 1000)
+            .await
+            .expect("Error while generating event filters");
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+    assert!(mismatches.is_empty(), "Event filters do not match");
+}
+
+///
+/// Unit test for `starknet_getEvents` filter semantics
+///
+/// purpose: check filters from a historical block to the `latest` tag, keeping the events of
+/// that block only.
+/// success case: every node selects exactly the events of the local filter in that block.
+///
+#[rstest]
+#[tokio::test]
+async fn work_filter_to_latest_tag(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
+    let pathfinder = &clients[mainnet::network::PATHFINDER];
+
+    let mismatches = verify_latest_to_block(pathfinder, &clients, 50000..=50000, 1000)
+        .await
+        .expect("Error while generating event filters");
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+    assert!(mismatches.is_empty(), "Event filters do not match");
+}
+
+///
+/// Unit test for `starknet_getEvents` filter semantics
+///
+/// purpose: check the filter matrix on the latest block, including the `latest` tag.
+/// success case: every node selects exactly the events of the local filter.
+///
+#[rstest]
+#[tokio::test]
+#[ignore = "Fails if a new block is accepted while the matrix is running"]
+async fn work_filter_matrix_latest(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
+    let pathfinder = &clients[mainnet::network::PATHFINDER];
+
+    let latest = pathfinder
+        .block_number()
+        .await
+        .expect("Error while getting the block number");
+
+    let mismatches = verify_filter_matrix(pathfinder, &clients, latest..=latest, 1000)
+        .await
+        .expect("Error while generating event filters");
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+    assert!(mismatches.is_empty(), "Event filters do not match");
+}
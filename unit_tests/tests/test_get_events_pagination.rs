@@ -0,0 +1,126 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::collections::HashMap;
+
+use serde_json::json;
+use starknet::macros::selector;
+use starknet_core::types::{BlockId, EventFilter, FieldElement};
+use starknet_providers::JsonRpcClient;
+use unit_tests::mock::MockServer;
+use unit_tests::verify::events::{get_event_pages, verify_pagination};
+
+///
+/// Test for `starknet_getEvents` pagination.
+///
+/// Each filter is paged through with several chunk sizes by following `continuation_token` until
+/// the last page. The union of the pages must be the same for every chunk size, contain no
+/// duplicates and keep chain order: it is checked against the events rebuilt from
+/// `starknet_getBlockWithReceipts` on the same node.
+///
+
+const CHUNK_SIZES: [u64; 4] = [1, 7, 100, 1000];
+
+///
+/// Unit test for `starknet_getEvents` pagination
+///
+/// purpose: page through events matching a single selector.
+/// success case: pages hold every event of the block exactly once, in order.
+///
+#[rstest]
+#[tokio::test]
+async fn work_paginate_single_selector(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
+    let filter = EventFilter {
+        from_block: Some(BlockId::Number(50000)),
+        to_block: Some(BlockId::Number(50000)),
+        address: None,
+        keys: Some(vec![vec![selector!("transaction_executed")]]),
+    };
+
+    let mismatches = verify_pagination(&clients, &filter, &CHUNK_SIZES).await;
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+    assert!(mismatches.is_empty(), "Paginated events do not match");
+}
+
+///
+/// Unit test for `starknet_getEvents` pagination
+///
+/// purpose: page through all events of a contract over several blocks.
+/// success case: pages hold every event of the range exactly once, in order.
+///
+#[rstest]
+#[tokio::test]
+async fn work_paginate_address_over_block_range(
+    clients: HashMap<String, JsonRpcClient<SuiteTransport>>,
+) {
+    let filter = EventFilter {
+        from_block: Some(BlockId::Number(100000)),
+        to_block: Some(BlockId::Number(100002)),
+        address: Some(FieldElement::from_hex_be(mainnet::contract::STARKGATE_ETHER).unwrap()),
+        keys: None,
+    };
+
+    let mismatches = verify_pagination(&clients, &filter, &CHUNK_SIZES).await;
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+    assert!(mismatches.is_empty(), "Paginated events do not match");
+}
+
+///
+/// Unit test for `starknet_getEvents` pagination
+///
+/// purpose: page through every event of a block range without any filter.
+/// success case: pages hold every event of the range exactly once, in order.
+///
+#[rstest]
+#[tokio::test]
+async fn work_paginate_unfiltered(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
+    let filter = EventFilter {
+        from_block: Some(BlockId::Number(mainnet::block::BLOCK_0_13_1)),
+        to_block: Some(BlockId::Number(mainnet::block::BLOCK_0_13_1 + 1)),
+        address: None,
+        keys: None,
+    };
+
+    let mismatches = verify_pagination(&clients, &filter, &CHUNK_SIZES).await;
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+    assert!(mismatches.is_empty(), "Paginated events do not match");
+}
+
+///
+/// Unit test for `starknet_getEvents` pagination
+///
+/// purpose: page through a mock node which always returns the same continuation token.
+/// success case: paging stops with an error instead of looping forever.
+///
+#[rstest]
+#[tokio::test]
+async fn fail_paginate_repeated_token() {
+    let server = MockServer::start().await;
+    server.on_result(
+        "starknet_getEvents",
+        json!({ "events": [], "continuation_token": "0" }),
+    );
+    let filter = EventFilter {
+        from_block: Some(BlockId::Number(0)),
+        to_block: Some(BlockId::Number(0)),
+        address: None,
+        keys: None,
+    };
+
+    let pages = get_event_pages(&server.client(), &filter, 10).await;
+
+    let error = pages.expect_err("Repeated continuation token was followed");
+    assert!(error.to_string().contains("returned twice"), "{error}");
+    assert_eq!(server.requests().len(), 2);
+}
@@ -5,7 +5,7 @@ use std::collections::HashMap;
 
 use common::*;
 use starknet_core::types::{BlockId, BlockTag, FieldElement, StarknetError};
-use starknet_providers::{jsonrpc::HttpTransport, JsonRpcClient, Provider};
+use starknet_providers::{JsonRpcClient, Provider};
 
 ///
 /// Test for RPC call starknet_getNonce.
@@ -31,7 +31,7 @@ use starknet_providers::{jsonrpc::HttpTransport, JsonRpcClient, Provider};
 ///
 #[rstest]
 #[tokio::test]
-async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
 
     let response_deoxys = deoxys
@@ -64,7 +64,7 @@ async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<HttpTran
 ///
 #[rstest]
 #[tokio::test]
-async fn fail_non_existing_contract(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn fail_non_existing_contract(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
 
     let response_deoxys = deoxys
@@ -100,7 +100,7 @@ async fn fail_non_existing_contract(clients: HashMap<String, JsonRpcClient<HttpT
 ///
 #[rstest]
 #[tokio::test]
-async fn work_erc721_contract(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_erc721_contract(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
 
     let block_number = get_block_setting();
@@ -124,7 +124,7 @@ async fn work_erc721_contract(clients: HashMap<String, JsonRpcClient<HttpTranspo
 ///
 #[rstest]
 #[tokio::test]
-async fn work_erc20_contract(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_erc20_contract(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
 
     //This contract was created at Block 500192, so need to be synced to this minimum block
@@ -150,7 +150,7 @@ async fn work_erc20_contract(clients: HashMap<String, JsonRpcClient<HttpTranspor
 ///
 #[rstest]
 #[tokio::test]
-async fn work_account_contract(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_account_contract(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
@@ -184,7 +184,7 @@ async fn work_account_contract(clients: HashMap<String, JsonRpcClient<HttpTransp
 ///
 #[rstest]
 #[tokio::test]
-async fn work_account_proxy_contract(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_account_proxy_contract(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
@@ -6,7 +6,7 @@ use common::*;
 use starknet_core::types::{
     BlockId, BlockTag, MaybePendingStateUpdate, StarknetError, StateUpdate,
 };
-use starknet_providers::{jsonrpc::HttpTransport, JsonRpcClient, Provider};
+use starknet_providers::{JsonRpcClient, Provider};
 use std::collections::HashMap;
 
 pub fn extract_and_sort_state_update(maybe_update: MaybePendingStateUpdate) -> Option<StateUpdate> {
@@ -51,7 +51,7 @@ pub fn sort_state_update(state_update: StateUpdate) -> StateUpdate {
 
 #[rstest]
 #[tokio::test]
-async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
 
     let response_deoxys = deoxys.get_state_update(BlockId::Number(0)).await;
@@ -73,7 +73,7 @@ async fn fail_non_existing_block(clients: HashMap<String, JsonRpcClient<HttpTran
 
 #[rstest]
 #[tokio::test]
-async fn work_genesis_block(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_genesis_block(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
     let juno = &clients[mainnet::network::JUNO];
@@ -113,7 +113,7 @@ async fn work_genesis_block(clients: HashMap<String, JsonRpcClient<HttpTransport
 
 #[rstest]
 #[tokio::test]
-async fn work_existing_block(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_existing_block(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
     let juno = &clients[mainnet::network::JUNO];
@@ -154,7 +154,7 @@ async fn work_existing_block(clients: HashMap<String, JsonRpcClient<HttpTranspor
 
 #[rstest]
 #[tokio::test]
-async fn work_loop_existing_block(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_loop_existing_block(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
     let juno = &clients[mainnet::network::JUNO];
@@ -197,7 +197,7 @@ async fn work_loop_existing_block(clients: HashMap<String, JsonRpcClient<HttpTra
 #[rstest]
 #[tokio::test]
 #[ignore = "Pending data is not supported yet"]
-async fn work_block_pending(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_block_pending(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
     let juno = &clients[mainnet::network::JUNO];
@@ -237,7 +237,7 @@ async fn work_block_pending(clients: HashMap<String, JsonRpcClient<HttpTransport
 
 #[rstest]
 #[tokio::test]
-async fn work_block_latest(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_block_latest(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
     let juno = &clients[mainnet::network::JUNO];
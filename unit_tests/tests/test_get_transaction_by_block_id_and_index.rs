@@ -5,7 +5,7 @@ use std::collections::HashMap;
 
 use common::*;
 use starknet_core::types::{BlockId, FieldElement, StarknetError};
-use starknet_providers::{jsonrpc::HttpTransport, JsonRpcClient, Provider};
+use starknet_providers::{JsonRpcClient, Provider};
 
 ///
 /// Unit test for `starknet_getTransactionByBlockIdAndIndex`
@@ -15,7 +15,7 @@ use starknet_providers::{jsonrpc::HttpTransport, JsonRpcClient, Provider};
 ///
 #[rstest]
 #[tokio::test]
-async fn fail_non_existent_block(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn fail_non_existent_block(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
 
     let response_deoxys = deoxys
@@ -45,7 +45,7 @@ async fn fail_non_existent_block(clients: HashMap<String, JsonRpcClient<HttpTran
 ///
 #[rstest]
 #[tokio::test]
-async fn fail_non_existent_block_index(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn fail_non_existent_block_index(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
 
     let response_deoxys = deoxys
@@ -76,7 +76,7 @@ async fn fail_non_existent_block_index(clients: HashMap<String, JsonRpcClient<Ht
 ///
 #[rstest]
 #[tokio::test]
-async fn work_deploy_invoke(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_deploy_invoke(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
@@ -115,7 +115,7 @@ async fn work_deploy_invoke(clients: HashMap<String, JsonRpcClient<HttpTransport
 ///
 #[rstest]
 #[tokio::test]
-async fn work_deploy_l1_handler(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_deploy_l1_handler(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
@@ -154,7 +154,7 @@ async fn work_deploy_l1_handler(clients: HashMap<String, JsonRpcClient<HttpTrans
 ///
 #[rstest]
 #[tokio::test]
-async fn work_deploy_declare(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_deploy_declare(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
@@ -193,7 +193,7 @@ async fn work_deploy_declare(clients: HashMap<String, JsonRpcClient<HttpTranspor
 ///
 #[rstest]
 #[tokio::test]
-async fn work_deploy_account(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_deploy_account(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
@@ -5,7 +5,7 @@ use std::{assert_matches::assert_matches, collections::HashMap};
 
 use common::*;
 use starknet_core::types::{FieldElement, StarknetError, Transaction};
-use starknet_providers::{jsonrpc::HttpTransport, JsonRpcClient, Provider};
+use starknet_providers::{JsonRpcClient, Provider};
 
 ///
 /// Unit test for `starknet_getTransactionByHash`
@@ -15,7 +15,7 @@ use starknet_providers::{jsonrpc::HttpTransport, JsonRpcClient, Provider};
 ///
 #[rstest]
 #[tokio::test]
-async fn fail_non_existing_transaction(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn fail_non_existing_transaction(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
 
     let response_deoxys = deoxys.get_transaction_by_hash(FieldElement::ZERO).await;
@@ -44,7 +44,7 @@ async fn fail_non_existing_transaction(clients: HashMap<String, JsonRpcClient<Ht
 ///
 #[rstest]
 #[tokio::test]
-async fn work_transaction_invoke(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_transaction_invoke(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
@@ -70,7 +70,7 @@ async fn work_transaction_invoke(clients: HashMap<String, JsonRpcClient<HttpTran
 ///
 #[rstest]
 #[tokio::test]
-async fn work_transaction_l1_handler(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_transaction_l1_handler(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
@@ -96,7 +96,7 @@ async fn work_transaction_l1_handler(clients: HashMap<String, JsonRpcClient<Http
 ///
 #[rstest]
 #[tokio::test]
-async fn work_declare_transaction(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_declare_transaction(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
@@ -128,7 +128,7 @@ async fn work_declare_transaction(clients: HashMap<String, JsonRpcClient<HttpTra
 ///
 #[rstest]
 #[tokio::test]
-async fn work_transaction_deploy_account(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn work_transaction_deploy_account(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
 
@@ -152,8 +152,8 @@ async fn work_transaction_deploy_account(clients: HashMap<String, JsonRpcClient<
 
 /// helper function for testing transaction by hash
 async fn work_with_hash(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
     transaction_hash: &str,
 ) {
     let transaction_hash =
@@ -176,8 +176,8 @@ async fn work_with_hash(
 #[rstest]
 #[tokio::test]
 async fn work_with_first_transaction_block_0(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     work_with_hash(
         deoxys,
@@ -191,8 +191,8 @@ async fn work_with_first_transaction_block_0(
 #[rstest]
 #[tokio::test]
 async fn work_with_deploy_transaction_block_0(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     work_with_hash(
         deoxys,
@@ -206,8 +206,8 @@ async fn work_with_deploy_transaction_block_0(
 #[rstest]
 #[tokio::test]
 async fn work_with_invoke_transaction_block_0(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     work_with_hash(
         deoxys,
@@ -221,8 +221,8 @@ async fn work_with_invoke_transaction_block_0(
 #[rstest]
 #[tokio::test]
 async fn work_with_deploy_transaction_block_1(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     work_with_hash(
         deoxys,
@@ -236,8 +236,8 @@ async fn work_with_deploy_transaction_block_1(
 #[rstest]
 #[tokio::test]
 async fn work_with_invoke_transaction_block_10(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     work_with_hash(
         deoxys,
@@ -251,8 +251,8 @@ async fn work_with_invoke_transaction_block_10(
 #[rstest]
 #[tokio::test]
 async fn work_with_deploy_transaction_block_10(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     work_with_hash(
         deoxys,
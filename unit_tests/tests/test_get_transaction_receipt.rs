@@ -6,15 +6,12 @@ use common::*;
 use std::{char::MAX, collections::HashMap};
 
 use starknet_core::types::{FieldElement, StarknetError};
-use starknet_providers::{
-    jsonrpc::{HttpTransport, JsonRpcClient},
-    Provider,
-};
+use starknet_providers::{JsonRpcClient, Provider};
 
 // invalid transaction_hash
 #[rstest]
 #[tokio::test]
-async fn fail_invalid_transaction_hash(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn fail_invalid_transaction_hash(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
 
     let response_deoxys = deoxys.get_transaction_receipt(FieldElement::ZERO).await;
@@ -36,8 +33,8 @@ async fn fail_invalid_transaction_hash(clients: HashMap<String, JsonRpcClient<Ht
 }
 
 async fn work_with_hash(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
     transaction_hash: &str,
 ) {
     let transaction_hash =
@@ -60,8 +57,8 @@ async fn work_with_hash(
 #[rstest]
 #[tokio::test]
 async fn work_with_reverted_transaction_block_200_000(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     work_with_hash(
         deoxys,
@@ -75,8 +72,8 @@ async fn work_with_reverted_transaction_block_200_000(
 #[rstest]
 #[tokio::test]
 async fn work_with_first_transaction_block_0(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     work_with_hash(
         deoxys,
@@ -90,8 +87,8 @@ async fn work_with_first_transaction_block_0(
 #[rstest]
 #[tokio::test]
 async fn work_with_deploy_transaction_block_0(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     work_with_hash(
         deoxys,
@@ -105,8 +102,8 @@ async fn work_with_deploy_transaction_block_0(
 #[rstest]
 #[tokio::test]
 async fn work_with_invoke_transaction_block_0(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     work_with_hash(
         deoxys,
@@ -120,8 +117,8 @@ async fn work_with_invoke_transaction_block_0(
 #[rstest]
 #[tokio::test]
 async fn work_with_deploy_transaction_block_1(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     work_with_hash(
         deoxys,
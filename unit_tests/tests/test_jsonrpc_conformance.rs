@@ -0,0 +1,98 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::collections::HashMap;
+
+use serde_json::json;
+use unit_tests::conformance::{
+    check_expectation, conformance_cases, post_raw, run_conformance, Expectation, METHOD_NOT_FOUND,
+};
+use unit_tests::mock::{MockNetwork, MockResponse};
+use unit_tests::transport::suite::MODE_ENV;
+
+///
+/// JSON-RPC 2.0 protocol conformance tests, sent as raw HTTP bodies.
+///
+/// These cover the envelope rather than Starknet itself: request ids of every type,
+/// notifications, named and positional params, unknown methods, malformed JSON and batches.
+///
+
+///
+/// Unit test for JSON-RPC 2.0 conformance
+///
+/// purpose: send every conformance case to every node.
+/// success case: every node answers as the specification requires, and like the other nodes.
+///
+#[rstest]
+#[tokio::test]
+async fn work_jsonrpc_conformance(urls: HashMap<String, String>) {
+    if std::env::var(MODE_ENV).as_deref() == Ok("replay") {
+        println!("Raw HTTP requests cannot be replayed");
+        return;
+    }
+
+    let failures = run_conformance(&urls).await;
+
+    for failure in &failures {
+        println!("{failure}");
+    }
+    assert!(failures.is_empty(), "JSON-RPC conformance failed");
+}
+
+///
+/// Unit test for JSON-RPC 2.0 conformance
+///
+/// purpose: run the conformance cases on mock nodes.
+/// success case: the mock server conforms to the specification.
+///
+#[rstest]
+#[tokio::test]
+async fn work_mock_conformance() {
+    let network = MockNetwork::start().await;
+    network.on_result("starknet_chainId", json!("0x534e5f4d41494e"));
+    network.on_result("starknet_blockNumber", json!(0));
+    network.on_result("starknet_getBlockWithTxHashes", json!({}));
+
+    let urls = network
+        .servers
+        .iter()
+        .map(|(node, server)| (node.clone(), server.url()))
+        .collect();
+
+    let failures = run_conformance(&urls).await;
+    assert!(failures.is_empty(), "Unexpected failures: {failures:?}");
+}
+
+///
+/// Unit test for JSON-RPC 2.0 conformance
+///
+/// purpose: check a batch answered out of order, and a node which does not echo ids.
+/// success case: reordering is accepted and the wrong id is reported.
+///
+#[rstest]
+#[tokio::test]
+async fn fail_conformance_wrong_id() {
+    let expectation = Expectation::Batch(vec![
+        Expectation::Result(json!(1)),
+        Expectation::Error(json!(2), METHOD_NOT_FOUND),
+    ]);
+    let reordered = json!([
+        { "jsonrpc": "2.0", "id": 2, "error": { "code": METHOD_NOT_FOUND, "message": "" } },
+        { "jsonrpc": "2.0", "id": 1, "result": "0x1" },
+    ]);
+    assert!(check_expectation(&expectation, Some(&reordered)).is_ok());
+
+    let server = unit_tests::mock::MockServer::start().await;
+    server.on("starknet_chainId", |_| {
+        MockResponse::Raw(json!({ "jsonrpc": "2.0", "id": 0, "result": "0x1" }))
+    });
+
+    let case = conformance_cases()
+        .into_iter()
+        .find(|case| case.name == "string id")
+        .unwrap();
+    let response = post_raw(&server.url(), &case.body).await.unwrap();
+    assert!(check_expectation(&case.expectation, response.as_ref()).is_err());
+}
@@ -0,0 +1,216 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use macro_utils::TestConfig;
+use serde_json::{json, Value};
+use starknet_core::types::{FieldElement, TransactionExecutionStatus, TransactionStatus};
+use unit_tests::devnet::DevnetHarness;
+use unit_tests::generate_call;
+use unit_tests::lifecycle::{LifecycleReport, LifecycleTracker, Stage};
+use unit_tests::mock::{MockNetwork, MockResponse};
+
+///
+/// Tests for the lifecycle tracker, which follows a submitted transaction on every node.
+///
+
+fn status(finality_status: &str, execution_status: Option<&str>) -> Value {
+    match execution_status {
+        Some(execution_status) => json!({
+            "finality_status": finality_status,
+            "execution_status": execution_status,
+        }),
+        None => json!({ "finality_status": finality_status }),
+    }
+}
+
+fn invoke_receipt(execution_status: &str) -> Value {
+    let mut receipt = json!({
+        "type": "INVOKE",
+        "transaction_hash": "0x1",
+        "actual_fee": { "amount": "0x1", "unit": "WEI" },
+        "execution_status": execution_status,
+        "finality_status": "ACCEPTED_ON_L2",
+        "block_hash": "0x2",
+        "block_number": 1,
+        "messages_sent": [],
+        "events": [],
+        "execution_resources": {
+            "steps": 1,
+            "data_availability": { "l1_gas": 0, "l1_data_gas": 0 }
+        }
+    });
+    if execution_status == "REVERTED" {
+        receipt["revert_reason"] = json!("Insufficient balance");
+    }
+    receipt
+}
+
+///
+/// Unit test for lifecycle reports
+///
+/// purpose: record the statuses of two nodes accepting a transaction at different times.
+/// success case: transitions are kept once, propagation is relative to the first node and
+/// no inconsistency is found.
+///
+#[rstest]
+fn work_lifecycle_report() {
+    let accepted = TransactionStatus::AcceptedOnL2(TransactionExecutionStatus::Succeeded);
+    let ms = Duration::from_millis;
+
+    let mut report = LifecycleReport::new(FieldElement::ONE);
+    report.record_status("a", &TransactionStatus::Received, ms(10));
+    report.record_status("a", &TransactionStatus::Received, ms(20));
+    report.record_status("a", &accepted, ms(100));
+    report.record_status("b", &TransactionStatus::Received, ms(30));
+    report.record_status("b", &accepted, ms(250));
+
+    assert_eq!(report.nodes["a"].transitions.len(), 2);
+    assert_eq!(report.nodes["a"].reached(Stage::Received), Some(ms(10)));
+    assert_eq!(report.propagation(Stage::AcceptedOnL2)["a"], ms(0));
+    assert_eq!(report.propagation(Stage::AcceptedOnL2)["b"], ms(150));
+    assert!(report.inconsistencies().is_empty(), "{report}");
+}
+
+///
+/// Unit test for lifecycle reports
+///
+/// purpose: record nodes disagreeing on the stage and the execution of a transaction.
+/// success case: each disagreement is reported.
+///
+#[rstest]
+fn fail_lifecycle_report() {
+    let ms = Duration::from_millis;
+
+    let mut report = LifecycleReport::new(FieldElement::ONE);
+    report.record_status(
+        "a",
+        &TransactionStatus::AcceptedOnL2(TransactionExecutionStatus::Succeeded),
+        ms(100),
+    );
+    report.record_status(
+        "b",
+        &TransactionStatus::AcceptedOnL2(TransactionExecutionStatus::Reverted),
+        ms(100),
+    );
+    report.record_status(
+        "c",
+        &TransactionStatus::AcceptedOnL2(TransactionExecutionStatus::Succeeded),
+        ms(100),
+    );
+    report.record_status("c", &TransactionStatus::Received, ms(200));
+
+    let inconsistencies = report.inconsistencies();
+    assert!(inconsistencies
+        .iter()
+        .any(|i| i.starts_with("c: went from AcceptedOnL2 back to Received")));
+    assert!(inconsistencies
+        .iter()
+        .any(|i| i.starts_with("Nodes end at different stages")));
+    assert!(inconsistencies
+        .iter()
+        .any(|i| i.starts_with("Nodes disagree on the execution")));
+}
+
+///
+/// Unit test for the lifecycle tracker
+///
+/// purpose: track a transaction received then accepted by every mock node, one of which
+/// serves a reverted receipt.
+/// success case: every node reaches ACCEPTED_ON_L2, and the reverted receipt is reported.
+///
+#[rstest]
+#[tokio::test]
+async fn fail_track_reverted_receipt() {
+    let network = MockNetwork::start().await;
+    for (node, server) in &network.servers {
+        let polls = AtomicUsize::new(0);
+        server.on("starknet_getTransactionStatus", move |_| {
+            match polls.fetch_add(1, Ordering::SeqCst) {
+                0 => MockResponse::Result(status("RECEIVED", None)),
+                _ => MockResponse::Result(status("ACCEPTED_ON_L2", Some("SUCCEEDED"))),
+            }
+        });
+
+        let execution_status = match node.as_str() {
+            mainnet::network::PATHFINDER => "REVERTED",
+            _ => "SUCCEEDED",
+        };
+        server.on_result(
+            "starknet_getTransactionReceipt",
+            invoke_receipt(execution_status),
+        );
+    }
+
+    let clients = network.clients();
+    let report = LifecycleTracker::new(clients.iter())
+        .poll_interval(Duration::from_millis(10))
+        .timeout(Duration::from_secs(5))
+        .track(FieldElement::ONE, Instant::now())
+        .await;
+
+    for (node, timeline) in &report.nodes {
+        assert_eq!(timeline.stage(), Some(Stage::AcceptedOnL2), "{node}");
+        assert!(timeline.reached(Stage::Received).is_some(), "{node}");
+    }
+
+    let inconsistencies = report.inconsistencies();
+    assert!(
+        inconsistencies
+            .iter()
+            .any(|i| i.starts_with(&format!("{}: status is", mainnet::network::PATHFINDER))),
+        "{report}"
+    );
+    assert!(
+        inconsistencies
+            .iter()
+            .any(|i| i.starts_with("Nodes disagree on the execution")),
+        "{report}"
+    );
+}
+
+///
+/// Unit test for the lifecycle tracker
+///
+/// purpose: track a transfer sent to a devnet on every node following it.
+/// success case: every node accepts the transfer consistently.
+///
+#[rstest]
+#[tokio::test]
+async fn work_track_devnet_transfer(config: TestConfig) {
+    let harness = match DevnetHarness::from_test_config(&config).await {
+        Ok(harness) => harness,
+        Err(reason) => {
+            println!("{reason}");
+            return;
+        }
+    };
+
+    let transaction = harness
+        .builder(unit_tests::devnet::DEVNET, 0)
+        .await
+        .expect("Error while building the transaction")
+        .calls(vec![generate_call(
+            mainnet::contract::STARKGATE_ETHER,
+            "transfer",
+            vec![1, 1, 0],
+        )])
+        .build();
+
+    let submitted_at = Instant::now();
+    let transaction_hash = harness
+        .submit(unit_tests::devnet::DEVNET, transaction)
+        .await
+        .expect("Error while sending the transaction");
+
+    let report = LifecycleTracker::new(harness.nodes.iter())
+        .track(transaction_hash, submitted_at)
+        .await;
+
+    println!("{report}");
+    assert!(report.inconsistencies().is_empty(), "{report}");
+}
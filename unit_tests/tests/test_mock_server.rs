@@ -0,0 +1,153 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use serde_json::{json, Value};
+use starknet_core::types::{BlockId, StarknetError};
+use starknet_providers::Provider;
+use unit_tests::mock::{block_with_tx_hashes, MockNetwork, MockResponse, MockServer};
+use unit_tests::verify::chain::verify_chain_linkage;
+
+///
+/// Tests for Ditto itself, run against in-process mock nodes instead of real ones.
+///
+/// Each mock node answers with canned or programmable responses, so faulty behaviour such as a
+/// wrong hash or a missing field can be injected to check that Ditto detects it.
+///
+
+///
+/// Answers `getBlockWithTxHashes` with a linked chain where block `n` has hash `n + 1`.
+///
+fn linked_block(params: &Value) -> MockResponse {
+    let block_number = params["block_id"]["block_number"]
+        .as_u64()
+        .expect("Mock only serves blocks by number");
+
+    MockResponse::Result(block_with_tx_hashes(
+        block_number,
+        &format!("{:#x}", block_number + 1),
+        &format!("{block_number:#x}"),
+    ))
+}
+
+///
+/// Unit test for `checking_error_format`
+///
+/// purpose: recognize a Starknet error returned by a node.
+/// success case: a `BLOCK_NOT_FOUND` error matches `StarknetError::BlockNotFound` only.
+///
+#[rstest]
+#[tokio::test]
+async fn work_checking_error_format() {
+    let server = MockServer::start().await;
+    server.on_error("starknet_getBlockWithTxHashes", 24, "Block not found");
+
+    let error = server
+        .client()
+        .get_block_with_tx_hashes(BlockId::Number(0))
+        .await
+        .expect_err("Expected an error, but got a result");
+
+    assert!(checking_error_format(&error, StarknetError::BlockNotFound));
+    assert!(!checking_error_format(
+        &error,
+        StarknetError::ClassHashNotFound
+    ));
+}
+
+///
+/// Unit test for the mock server
+///
+/// purpose: record the calls received by a mock node.
+/// success case: every call is logged with its method and params.
+///
+#[rstest]
+#[tokio::test]
+async fn work_mock_logs_requests() {
+    let server = MockServer::start().await;
+    server.on_result("starknet_blockNumber", json!(1234));
+
+    let block_number = server
+        .client()
+        .block_number()
+        .await
+        .expect("Error while getting the block number");
+
+    assert_eq!(block_number, 1234);
+    assert_eq!(server.requests().len(), 1);
+    assert_eq!(server.requests()[0].0, "starknet_blockNumber");
+}
+
+///
+/// Unit test for the mock server
+///
+/// purpose: answer with a response missing its result.
+/// success case: the client reports an error instead of a value.
+///
+#[rstest]
+#[tokio::test]
+async fn fail_missing_result() {
+    let server = MockServer::start().await;
+    server.on("starknet_blockNumber", |_| {
+        MockResponse::Raw(json!({ "jsonrpc": "2.0", "id": 1 }))
+    });
+
+    let response = server.client().block_number().await;
+
+    assert!(response.is_err(), "Expected an error, but got a result");
+}
+
+///
+/// Unit test for the parent hash chain sweeper
+///
+/// purpose: serve a linked chain from every mock node.
+/// success case: no broken link is reported.
+///
+#[rstest]
+#[tokio::test]
+async fn work_chain_linkage_on_mock() {
+    let network = MockNetwork::start().await;
+    for server in network.servers.values() {
+        server.on("starknet_getBlockWithTxHashes", linked_block);
+    }
+
+    let mismatches = verify_chain_linkage(&network.clients(), 0..=5).await;
+
+    assert!(
+        mismatches.is_empty(),
+        "Unexpected mismatches: {mismatches:?}"
+    );
+}
+
+///
+/// Unit test for the parent hash chain sweeper
+///
+/// purpose: serve a wrong parent hash from a single mock node.
+/// success case: exactly that node and block are reported.
+///
+#[rstest]
+#[tokio::test]
+async fn fail_chain_linkage_wrong_parent_hash() {
+    let network = MockNetwork::start().await;
+    for server in network.servers.values() {
+        server.on("starknet_getBlockWithTxHashes", linked_block);
+    }
+    network[mainnet::network::JUNO].on(
+        "starknet_getBlockWithTxHashes",
+        |params| match linked_block(params) {
+            MockResponse::Result(mut block) if block["block_number"] == 3 => {
+                block["parent_hash"] = json!("0xdead");
+                MockResponse::Result(block)
+            }
+            response => response,
+        },
+    );
+
+    let mismatches = verify_chain_linkage(&network.clients(), 0..=5).await;
+
+    assert_eq!(mismatches.len(), 1, "Unexpected mismatches: {mismatches:?}");
+    assert_eq!(mismatches[0].node, mainnet::network::JUNO);
+    assert_eq!(mismatches[0].block_number, 3);
+    assert_eq!(mismatches[0].request, "parent_hash");
+}
@@ -0,0 +1,199 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde_json::{json, Value};
+use unit_tests::mock::{MockNetwork, MockResponse};
+use unit_tests::openrpc::{
+    check_negative_case, is_supported, spec_path, OpenRpcSpec, FIELD_PRIME, INVALID_PARAMS,
+};
+
+///
+/// Negative tests generated from the param schemas of the OpenRPC specification.
+///
+/// Every method is called with invalid variants of valid params: a param of the wrong JSON
+/// type, a missing required param, a felt above the field prime, an unknown enum tag and an
+/// extra positional param. Nodes must answer with `-32602 Invalid params`, or with one of the
+/// errors documented for the method.
+///
+
+fn spec() -> OpenRpcSpec {
+    OpenRpcSpec::new(json!({
+        "methods": [{
+            "name": "starknet_getClassHashAt",
+            "params": [
+                { "name": "block_id", "required": true,
+                  "schema": { "$ref": "#/components/schemas/BLOCK_ID" } },
+                { "name": "contract_address", "required": true,
+                  "schema": { "$ref": "#/components/schemas/ADDRESS" } }
+            ],
+            "errors": [{ "$ref": "#/components/errors/BLOCK_NOT_FOUND" }]
+        }],
+        "components": {
+            "schemas": {
+                "FELT": { "type": "string", "pattern": "^0x(0|[a-fA-F1-9]{1}[a-fA-F0-9]{0,62})$" },
+                "ADDRESS": { "$ref": "#/components/schemas/FELT" },
+                "BLOCK_ID": { "oneOf": [
+                    { "type": "object", "properties": {
+                        "block_number": { "type": "integer", "minimum": 0 }
+                    }, "required": ["block_number"] },
+                    { "$ref": "#/components/schemas/BLOCK_TAG" }
+                ] },
+                "BLOCK_TAG": { "type": "string", "enum": ["latest", "pending"] }
+            },
+            "errors": { "BLOCK_NOT_FOUND": { "code": 24, "message": "Block not found" } }
+        }
+    }))
+}
+
+///
+/// Unit test for OpenRPC negative cases
+///
+/// purpose: call every method of the specification with invalid params.
+/// success case: every node answers every case with an expected error.
+///
+#[rstest]
+#[tokio::test]
+async fn fail_negative_params(transports: HashMap<String, SuiteTransport>) {
+    let path = spec_path();
+    assert!(
+        Path::new(&path).exists(),
+        "No OpenRPC specification at {path}"
+    );
+    let spec = OpenRpcSpec::load(&path).expect("Error while loading the specification");
+    let methods = spec
+        .methods()
+        .expect("Error while reading the specification");
+
+    let mut nodes: Vec<&String> = transports.keys().collect();
+    nodes.sort();
+
+    let mut failures = vec![];
+    for method in methods.iter().filter(|method| is_supported(method)) {
+        let cases = spec
+            .negative_cases(method)
+            .expect("Error while generating negative cases");
+
+        for case in &cases {
+            for node in &nodes {
+                if let Err(e) = check_negative_case(&transports[*node], case).await {
+                    failures.push(format!("[{node}] {e}"));
+                }
+            }
+        }
+    }
+
+    for failure in &failures {
+        println!("{failure}");
+    }
+    assert!(failures.is_empty(), "Negative cases failed");
+}
+
+///
+/// Unit test for OpenRPC negative cases
+///
+/// purpose: generate the negative cases of every method of the vendored specification.
+/// success case: every reference resolves, every method is known to the client and has
+/// cases.
+///
+#[rstest]
+fn work_vendored_spec() {
+    let spec = OpenRpcSpec::load(&spec_path()).expect("Error while loading the specification");
+    let methods = spec
+        .methods()
+        .expect("Error while reading the specification");
+    assert!(!methods.is_empty());
+
+    for method in &methods {
+        assert!(is_supported(method), "{} is not supported", method.name);
+        let cases = spec
+            .negative_cases(method)
+            .expect("Error while generating negative cases");
+        assert!(!cases.is_empty(), "{} has no negative case", method.name);
+    }
+}
+
+///
+/// Unit test for OpenRPC negative cases
+///
+/// purpose: generate the negative cases of a method from its schemas.
+/// success case: one case per kind of invalid param, each expecting `-32602` or a documented
+/// error, and every other param names a block or contract which exists.
+///
+#[rstest]
+fn work_generate_negative_cases() {
+    let spec = spec();
+    let methods = spec.methods().unwrap();
+    assert_eq!(methods[0].errors, vec![24]);
+
+    let cases = spec.negative_cases(&methods[0]).unwrap();
+    let descriptions: Vec<&str> = cases.iter().map(|case| case.description.as_str()).collect();
+    assert_eq!(
+        descriptions,
+        vec![
+            "wrong type for 'block_id'",
+            "missing 'block_id'",
+            "unknown tag for 'block_id'",
+            "wrong type for 'contract_address'",
+            "missing 'contract_address'",
+            "'contract_address' above the field prime",
+            "extra positional param",
+        ]
+    );
+
+    let by_description = |description: &str| -> &Value {
+        &cases
+            .iter()
+            .find(|case| case.description == description)
+            .unwrap()
+            .params
+    };
+    assert_eq!(
+        by_description("'contract_address' above the field prime"),
+        &json!({ "block_id": "latest", "contract_address": FIELD_PRIME })
+    );
+    assert_eq!(
+        by_description("extra positional param"),
+        &json!(["latest", mainnet::contract::CONTRACT_ERC20, "0x1"])
+    );
+    assert!(cases
+        .iter()
+        .all(|case| case.expected_errors == vec![INVALID_PARAMS, 24]));
+}
+
+///
+/// Unit test for OpenRPC negative cases
+///
+/// purpose: check negative cases on mock nodes, one of which accepts invalid params.
+/// success case: only the node answering with a result is reported.
+///
+#[rstest]
+#[tokio::test]
+async fn fail_negative_case_accepted() {
+    let spec = spec();
+    let method = &spec.methods().unwrap()[0];
+    let cases = spec.negative_cases(method).unwrap();
+
+    let network = MockNetwork::start().await;
+    for server in network.servers.values() {
+        server.on_error("starknet_getClassHashAt", INVALID_PARAMS, "Invalid params");
+    }
+    network[mainnet::network::JUNO].on("starknet_getClassHashAt", |_| {
+        MockResponse::Result(json!("0x1"))
+    });
+
+    for (node, transport) in network.transports() {
+        for case in &cases {
+            let result = check_negative_case(&transport, case).await;
+            assert_eq!(
+                result.is_err(),
+                node == mainnet::network::JUNO,
+                "{node}: {result:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,275 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::assert_matches::assert_matches;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use macro_utils::TestConfig;
+use serde_json::json;
+use starknet_core::types::{FieldElement, StarknetError};
+use starknet_providers::jsonrpc::{HttpTransport, JsonRpcClient};
+use starknet_providers::ProviderError;
+use tokio::task::JoinSet;
+use unit_tests::devnet::{DevnetHarness, DEVNET};
+use unit_tests::generate_call;
+use unit_tests::mock::{MockResponse, MockServer};
+use unit_tests::nonce::NonceManager;
+use url::Url;
+
+///
+/// Tests for the nonce manager, which hands out sequential nonces to concurrent tasks.
+///
+
+fn address() -> FieldElement {
+    FieldElement::from_hex_be("0x123").unwrap()
+}
+
+fn nonce_requests(server: &MockServer) -> usize {
+    server
+        .requests()
+        .iter()
+        .filter(|(method, _)| method == "starknet_getNonce")
+        .count()
+}
+
+///
+/// Answers `starknet_getNonce` with each of `nonces` in turn, then with the last one.
+///
+fn serve_nonces(server: &MockServer, nonces: &'static [&'static str]) {
+    let calls = AtomicUsize::new(0);
+    server.on("starknet_getNonce", move |_| {
+        let call = calls.fetch_add(1, Ordering::SeqCst);
+        MockResponse::Result(json!(nonces[call.min(nonces.len() - 1)]))
+    });
+}
+
+///
+/// Unit test for the nonce manager
+///
+/// purpose: ask for nonces of the same account from many tasks at once.
+/// success case: the pending nonce is fetched once, and every task gets a distinct nonce
+/// following it.
+///
+#[rstest]
+#[tokio::test]
+async fn work_next_nonce_concurrent() {
+    let server = MockServer::start().await;
+    serve_nonces(&server, &["0x5"]);
+    let nonces = Arc::new(NonceManager::new(server.client()));
+
+    let mut tasks = JoinSet::new();
+    for _ in 0..20 {
+        let nonces = nonces.clone();
+        tasks.spawn(async move { nonces.next(address()).await });
+    }
+
+    let mut handed_out = vec![];
+    while let Some(nonce) = tasks.join_next().await {
+        handed_out.push(nonce.unwrap().expect("Error while getting a nonce"));
+    }
+    handed_out.sort();
+
+    let expected: Vec<FieldElement> = (5u64..25).map(FieldElement::from).collect();
+    assert_eq!(handed_out, expected);
+    assert_eq!(nonce_requests(&server), 1);
+}
+
+///
+/// Unit test for the nonce manager
+///
+/// purpose: resync an account whose pending nonce went back, as when a transaction is
+/// rejected.
+/// success case: nonces are handed out again from the new pending nonce.
+///
+#[rstest]
+#[tokio::test]
+async fn work_resync_nonce() {
+    let server = MockServer::start().await;
+    serve_nonces(&server, &["0x5", "0x6"]);
+    let nonces = NonceManager::new(server.client());
+
+    assert_eq!(
+        nonces.next(address()).await.unwrap(),
+        FieldElement::from(5u64)
+    );
+    assert_eq!(
+        nonces.next(address()).await.unwrap(),
+        FieldElement::from(6u64)
+    );
+    assert_eq!(
+        nonces.next(address()).await.unwrap(),
+        FieldElement::from(7u64)
+    );
+
+    assert_eq!(
+        nonces.resync(address()).await.unwrap(),
+        FieldElement::from(6u64)
+    );
+    assert_eq!(
+        nonces.next(address()).await.unwrap(),
+        FieldElement::from(6u64)
+    );
+    assert_eq!(nonce_requests(&server), 2);
+}
+
+///
+/// Unit test for the nonce manager
+///
+/// purpose: send a transaction whose first nonce is rejected as invalid.
+/// success case: the manager resyncs and the transaction is sent again with the pending
+/// nonce.
+///
+#[rstest]
+#[tokio::test]
+async fn work_send_resyncs_invalid_nonce() {
+    let server = MockServer::start().await;
+    serve_nonces(&server, &["0x5", "0x7"]);
+    let nonces = NonceManager::new(server.client());
+
+    let sent = nonces
+        .send(address(), 1, |nonce| async move {
+            match nonce == FieldElement::from(7u64) {
+                true => Ok(nonce),
+                false => Err(ProviderError::StarknetError(
+                    StarknetError::InvalidTransactionNonce,
+                )),
+            }
+        })
+        .await;
+
+    assert_eq!(sent.unwrap(), FieldElement::from(7u64));
+    assert_eq!(
+        nonces.next(address()).await.unwrap(),
+        FieldElement::from(8u64)
+    );
+}
+
+///
+/// Unit test for the nonce manager
+///
+/// purpose: send transactions from many tasks at once, whose first nonces are all rejected as
+/// invalid.
+/// success case: the account is resynced once, and the transactions are sent again with
+/// distinct nonces following the new pending nonce.
+///
+#[rstest]
+#[tokio::test]
+async fn work_send_resyncs_once_concurrent() {
+    let server = MockServer::start().await;
+    serve_nonces(&server, &["0x5", "0xa"]);
+    let nonces = Arc::new(NonceManager::new(server.client()));
+
+    let mut tasks = JoinSet::new();
+    for _ in 0..5 {
+        let nonces = nonces.clone();
+        tasks.spawn(async move {
+            nonces
+                .send(address(), 3, |nonce| async move {
+                    tokio::task::yield_now().await;
+                    match nonce >= FieldElement::from(10u64) {
+                        true => Ok(nonce),
+                        false => Err(ProviderError::StarknetError(
+                            StarknetError::InvalidTransactionNonce,
+                        )),
+                    }
+                })
+                .await
+        });
+    }
+
+    let mut sent = vec![];
+    while let Some(nonce) = tasks.join_next().await {
+        sent.push(nonce.unwrap().expect("Error while sending the transaction"));
+    }
+    sent.sort();
+
+    let expected: Vec<FieldElement> = (10u64..15).map(FieldElement::from).collect();
+    assert_eq!(sent, expected);
+    assert_eq!(nonce_requests(&server), 2);
+}
+
+///
+/// Unit test for the nonce manager
+///
+/// purpose: send a transaction whose nonce is always rejected.
+/// success case: the error is returned once the retries are exhausted.
+///
+#[rstest]
+#[tokio::test]
+async fn fail_send_invalid_nonce() {
+    let server = MockServer::start().await;
+    serve_nonces(&server, &["0x5"]);
+    let nonces = NonceManager::new(server.client());
+
+    let sent: Result<(), _> = nonces
+        .send(address(), 2, |_| async {
+            Err(ProviderError::StarknetError(
+                StarknetError::InvalidTransactionNonce,
+            ))
+        })
+        .await;
+
+    assert_matches!(
+        sent,
+        Err(ProviderError::StarknetError(
+            StarknetError::InvalidTransactionNonce
+        ))
+    );
+    assert_eq!(nonce_requests(&server), 3);
+}
+
+///
+/// Unit test for the nonce manager
+///
+/// purpose: pipeline transfers from one predeployed account of the devnet, without waiting
+/// for each other.
+/// success case: every transfer is accepted and succeeds.
+///
+#[rstest]
+#[tokio::test]
+async fn work_pipeline_devnet_transfers(config: TestConfig) {
+    let harness = match DevnetHarness::from_test_config(&config).await {
+        Ok(harness) => harness,
+        Err(reason) => {
+            println!("{reason}");
+            return;
+        }
+    };
+    let harness = Arc::new(harness);
+    let client = JsonRpcClient::new(HttpTransport::new(Url::parse(&harness.url).unwrap()));
+    let nonces = Arc::new(NonceManager::new(client));
+    let address = harness.accounts[0].address;
+
+    let mut tasks = JoinSet::new();
+    for _ in 0..5 {
+        let (harness, nonces) = (harness.clone(), nonces.clone());
+        tasks.spawn(async move {
+            let builder = harness
+                .builder(DEVNET, 0)
+                .await
+                .expect("Error while building the transaction")
+                .calls(vec![generate_call(
+                    mainnet::contract::STARKGATE_ETHER,
+                    "transfer",
+                    vec![1, 1, 0],
+                )]);
+
+            let transaction_hash = nonces
+                .send(address, 3, |nonce| {
+                    let (harness, builder) = (harness.clone(), builder.clone());
+                    async move { harness.submit(DEVNET, builder.nonce(nonce).build()).await }
+                })
+                .await
+                .expect("Error while sending the transaction");
+
+            harness.wait_for_acceptance(DEVNET, transaction_hash).await
+        });
+    }
+
+    while let Some(receipt) = tasks.join_next().await {
+        receipt.unwrap().unwrap_or_else(|e| panic!("{e}"));
+    }
+}
@@ -0,0 +1,115 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::collections::HashMap;
+use std::fs;
+
+use starknet_core::types::BlockId;
+use starknet_providers::{JsonRpcClient, Provider};
+use unit_tests::transport::{corpus::CorpusEntry, recording::RecordingTransport};
+
+///
+/// Test for the recording transport.
+///
+/// Requests are sent to every node through a [`RecordingTransport`] writing to a temporary
+/// corpus, which must then hold one entry per request with the raw response of the node.
+///
+
+fn read_corpus(path: &str) -> Vec<CorpusEntry> {
+    fs::read_to_string(path)
+        .expect("Error while reading the corpus")
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("Invalid corpus entry"))
+        .collect()
+}
+
+///
+/// Unit test for the recording transport
+///
+/// purpose: record successful requests to every node.
+/// success case: every request is recorded once with its node, method, params and result.
+///
+#[rstest]
+#[tokio::test]
+async fn work_records_every_request(transports: HashMap<String, SuiteTransport>) {
+    let path = std::env::temp_dir().join("ditto_work_records_every_request.jsonl");
+    let path = path.to_str().expect("Invalid corpus path");
+    let _ = fs::remove_file(path);
+
+    for (node, transport) in transports {
+        let client = JsonRpcClient::new(RecordingTransport::with_path(&node, transport, path));
+
+        client
+            .block_hash_and_number()
+            .await
+            .expect("Error while getting the latest block");
+        client
+            .get_block_transaction_count(BlockId::Number(1000))
+            .await
+            .expect("Error while getting the transaction count");
+    }
+
+    let entries = read_corpus(path);
+    assert_eq!(entries.len(), 6);
+
+    for node in [
+        mainnet::network::DEOXYS,
+        mainnet::network::PATHFINDER,
+        mainnet::network::JUNO,
+    ] {
+        let methods: Vec<_> = entries
+            .iter()
+            .filter(|entry| entry.node == node)
+            .map(|entry| entry.method.as_str())
+            .collect();
+        assert_eq!(
+            methods,
+            [
+                "starknet_blockHashAndNumber",
+                "starknet_getBlockTransactionCount"
+            ]
+        );
+    }
+
+    for entry in &entries {
+        assert!(entry.response.get("result").is_some());
+        assert!(entry.timestamp > 0);
+    }
+}
+
+///
+/// Unit test for the recording transport
+///
+/// purpose: record a request answered with a JSON-RPC error.
+/// success case: the error is recorded and still returned to the caller.
+///
+#[rstest]
+#[tokio::test]
+async fn work_records_errors(transports: HashMap<String, SuiteTransport>) {
+    let path = std::env::temp_dir().join("ditto_work_records_errors.jsonl");
+    let path = path.to_str().expect("Invalid corpus path");
+    let _ = fs::remove_file(path);
+
+    let transport = transports
+        .into_iter()
+        .find(|(node, _)| node == mainnet::network::DEOXYS)
+        .map(|(_, transport)| transport)
+        .expect("Missing Deoxys transport");
+    let client = JsonRpcClient::new(RecordingTransport::with_path(
+        mainnet::network::DEOXYS,
+        transport,
+        path,
+    ));
+
+    let response = client
+        .get_block_transaction_count(BlockId::Number(u64::MAX))
+        .await;
+    assert!(response.is_err(), "Expected an error, but got a result");
+
+    let entries = read_corpus(path);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].params["block_id"]["block_number"], u64::MAX);
+    assert!(entries[0].response.get("error").is_some());
+}
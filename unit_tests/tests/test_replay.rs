@@ -0,0 +1,145 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::collections::HashMap;
+use std::fs;
+
+use serde_json::json;
+use starknet_core::types::{BlockId, FieldElement};
+use starknet_providers::{JsonRpcClient, Provider};
+use unit_tests::transport::{
+    corpus::{append, CorpusEntry},
+    recording::RecordingTransport,
+    replay::{corpus_key, ReplayTransport},
+};
+
+///
+/// Test for the replay transport.
+///
+/// The whole suite runs offline with `DITTO_MODE=replay`, answering every request from the
+/// corpus recorded with `DITTO_MODE=record`. These tests check that replayed responses match
+/// the recorded ones, and that requests missing from the corpus fail without reaching a node.
+///
+
+///
+/// Unit test for the replay transport
+///
+/// purpose: replay a handcrafted corpus without any node.
+/// success case: recorded requests are answered, others fail as not recorded.
+///
+#[rstest]
+#[tokio::test]
+async fn work_replay_offline() {
+    let path = std::env::temp_dir().join("ditto_work_replay_offline.jsonl");
+    let path = path.to_str().expect("Invalid corpus path");
+    let _ = fs::remove_file(path);
+
+    append(
+        path,
+        &CorpusEntry {
+            node: String::from(mainnet::network::DEOXYS),
+            method: String::from("starknet_getBlockTransactionCount"),
+            params: json!({ "block_id": { "block_number": 1000 } }),
+            response: json!({ "jsonrpc": "2.0", "id": 1, "result": 42 }),
+            latency_ms: 0,
+            timestamp: 0,
+        },
+    )
+    .expect("Error while writing the corpus");
+
+    let client = JsonRpcClient::new(ReplayTransport::with_path(mainnet::network::DEOXYS, path));
+
+    let count = client
+        .get_block_transaction_count(BlockId::Number(1000))
+        .await
+        .expect("Recorded request was not replayed");
+    assert_eq!(count, 42);
+
+    let error = client
+        .get_block_transaction_count(BlockId::Number(1001))
+        .await
+        .expect_err("Request missing from the corpus was answered");
+    assert!(
+        error.to_string().contains("Not recorded"),
+        "Unexpected error: {error}"
+    );
+}
+
+///
+/// Unit test for the replay transport
+///
+/// purpose: build corpus keys from the same params written with their fields in different
+/// orders.
+/// success case: the keys are identical, and differ from keys of other params.
+///
+#[rstest]
+fn work_corpus_key_canonical() {
+    let key = |params| corpus_key(mainnet::network::DEOXYS, "starknet_getStorageAt", &params);
+
+    let params: serde_json::Value =
+        serde_json::from_str(r#"{"key":"0x2","block_id":{"block_number":1,"x":[{"b":1,"a":2}]}}"#)
+            .unwrap();
+    let reordered: serde_json::Value =
+        serde_json::from_str(r#"{"block_id":{"x":[{"a":2,"b":1}],"block_number":1},"key":"0x2"}"#)
+            .unwrap();
+
+    assert_eq!(key(params.clone()), key(reordered));
+    assert_ne!(key(params), key(json!({ "key": "0x2" })));
+}
+
+///
+/// Unit test for the replay transport
+///
+/// purpose: replay requests recorded from every node.
+/// success case: replayed responses are identical to the live ones.
+///
+#[rstest]
+#[tokio::test]
+async fn work_replay_matches_recording(transports: HashMap<String, SuiteTransport>) {
+    let path = std::env::temp_dir().join("ditto_work_replay_matches_recording.jsonl");
+    let path = path.to_str().expect("Invalid corpus path");
+    let _ = fs::remove_file(path);
+
+    let mut recorded = HashMap::new();
+    for (node, transport) in transports {
+        let client = JsonRpcClient::new(RecordingTransport::with_path(&node, transport, path));
+        let block = client
+            .get_block_with_tx_hashes(BlockId::Number(50000))
+            .await
+            .expect("Error while getting the block");
+        let storage = client
+            .get_storage_at(
+                FieldElement::from_hex_be(mainnet::contract::STARKGATE_ETHER).unwrap(),
+                FieldElement::ZERO,
+                BlockId::Number(50000),
+            )
+            .await
+            .expect("Error while getting the storage");
+        recorded.insert(node, (block, storage));
+    }
+
+    for (node, (block, storage)) in recorded {
+        let client = JsonRpcClient::new(ReplayTransport::with_path(&node, path));
+
+        let replayed_block = client
+            .get_block_with_tx_hashes(BlockId::Number(50000))
+            .await
+            .expect("Error while replaying the block");
+        let replayed_storage = client
+            .get_storage_at(
+                FieldElement::from_hex_be(mainnet::contract::STARKGATE_ETHER).unwrap(),
+                FieldElement::ZERO,
+                BlockId::Number(50000),
+            )
+            .await
+            .expect("Error while replaying the storage");
+
+        assert_eq!(block, replayed_block, "Replayed block differs on {node}");
+        assert_eq!(
+            storage, replayed_storage,
+            "Replayed storage differs on {node}"
+        );
+    }
+}
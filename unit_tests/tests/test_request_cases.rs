@@ -0,0 +1,129 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde_json::json;
+use unit_tests::cases::{load_cases, requests_path, run_case, run_cases, RequestCase};
+use unit_tests::mock::MockNetwork;
+
+///
+/// Data-driven tests from JSONL request cases.
+///
+/// Each line of a cases file is a JSON-RPC request, optionally with paths to ignore, an expected
+/// error code or a subset of nodes. Every case is run against every node and the responses are
+/// compared, so regression cases can be added without writing Rust.
+///
+
+///
+/// Unit test for JSONL request cases
+///
+/// purpose: run the regression cases shipped with Ditto.
+/// success case: every node answers every case identically.
+///
+#[rstest]
+#[tokio::test]
+async fn work_regression_cases(transports: HashMap<String, SuiteTransport>) {
+    let cases = load_cases("cases/regressions.jsonl").expect("Error while loading request cases");
+
+    let failures = run_cases(&transports, &cases).await;
+
+    for failure in &failures {
+        println!("{failure}");
+    }
+    assert!(failures.is_empty(), "Request cases failed");
+}
+
+///
+/// Unit test for JSONL request cases
+///
+/// purpose: run the cases of `cases.jsonl`, or of the file given by `DITTO_REQUESTS`.
+/// success case: every node answers every case identically.
+///
+#[rstest]
+#[tokio::test]
+async fn work_requests_file(transports: HashMap<String, SuiteTransport>) {
+    let path = requests_path();
+    if !Path::new(&path).exists() {
+        println!("No request cases at {path}");
+        return;
+    }
+    let cases = load_cases(&path).expect("Error while loading request cases");
+
+    let failures = run_cases(&transports, &cases).await;
+
+    for failure in &failures {
+        println!("{failure}");
+    }
+    assert!(failures.is_empty(), "Request cases failed");
+}
+
+///
+/// Unit test for JSONL request cases
+///
+/// purpose: run a case on mock nodes where one node serves a different field.
+/// success case: the node is reported, unless the field is ignored.
+///
+#[rstest]
+#[tokio::test]
+async fn fail_case_detects_difference() {
+    let network = MockNetwork::start().await;
+    network.on_result(
+        "starknet_blockHashAndNumber",
+        json!({ "block_hash": "0x1", "block_number": 1 }),
+    );
+    network[mainnet::network::JUNO].on_result(
+        "starknet_blockHashAndNumber",
+        json!({ "block_hash": "0x2", "block_number": 1 }),
+    );
+
+    let mut case = RequestCase {
+        name: None,
+        method: String::from("starknet_blockHashAndNumber"),
+        params: json!([]),
+        ignore: vec![],
+        expected_error: None,
+        nodes: None,
+    };
+
+    let failures = run_case(&network.transports(), &case).await;
+    assert_eq!(failures.len(), 1, "Unexpected failures: {failures:?}");
+    assert_eq!(failures[0].node, mainnet::network::JUNO);
+    assert!(failures[0].message.contains("$.result.block_hash"));
+
+    case.ignore = vec![String::from("$.result.block_hash")];
+    let failures = run_case(&network.transports(), &case).await;
+    assert!(failures.is_empty(), "Unexpected failures: {failures:?}");
+}
+
+///
+/// Unit test for JSONL request cases
+///
+/// purpose: run a case expecting an error on mock nodes, one of which returns a result.
+/// success case: only the node returning a result is reported.
+///
+#[rstest]
+#[tokio::test]
+async fn fail_case_expected_error() {
+    let network = MockNetwork::start().await;
+    for server in network.servers.values() {
+        server.on_error("starknet_getBlockWithTxHashes", 24, "Block not found");
+    }
+    network[mainnet::network::DEOXYS].on_result("starknet_getBlockWithTxHashes", json!({}));
+
+    let case = RequestCase {
+        name: Some(String::from("block not found")),
+        method: String::from("starknet_getBlockWithTxHashes"),
+        params: json!({ "block_id": { "block_number": u64::MAX } }),
+        ignore: vec![],
+        expected_error: Some(24),
+        nodes: None,
+    };
+
+    let failures = run_case(&network.transports(), &case).await;
+    assert_eq!(failures.len(), 1, "Unexpected failures: {failures:?}");
+    assert_eq!(failures[0].node, mainnet::network::DEOXYS);
+}
@@ -0,0 +1,185 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+use unit_tests::mock::{MockNetwork, MockResponse};
+use unit_tests::scenario::{load_scenarios, run_scenario, substitute, Scenario};
+
+///
+/// Scenario tests, chaining calls with values captured from earlier responses.
+///
+/// Scenarios are YAML or TOML files under `unit_tests/scenarios/`. Each step is sent to every
+/// node, its assertions are checked and its response is compared across nodes.
+///
+
+const SCENARIO: &str = r#"
+name: deployed account
+steps:
+  - name: find a deployed account
+    method: starknet_getBlockWithReceipts
+    params: { block_id: { block_number: 10 } }
+    capture:
+      account: "$.result.transactions[?(@.transaction.type=='DEPLOY_ACCOUNT')]"
+  - name: class hash
+    method: starknet_getClassHashAt
+    params:
+      block_id: { block_number: 10 }
+      contract_address: "${account.receipt.contract_address}"
+    assert:
+      - path: "$.result"
+        equals: "${account.transaction.class_hash}"
+"#;
+
+fn block_with_receipts() -> Value {
+    json!({
+        "block_number": 10,
+        "transactions": [
+            {
+                "transaction": { "type": "INVOKE", "sender_address": "0x1" },
+                "receipt": { "type": "INVOKE" }
+            },
+            {
+                "transaction": { "type": "DEPLOY_ACCOUNT", "class_hash": "0xc1a55" },
+                "receipt": { "type": "DEPLOY_ACCOUNT", "contract_address": "0xacc" }
+            }
+        ]
+    })
+}
+
+///
+/// Unit test for scenarios
+///
+/// purpose: run the scenarios shipped with Ditto.
+/// success case: every step passes on every node.
+///
+#[rstest]
+#[tokio::test]
+async fn work_scenarios(transports: HashMap<String, SuiteTransport>) {
+    let scenarios = load_scenarios("scenarios").expect("Error while loading scenarios");
+
+    for scenario in &scenarios {
+        let failures = run_scenario(&transports, scenario).await;
+
+        for failure in &failures {
+            println!("{failure}");
+        }
+        assert!(failures.is_empty(), "Scenario '{}' failed", scenario.name);
+    }
+}
+
+///
+/// Unit test for scenario variables
+///
+/// purpose: substitute whole and partial variable references, including fields of objects.
+/// success case: whole references keep their type and partial ones are formatted.
+///
+#[rstest]
+fn work_substitute() {
+    let variables = HashMap::from([
+        (String::from("block"), json!(10)),
+        (
+            String::from("account"),
+            json!({ "address": "0xacc", "keys": ["0x1"] }),
+        ),
+    ]);
+
+    let params = json!({
+        "block_id": { "block_number": "${block}" },
+        "contract_address": "${account.address}",
+        "key": "${account.keys[0]}",
+        "label": "block ${block} of ${account.address}",
+    });
+
+    assert_eq!(
+        substitute(&params, &variables).unwrap(),
+        json!({
+            "block_id": { "block_number": 10 },
+            "contract_address": "0xacc",
+            "key": "0x1",
+            "label": "block 10 of 0xacc",
+        })
+    );
+    assert!(substitute(&json!("${missing}"), &variables).is_err());
+}
+
+///
+/// Unit test for scenarios
+///
+/// purpose: run a scenario on mock nodes, capturing an address from a filtered transaction.
+/// success case: the captured address is sent to every node and the assertion passes.
+///
+#[rstest]
+#[tokio::test]
+async fn work_scenario_capture() {
+    let scenario: Scenario = serde_yaml::from_str(SCENARIO).unwrap();
+    let network = MockNetwork::start().await;
+    network.on_result("starknet_getBlockWithReceipts", block_with_receipts());
+    network.on_result("starknet_getClassHashAt", json!("0x0c1a55"));
+
+    let failures = run_scenario(&network.transports(), &scenario).await;
+    assert!(failures.is_empty(), "Unexpected failures: {failures:?}");
+
+    for server in network.servers.values() {
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].1["contract_address"], json!("0xacc"));
+    }
+}
+
+///
+/// Unit test for scenarios
+///
+/// purpose: run a scenario on mock nodes where one node misses the deployed account.
+/// success case: only that node is reported, on the step it failed.
+///
+#[rstest]
+#[tokio::test]
+async fn fail_scenario_assertion() {
+    let scenario: Scenario = serde_yaml::from_str(SCENARIO).unwrap();
+    let network = MockNetwork::start().await;
+    network.on_result("starknet_getBlockWithReceipts", block_with_receipts());
+    network.on_result("starknet_getClassHashAt", json!("0xc1a55"));
+    network[mainnet::network::PATHFINDER].on("starknet_getClassHashAt", |_| MockResponse::Error {
+        code: 20,
+        message: String::from("Contract not found"),
+        data: None,
+    });
+
+    let failures = run_scenario(&network.transports(), &scenario).await;
+    assert_eq!(failures.len(), 1, "Unexpected failures: {failures:?}");
+    assert_eq!(failures[0].node, mainnet::network::PATHFINDER);
+    assert_eq!(failures[0].case, "deployed account: class hash");
+}
+
+///
+/// Unit test for scenarios
+///
+/// purpose: run a scenario whose step ignores an invalid JSON path.
+/// success case: the bad path is reported as a failure of that step on every node.
+///
+#[rstest]
+#[tokio::test]
+async fn fail_scenario_bad_ignore_path() {
+    let scenario: Scenario = serde_yaml::from_str(
+        r#"
+name: bad ignore path
+steps:
+  - method: starknet_blockNumber
+    ignore: ["result"]
+"#,
+    )
+    .unwrap();
+    let network = MockNetwork::start().await;
+    network.on_result("starknet_blockNumber", json!(10));
+
+    let failures = run_scenario(&network.transports(), &scenario).await;
+    assert_eq!(failures.len(), network.servers.len(), "{failures:?}");
+    for failure in &failures {
+        assert_eq!(failure.case, "bad ignore path: step 1");
+        assert!(failure.message.contains("must start with '$'"), "{failure}");
+    }
+}
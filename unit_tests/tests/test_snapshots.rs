@@ -0,0 +1,56 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::collections::HashMap;
+
+use serde_json::json;
+use starknet_providers::jsonrpc::JsonRpcMethod;
+use unit_tests::snapshot::{canonicalize, check_snapshot, first_difference};
+
+///
+/// Golden snapshot tests against the node under test only.
+///
+/// Responses on historical blocks are compared with the golden files under
+/// `unit_tests/snapshots/mainnet/<method>/`. A missing snapshot fails the test, and
+/// `DITTO_UPDATE_SNAPSHOTS=1` writes or rewrites them after a reviewed change.
+///
+
+const NETWORK: &str = "mainnet";
+
+///
+/// Unit test for snapshot canonicalization
+///
+/// purpose: compare responses which only differ in hex formatting.
+/// success case: both canonicalize to the same value, and a real difference is located.
+///
+#[rstest]
+fn work_canonicalize() {
+    let padded = json!({ "block_hash": "0x00AB", "transactions": ["0x0", "0x000"] });
+    let compact = json!({ "block_hash": "0xab", "transactions": ["0x0", "0x0"] });
+    assert_eq!(canonicalize(&padded), canonicalize(&compact));
+
+    let changed = json!({ "block_hash": "0xab", "transactions": ["0x0", "0x1"] });
+    assert_eq!(
+        first_difference(&canonicalize(&compact), &canonicalize(&changed), "$"),
+        Some(String::from("$.transactions[1]"))
+    );
+}
+
+///
+/// Unit test for golden snapshots
+///
+/// purpose: lock in the error returned for a block which does not exist.
+/// success case: the error matches its snapshot.
+///
+#[rstest]
+#[tokio::test]
+async fn fail_snapshot_block_not_found(transports: HashMap<String, SuiteTransport>) {
+    let deoxys = &transports[mainnet::network::DEOXYS];
+    let params = json!({ "block_id": { "block_number": u64::MAX } });
+
+    check_snapshot(NETWORK, deoxys, JsonRpcMethod::GetBlockWithTxHashes, params)
+        .await
+        .expect("Snapshot mismatch");
+}
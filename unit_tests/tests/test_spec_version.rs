@@ -5,7 +5,7 @@ use std::collections::HashMap;
 
 use colored::*;
 use common::*;
-use starknet_providers::{jsonrpc::HttpTransport, JsonRpcClient, Provider};
+use starknet_providers::{JsonRpcClient, Provider};
 
 ///
 /// Unit test for `starknet_specversion`
@@ -15,7 +15,7 @@ use starknet_providers::{jsonrpc::HttpTransport, JsonRpcClient, Provider};
 ///
 #[rstest]
 #[tokio::test]
-async fn test_spec_version_7_1(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn test_spec_version_7_1(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
     let juno = &clients[mainnet::network::JUNO];
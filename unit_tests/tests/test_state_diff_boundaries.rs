@@ -0,0 +1,174 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+use starknet_core::types::FieldElement;
+use starknet_providers::JsonRpcClient;
+use unit_tests::mock::{MockResponse, MockServer};
+use unit_tests::verify::boundary::{verify_boundaries, Boundary};
+
+///
+/// Test for block boundaries of `starknet_getClassHashAt`, `starknet_getClass` and
+/// `starknet_getNonce`.
+///
+/// Boundaries are generated from the state diffs served by Pathfinder:
+/// - a contract deployed at block N must return `ContractNotFound` at N-1 and its class hash at N.
+/// - a class declared at block N must return `ClassHashNotFound` at N-1.
+/// - a nonce updated at block N must be lower at N-1 and equal to the new nonce at N. If the
+///   contract is deployed at N too, N-1 must return `ContractNotFound` instead.
+///
+
+///
+/// Unit test for state diff boundaries
+///
+/// purpose: check boundaries of the first blocks, which only contain deployments.
+/// success case: every node observes each deployment exactly at its block.
+///
+#[rstest]
+#[tokio::test]
+async fn work_boundaries_genesis(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
+    let pathfinder = &clients[mainnet::network::PATHFINDER];
+
+    let mismatches = verify_boundaries(pathfinder, &clients, 0..=5)
+        .await
+        .expect("Error while generating state diff boundaries");
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+    assert!(mismatches.is_empty(), "State diff boundaries do not match");
+}
+
+///
+/// Unit test for state diff boundaries
+///
+/// purpose: check boundaries from v0.11.0 onward, which introduced Sierra class declarations.
+/// success case: every node observes each declaration, deployment and nonce update at its block.
+///
+#[rstest]
+#[tokio::test]
+async fn work_boundaries_v0_11_0(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
+    let pathfinder = &clients[mainnet::network::PATHFINDER];
+    let block_number = mainnet::block::BLOCK_0_11_0;
+
+    let mismatches = verify_boundaries(pathfinder, &clients, block_number..=block_number + 5)
+        .await
+        .expect("Error while generating state diff boundaries");
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+    assert!(mismatches.is_empty(), "State diff boundaries do not match");
+}
+
+///
+/// Unit test for state diff boundaries
+///
+/// purpose: check boundaries on v0.13.1 blocks.
+/// success case: every node observes each declaration, deployment and nonce update at its block.
+///
+#[rstest]
+#[tokio::test]
+async fn work_boundaries_v0_13_1(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
+    let pathfinder = &clients[mainnet::network::PATHFINDER];
+    let block_number = mainnet::block::BLOCK_0_13_1;
+
+    let mismatches = verify_boundaries(pathfinder, &clients, block_number..=block_number + 2)
+        .await
+        .expect("Error while generating state diff boundaries");
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+    assert!(mismatches.is_empty(), "State diff boundaries do not match");
+}
+
+///
+/// Answers `starknet_getNonce` with `before` at block 9 and with nonce 1 at block 10.
+///
+async fn nonce_server(before: MockResponse) -> MockServer {
+    let server = MockServer::start().await;
+    server.on("starknet_getNonce", move |params: &Value| {
+        match params["block_id"]["block_number"].as_u64() {
+            Some(9) => before.clone(),
+            _ => MockResponse::Result(json!("0x1")),
+        }
+    });
+    server
+}
+
+fn nonce_boundary(deployed: bool) -> Boundary {
+    Boundary::Nonce {
+        address: FieldElement::from_hex_be("0x123").unwrap(),
+        nonce: FieldElement::ONE,
+        deployed,
+    }
+}
+
+///
+/// Unit test for state diff boundaries
+///
+/// purpose: check the nonce of a contract deployed in the same block as its first transaction.
+/// success case: `ContractNotFound` before the block is accepted.
+///
+#[rstest]
+#[tokio::test]
+async fn work_nonce_boundary_deployed() {
+    let server = nonce_server(MockResponse::Error {
+        code: 20,
+        message: String::from("Contract not found"),
+        data: None,
+    })
+    .await;
+
+    let mismatches = nonce_boundary(true)
+        .check("mock", &server.client(), 10)
+        .await;
+    assert!(mismatches.is_empty(), "{mismatches:?}");
+}
+
+///
+/// Unit test for state diff boundaries
+///
+/// purpose: check nonce boundaries against a node failing or not changing the nonce before
+/// the block.
+/// success case: an error for an existing contract, an unchanged nonce and a nonce for a
+/// contract deployed in the block are all reported.
+///
+#[rstest]
+#[tokio::test]
+async fn fail_nonce_boundary() {
+    let cases = [
+        (
+            false,
+            MockResponse::Error {
+                code: 20,
+                message: String::from("Contract not found"),
+                data: None,
+            },
+        ),
+        (
+            false,
+            MockResponse::Error {
+                code: 24,
+                message: String::from("Block not found"),
+                data: None,
+            },
+        ),
+        (false, MockResponse::Result(json!("0x1"))),
+        (true, MockResponse::Result(json!("0x0"))),
+    ];
+
+    for (deployed, before) in cases {
+        let server = nonce_server(before.clone()).await;
+        let mismatches = nonce_boundary(deployed)
+            .check("mock", &server.client(), 10)
+            .await;
+        assert_eq!(mismatches.len(), 1, "{before:?}: {mismatches:?}");
+        assert_eq!(mismatches[0].block_number, 9);
+    }
+}
@@ -0,0 +1,84 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::collections::HashMap;
+
+use starknet_core::types::FieldElement;
+use starknet_providers::JsonRpcClient;
+use unit_tests::verify::state::{touched_contracts, verify_state_reads};
+
+///
+/// Test for the consistency of `starknet_getStorageAt`, `starknet_getNonce` and
+/// `starknet_getClassHashAt` with `starknet_getStateUpdate`.
+///
+/// State diffs are fetched from Pathfinder and replayed locally over a range of blocks. The
+/// storage, nonces and class hashes rebuilt this way are then read back from every node at each
+/// block of the range.
+///
+
+///
+/// Unit test for state diff replay
+///
+/// purpose: replay the first blocks of the chain, where the whole state is known, for every
+/// contract their state diffs touch.
+/// success case: reads are compared, and every node reads back the replayed state.
+///
+#[rstest]
+#[tokio::test]
+async fn work_replay_from_genesis(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
+    let pathfinder = &clients[mainnet::network::PATHFINDER];
+
+    let contracts = touched_contracts(pathfinder, 0..=10)
+        .await
+        .expect("Error while reading the genesis state diffs");
+    assert!(
+        !contracts.is_empty(),
+        "Genesis state diffs touch no contract"
+    );
+
+    let reads = verify_state_reads(pathfinder, &clients, &contracts, 0..=10)
+        .await
+        .expect("Error while replaying state diffs");
+    assert!(reads.compared > 0, "No state read was compared");
+    let mismatches = reads.mismatches;
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+    assert!(
+        mismatches.is_empty(),
+        "State reads do not match state diffs"
+    );
+}
+
+///
+/// Unit test for state diff replay
+///
+/// purpose: replay a range of blocks touching the StarkGate ETH contract.
+/// success case: every node reads back the storage written in that range.
+///
+#[rstest]
+#[tokio::test]
+async fn work_replay_erc20_transfers(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
+    let pathfinder = &clients[mainnet::network::PATHFINDER];
+
+    let contracts = [
+        FieldElement::from_hex_be(mainnet::contract::STARKGATE_ETHER).unwrap(),
+        FieldElement::from_hex_be(mainnet::contract::STARKGATE_USDC).unwrap(),
+    ];
+
+    let mismatches = verify_state_reads(pathfinder, &clients, &contracts, 100000..=100005)
+        .await
+        .expect("Error while replaying state diffs")
+        .mismatches;
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+    assert!(
+        mismatches.is_empty(),
+        "State reads do not match state diffs"
+    );
+}
@@ -3,7 +3,7 @@ use common::*;
 
 use colored::*;
 use starknet_core::types::SyncStatusType;
-use starknet_providers::{jsonrpc::HttpTransport, JsonRpcClient, Provider};
+use starknet_providers::{JsonRpcClient, Provider};
 use std::collections::HashMap;
 
 ///
@@ -14,7 +14,7 @@ use std::collections::HashMap;
 ///
 #[rstest]
 #[tokio::test]
-async fn syncing(clients: HashMap<String, JsonRpcClient<HttpTransport>>) {
+async fn syncing(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
     let deoxys = &clients[mainnet::network::DEOXYS];
     let pathfinder = &clients[mainnet::network::PATHFINDER];
     let node_c = &clients[mainnet::network::JUNO];
@@ -9,14 +9,11 @@ use std::sync::Arc;
 use tokio::task::JoinSet;
 
 use starknet_core::types::{BlockId, BlockTag, FieldElement, StarknetError};
-use starknet_providers::{
-    jsonrpc::{HttpTransport, JsonRpcClient},
-    Provider,
-};
+use starknet_providers::{JsonRpcClient, Provider};
 
 #[rstest]
 #[tokio::test]
-async fn fail_non_existing_block(deoxys: JsonRpcClient<HttpTransport>) {
+async fn fail_non_existing_block(deoxys: JsonRpcClient<SuiteTransport>) {
     let response_deoxys = deoxys
         .trace_block_transactions(BlockId::Hash(FieldElement::ZERO))
         .await;
@@ -39,8 +36,8 @@ async fn fail_non_existing_block(deoxys: JsonRpcClient<HttpTransport>) {
 #[rstest]
 #[tokio::test]
 async fn works_ok_for_block_10000(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     let block_number = BlockId::Number(10000);
 
@@ -53,8 +50,8 @@ async fn works_ok_for_block_10000(
 #[rstest]
 #[tokio::test]
 async fn works_ok_for_block_300000(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     let block_number = BlockId::Number(300000);
 
@@ -67,8 +64,8 @@ async fn works_ok_for_block_300000(
 #[rstest]
 #[tokio::test]
 async fn works_ok_for_random_block(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     let mut rng = rand::thread_rng();
     let random_block_number = rng.gen_range(100000..650000);
@@ -89,8 +86,8 @@ async fn works_ok_for_random_block(
 #[tokio::test]
 #[ignore = "Slash this ignore when Deoxys node is fully synced, but it may not works at all bc of computation"]
 async fn works_ok_for_pending_block(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     let mut set = JoinSet::new();
     let arc_deoxys = Arc::new(deoxys);
@@ -10,14 +10,11 @@ use starknet_core::types::{
     BlockId, BlockTag, FieldElement, NoTraceAvailableErrorData, SequencerTransactionStatus,
     StarknetError,
 };
-use starknet_providers::{
-    jsonrpc::{HttpTransport, JsonRpcClient},
-    Provider,
-};
+use starknet_providers::{JsonRpcClient, Provider};
 
 #[rstest]
 #[tokio::test]
-async fn fail_non_existing_hash(deoxys: JsonRpcClient<HttpTransport>) {
+async fn fail_non_existing_hash(deoxys: JsonRpcClient<SuiteTransport>) {
     let transaction_hash = FieldElement::from_hex_be(
         "0x04456c75586c033f4c8f6731a87d10ff5779e40c351e9c8378590ae2a3f823d1",
     )
@@ -44,7 +41,7 @@ async fn fail_non_existing_hash(deoxys: JsonRpcClient<HttpTransport>) {
 /// Starknet-spec : Extra information on why trace is not available. Either it wasn't executed yet (RECEIVED), or the transaction failed (REJECTED.
 #[rstest]
 #[tokio::test]
-async fn fail_no_trace_available(deoxys: JsonRpcClient<HttpTransport>) {
+async fn fail_no_trace_available(deoxys: JsonRpcClient<SuiteTransport>) {
     let transaction_hash = FieldElement::from_hex_be(
         "0x2062dc37facfcc3bed03163dbbde0e3874bf8b231628c6aa21ac2d094b94372",
     )
@@ -75,8 +72,8 @@ async fn fail_no_trace_available(deoxys: JsonRpcClient<HttpTransport>) {
 #[rstest]
 #[tokio::test]
 async fn work_trace_transaction(
-    deoxys: JsonRpcClient<HttpTransport>,
-    pathfinder: JsonRpcClient<HttpTransport>,
+    deoxys: JsonRpcClient<SuiteTransport>,
+    pathfinder: JsonRpcClient<SuiteTransport>,
 ) {
     let transaction_hash = FieldElement::from_hex_be(
         "0x04456c75586c033f4c8f6731a87d10ff5779e40c351e9c8378590ae2a3f823da",
@@ -0,0 +1,199 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::collections::HashMap;
+
+use starknet_accounts::ExecutionEncoding;
+use starknet_core::chain_id;
+use starknet_core::types::{BlockId, BroadcastedInvokeTransaction, FieldElement};
+use starknet_providers::{jsonrpc::JsonRpcClient, Provider};
+use starknet_signers::SigningKey;
+use unit_tests::builder::{
+    encode_calls, Mutation, NetworkProfile, TestAccount, TransactionBuilder, TransactionVersion,
+};
+use unit_tests::generate_call;
+use unit_tests::hash::transaction::{invoke_v1_hash, invoke_v3_hash};
+
+///
+/// Tests for the transaction builder, which builds the valid and invalid transactions of the
+/// fee estimation, simulation and write tests.
+///
+
+const PRIVATE_KEY: &str = "0x1";
+
+fn builder() -> TransactionBuilder {
+    TransactionBuilder::new(
+        NetworkProfile::mainnet(),
+        TestAccount::new(mainnet::contract::CONTRACT_ACCOUNT).signing_key(PRIVATE_KEY),
+    )
+    .calls(vec![generate_call(
+        mainnet::contract::CONTRACT_ERC20,
+        "transfer",
+        vec![1, 81],
+    )])
+}
+
+///
+/// Returns whether the signature of an invoke transaction is valid on the network identified
+/// by `chain_id`.
+///
+fn verifies(tx: &BroadcastedInvokeTransaction, chain_id: FieldElement) -> bool {
+    let (hash, signature) = match tx {
+        BroadcastedInvokeTransaction::V1(tx) => (invoke_v1_hash(tx, chain_id), &tx.signature),
+        BroadcastedInvokeTransaction::V3(tx) => (invoke_v3_hash(tx, chain_id), &tx.signature),
+    };
+    let public_key = SigningKey::from_secret_scalar(FieldElement::ONE)
+        .verifying_key()
+        .scalar();
+
+    starknet_crypto::verify(&public_key, &hash, &signature[0], &signature[1]).unwrap_or(false)
+}
+
+///
+/// Unit test for call encoding
+///
+/// purpose: encode two calls for the current and the legacy account encodings.
+/// success case: the calldata follows each `__execute__` layout.
+///
+#[rstest]
+fn work_encode_calls() {
+    let first = generate_call("0xa", "transfer", vec![1, 2]);
+    let second = generate_call("0xb", "approve", vec![3]);
+    let calls = vec![first.clone(), second.clone()];
+
+    let felt = FieldElement::from;
+    assert_eq!(
+        encode_calls(&calls, ExecutionEncoding::New),
+        vec![
+            felt(2u8),
+            first.to,
+            first.selector,
+            felt(2u8),
+            felt(1u8),
+            felt(2u8),
+            second.to,
+            second.selector,
+            felt(1u8),
+            felt(3u8),
+        ]
+    );
+    assert_eq!(
+        encode_calls(&calls, ExecutionEncoding::Legacy),
+        vec![
+            felt(2u8),
+            first.to,
+            first.selector,
+            felt(0u8),
+            felt(2u8),
+            second.to,
+            second.selector,
+            felt(2u8),
+            felt(1u8),
+            felt(3u8),
+            felt(1u8),
+            felt(2u8),
+            felt(3u8),
+        ]
+    );
+}
+
+///
+/// Unit test for the transaction builder
+///
+/// purpose: build signed v1 and v3 invokes.
+/// success case: both signatures are valid for the network of the profile.
+///
+#[rstest]
+fn work_signed_invoke() {
+    for version in [TransactionVersion::V1, TransactionVersion::V3] {
+        let tx = builder().version(version).build_invoke();
+        assert!(verifies(&tx, chain_id::MAINNET), "{version:?}");
+        assert!(!verifies(&tx, chain_id::SEPOLIA), "{version:?}");
+    }
+}
+
+///
+/// Unit test for the transaction builder
+///
+/// purpose: apply every mutation to a signed invoke.
+/// success case: each mutation changes only what it targets.
+///
+#[rstest]
+fn work_mutations() {
+    let tx = builder().mutate(Mutation::BadSignature).build_invoke();
+    assert!(!verifies(&tx, chain_id::MAINNET));
+
+    let tx = builder().mutate(Mutation::WrongChainId).build_invoke();
+    assert!(!verifies(&tx, chain_id::MAINNET));
+
+    let tx = builder()
+        .nonce(FieldElement::TWO)
+        .mutate(Mutation::WrongNonce)
+        .build_invoke();
+    let BroadcastedInvokeTransaction::V1(v1) = &tx else {
+        panic!("Expected a v1 invoke");
+    };
+    assert_eq!(v1.nonce, FieldElement::ONE);
+    assert!(verifies(&tx, chain_id::MAINNET));
+
+    let tx = builder()
+        .version(TransactionVersion::V3)
+        .mutate(Mutation::OversizedFee)
+        .build_invoke();
+    let BroadcastedInvokeTransaction::V3(v3) = &tx else {
+        panic!("Expected a v3 invoke");
+    };
+    assert_eq!(v3.resource_bounds.l1_gas.max_amount, u64::MAX);
+    assert_eq!(
+        v3.resource_bounds.l1_gas.max_price_per_unit,
+        u128::from(u64::MAX)
+    );
+    assert!(verifies(&tx, chain_id::MAINNET));
+
+    let tx = builder().mutate(Mutation::OversizedFee).build_invoke();
+    let BroadcastedInvokeTransaction::V1(v1) = &tx else {
+        panic!("Expected a v1 invoke");
+    };
+    assert_eq!(v1.max_fee, FieldElement::from(u128::MAX));
+}
+
+///
+/// Unit test for `starknet_estimateFee` with an invalid signature
+///
+/// purpose: estimate the fee of a transfer whose signature does not match, with validation.
+/// success case: every node fails the same way.
+///
+#[rstest]
+#[tokio::test]
+async fn fail_estimate_fee_bad_signature(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
+    let deoxys = &clients[mainnet::network::DEOXYS];
+    let pathfinder = &clients[mainnet::network::PATHFINDER];
+
+    let block_id = BlockId::Number(mainnet::block::BLOCK_0_13_1);
+    let nonce = deoxys
+        .get_nonce(block_id, TransactionBuilder::transfer().address())
+        .await
+        .expect("Error while getting the nonce of the account");
+    let transaction = TransactionBuilder::transfer()
+        .nonce(nonce)
+        .mutate(Mutation::BadSignature)
+        .build();
+
+    let response_deoxys = deoxys
+        .estimate_fee(vec![transaction.clone()], vec![], block_id)
+        .await;
+    let response_pathfinder = pathfinder
+        .estimate_fee(vec![transaction], vec![], block_id)
+        .await;
+
+    assert!(
+        response_deoxys.is_err(),
+        "Expected an error, but got a result"
+    );
+    assert_eq!(
+        format!("{:?}", response_deoxys.unwrap_err()),
+        format!("{:?}", response_pathfinder.unwrap_err())
+    );
+}
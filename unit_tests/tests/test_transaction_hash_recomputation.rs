@@ -0,0 +1,88 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::collections::HashMap;
+
+use starknet_providers::JsonRpcClient;
+use unit_tests::verify::transaction_hash::verify_transaction_hashes;
+
+///
+/// Test for the transaction hashes returned by `starknet_getBlockWithTxs`.
+///
+/// Each transaction hash is recomputed locally from the fields served by every node and the
+/// chain id it reports, covering INVOKE v0/v1/v3, DECLARE v0 to v3, DEPLOY_ACCOUNT v1/v3,
+/// DEPLOY and L1_HANDLER transactions.
+///
+
+///
+/// Unit test for local transaction hash recomputation
+///
+/// purpose: recompute the hashes of the first transactions of the chain.
+/// success case: every recomputed hash matches.
+///
+#[rstest]
+#[tokio::test]
+async fn work_transaction_hash_early_blocks(
+    clients: HashMap<String, JsonRpcClient<SuiteTransport>>,
+) {
+    let mismatches = verify_transaction_hashes(&clients, 0..=10).await;
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+    assert!(mismatches.is_empty(), "Transaction hashes do not match");
+}
+
+///
+/// Unit test for local transaction hash recomputation
+///
+/// purpose: recompute the hashes of transactions around every Starknet version upgrade.
+/// success case: every recomputed hash matches.
+///
+#[rstest]
+#[tokio::test]
+async fn work_transaction_hash_across_versions(
+    clients: HashMap<String, JsonRpcClient<SuiteTransport>>,
+) {
+    let upgrades = [
+        mainnet::block::BLOCK_0_7_0,
+        mainnet::block::BLOCK_0_9_1,
+        mainnet::block::BLOCK_0_10_0,
+        mainnet::block::BLOCK_0_11_0,
+        mainnet::block::BLOCK_0_11_1,
+        mainnet::block::BLOCK_0_12_0,
+        mainnet::block::BLOCK_0_13_0,
+        mainnet::block::BLOCK_0_13_1,
+    ];
+
+    let mut mismatches = vec![];
+    for block_number in upgrades {
+        mismatches
+            .extend(verify_transaction_hashes(&clients, block_number..=block_number + 1).await);
+    }
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+    assert!(mismatches.is_empty(), "Transaction hashes do not match");
+}
+
+///
+/// Unit test for local transaction hash recomputation
+///
+/// purpose: recompute the hashes of recent blocks, which include v3 transactions.
+/// success case: every recomputed hash matches.
+///
+#[rstest]
+#[tokio::test]
+async fn work_transaction_hash_v3(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
+    let block_number = mainnet::block::BLOCK_0_13_1 + 10000;
+    let mismatches = verify_transaction_hashes(&clients, block_number..=block_number + 2).await;
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+    assert!(mismatches.is_empty(), "Transaction hashes do not match");
+}
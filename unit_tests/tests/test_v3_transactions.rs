@@ -0,0 +1,290 @@
+#![feature(assert_matches)]
+
+mod common;
+use common::*;
+
+use std::collections::HashMap;
+
+use starknet_core::chain_id;
+use starknet_core::types::{
+    BlockId, BroadcastedInvokeTransactionV3, BroadcastedTransaction, DataAvailabilityMode,
+    FieldElement, InvokeTransaction, InvokeTransactionV3, SimulationFlagForEstimateFee,
+    Transaction,
+};
+use starknet_core::utils::get_contract_address;
+use starknet_providers::{jsonrpc::JsonRpcClient, Provider};
+use starknet_signers::SigningKey;
+use unit_tests::account::{AccountKind, CounterfactualAccount};
+use unit_tests::builder::{Mutation, NetworkProfile, TransactionBuilder, TransactionVersion};
+use unit_tests::hash::transaction::{invoke_v3_hash, transaction_hashes};
+use unit_tests::mock::MockServer;
+use unit_tests::v3::{deploy_account_v3, deployed_address, invoke_v3, sign_invoke_v3, V3Fees};
+use unit_tests::{build_single_owner_account, generate_call, PrepareInvoke};
+
+///
+/// Tests for the construction of v3 transactions, which carry resource bounds, a tip,
+/// paymaster data and data availability modes instead of a max fee.
+///
+
+fn constructor_calldata() -> Vec<FieldElement> {
+    vec![SigningKey::from_secret_scalar(FieldElement::ONE)
+        .verifying_key()
+        .scalar()]
+}
+
+fn sequenced(tx: &BroadcastedInvokeTransactionV3) -> Transaction {
+    Transaction::Invoke(InvokeTransaction::V3(InvokeTransactionV3 {
+        transaction_hash: FieldElement::ZERO,
+        sender_address: tx.sender_address,
+        calldata: tx.calldata.clone(),
+        signature: tx.signature.clone(),
+        nonce: tx.nonce,
+        resource_bounds: tx.resource_bounds.clone(),
+        tip: tx.tip,
+        paymaster_data: tx.paymaster_data.clone(),
+        account_deployment_data: tx.account_deployment_data.clone(),
+        nonce_data_availability_mode: tx.nonce_data_availability_mode,
+        fee_data_availability_mode: tx.fee_data_availability_mode,
+    }))
+}
+
+///
+/// Unit test for v3 transaction construction
+///
+/// purpose: build an invoke v3 with every fee field set, and sign it.
+/// success case: the fields are kept, the hash matches the hash of the sequenced transaction
+/// and the signature verifies against it.
+///
+#[rstest]
+fn work_invoke_v3_signature() {
+    let signing_key = SigningKey::from_secret_scalar(FieldElement::ONE);
+    let fees = V3Fees::l1_gas(10_000, 100_000_000_000)
+        .tip(5)
+        .paymaster_data(vec![FieldElement::TWO])
+        .data_availability_modes(DataAvailabilityMode::L2, DataAvailabilityMode::L1);
+
+    let mut tx = invoke_v3(
+        FieldElement::from_hex_be(mainnet::contract::CONTRACT_ACCOUNT).unwrap(),
+        vec![FieldElement::ONE],
+        FieldElement::THREE,
+        &fees,
+        false,
+    );
+    assert_eq!(tx.tip, 5);
+    assert_eq!(tx.paymaster_data, vec![FieldElement::TWO]);
+    assert_eq!(tx.nonce_data_availability_mode, DataAvailabilityMode::L2);
+    assert_eq!(tx.resource_bounds.l1_gas.max_amount, 10_000);
+
+    sign_invoke_v3(&mut tx, &signing_key, chain_id::MAINNET);
+    let hash = invoke_v3_hash(&tx, chain_id::MAINNET);
+    assert_eq!(
+        transaction_hashes(&sequenced(&tx), chain_id::MAINNET),
+        vec![hash]
+    );
+
+    let public_key = signing_key.verifying_key().scalar();
+    assert!(
+        starknet_crypto::verify(&public_key, &hash, &tx.signature[0], &tx.signature[1]).unwrap()
+    );
+
+    tx.is_query = true;
+    assert_ne!(invoke_v3_hash(&tx, chain_id::MAINNET), hash);
+}
+
+///
+/// Unit test for v3 invokes prepared by an account
+///
+/// purpose: prepare an invoke v3 bounded by L1 gas through a single owner account.
+/// success case: the transaction is the one built and signed by `invoke_v3`.
+///
+#[rstest]
+#[tokio::test]
+async fn work_prepare_invoke_v3() {
+    let server = MockServer::start().await;
+    let client = server.client();
+    let account =
+        build_single_owner_account(&client, "0x1", mainnet::contract::CONTRACT_ACCOUNT, false);
+    let fees = V3Fees::l1_gas(10_000, 100_000_000_000);
+
+    let prepared = account
+        .prepare_invoke_v3(
+            vec![generate_call(
+                mainnet::contract::STARKGATE_ETHER,
+                "transfer",
+                vec![1, 1, 0],
+            )],
+            FieldElement::THREE,
+            &fees,
+            false,
+        )
+        .await
+        .expect("Error while preparing the transaction");
+
+    let mut expected = invoke_v3(
+        FieldElement::from_hex_be(mainnet::contract::CONTRACT_ACCOUNT).unwrap(),
+        prepared.calldata.clone(),
+        FieldElement::THREE,
+        &fees,
+        false,
+    );
+    sign_invoke_v3(
+        &mut expected,
+        &SigningKey::from_secret_scalar(FieldElement::ONE),
+        chain_id::SEPOLIA,
+    );
+    assert_eq!(prepared, expected);
+}
+
+///
+/// Unit test for v3 invokes prepared by an account
+///
+/// purpose: prepare invokes v3 with fee fields the account does not sign.
+/// success case: each of them is rejected instead of being dropped.
+///
+#[rstest]
+#[tokio::test]
+async fn fail_prepare_invoke_v3_fees() {
+    let server = MockServer::start().await;
+    let client = server.client();
+    let account =
+        build_single_owner_account(&client, "0x1", mainnet::contract::CONTRACT_ACCOUNT, false);
+
+    let mut l2_gas = V3Fees::l1_gas(10_000, 1);
+    l2_gas.resource_bounds.l2_gas.max_amount = 1;
+    for fees in [
+        V3Fees::l1_gas(10_000, 1).tip(1),
+        V3Fees::l1_gas(10_000, 1).paymaster_data(vec![FieldElement::ONE]),
+        V3Fees::l1_gas(10_000, 1)
+            .data_availability_modes(DataAvailabilityMode::L2, DataAvailabilityMode::L1),
+        l2_gas,
+    ] {
+        let prepared = account
+            .prepare_invoke_v3(vec![], FieldElement::ZERO, &fees, false)
+            .await;
+        assert!(prepared.is_err(), "{fees:?} was not rejected");
+    }
+}
+
+///
+/// Unit test for v3 transaction construction
+///
+/// purpose: build a deploy account v3 for the OpenZeppelin account class.
+/// success case: the transaction deploys to the counterfactual address of its class and salt.
+///
+#[rstest]
+fn work_deploy_account_v3_address() {
+    let class_hash = FieldElement::from_hex_be(mainnet::contract::CLASS_OZ_ACCOUNT).unwrap();
+    let constructor_calldata = constructor_calldata();
+
+    let tx = deploy_account_v3(
+        class_hash,
+        FieldElement::ONE,
+        constructor_calldata.clone(),
+        FieldElement::ZERO,
+        &V3Fees::default(),
+        true,
+    );
+
+    assert_eq!(
+        deployed_address(&tx),
+        get_contract_address(
+            FieldElement::ONE,
+            class_hash,
+            &constructor_calldata,
+            FieldElement::ZERO
+        )
+    );
+}
+
+///
+/// Unit test for `starknet_estimateFee` with v3 transactions
+///
+/// purpose: estimate the fee of v3 invoke and deploy account transactions.
+/// success case: every node gives the same estimate.
+///
+#[rstest]
+#[tokio::test]
+async fn work_estimate_fee_v3(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
+    let deoxys = &clients[mainnet::network::DEOXYS];
+    let pathfinder = &clients[mainnet::network::PATHFINDER];
+
+    let block_id = BlockId::Number(mainnet::block::BLOCK_0_13_1);
+    let nonce = deoxys
+        .get_nonce(block_id, TransactionBuilder::transfer().address())
+        .await
+        .expect("Error while getting the nonce of the account");
+
+    let account = CounterfactualAccount::new(AccountKind::OpenZeppelin, "0x1", FieldElement::ONE);
+    let transactions: Vec<BroadcastedTransaction> = vec![
+        TransactionBuilder::transfer()
+            .version(TransactionVersion::V3)
+            .nonce(nonce)
+            .build(),
+        BroadcastedTransaction::DeployAccount(
+            account.deploy_transaction(
+                &account
+                    .transaction_builder(NetworkProfile::mainnet())
+                    .version(TransactionVersion::V3),
+            ),
+        ),
+    ];
+
+    let response_deoxys = deoxys
+        .estimate_fee(
+            &transactions,
+            vec![SimulationFlagForEstimateFee::SkipValidate],
+            block_id,
+        )
+        .await
+        .expect("Deoxys : Error while estimating the fee of v3 transactions");
+    let response_pathfinder = pathfinder
+        .estimate_fee(
+            &transactions,
+            vec![SimulationFlagForEstimateFee::SkipValidate],
+            block_id,
+        )
+        .await
+        .expect("RPC : Error while estimating the fee of v3 transactions");
+
+    assert_eq!(response_deoxys, response_pathfinder);
+}
+
+///
+/// Unit test for `starknet_estimateFee` with v3 transactions
+///
+/// purpose: estimate the fee of a v3 invoke with resource bounds above the account balance.
+/// success case: every node fails the same way.
+///
+#[rstest]
+#[tokio::test]
+async fn fail_estimate_fee_v3_max_bounds(clients: HashMap<String, JsonRpcClient<SuiteTransport>>) {
+    let deoxys = &clients[mainnet::network::DEOXYS];
+    let pathfinder = &clients[mainnet::network::PATHFINDER];
+
+    let block_id = BlockId::Number(mainnet::block::BLOCK_0_13_1);
+    let nonce = deoxys
+        .get_nonce(block_id, TransactionBuilder::transfer().address())
+        .await
+        .expect("Error while getting the nonce of the account");
+    let transaction = TransactionBuilder::transfer()
+        .version(TransactionVersion::V3)
+        .nonce(nonce)
+        .mutate(Mutation::OversizedFee)
+        .build();
+
+    let response_deoxys = deoxys
+        .estimate_fee(vec![transaction.clone()], vec![], block_id)
+        .await;
+    let response_pathfinder = pathfinder
+        .estimate_fee(vec![transaction], vec![], block_id)
+        .await;
+
+    assert!(
+        response_deoxys.is_err(),
+        "Expected an error, but got a result"
+    );
+    assert_eq!(
+        format!("{:?}", response_deoxys.unwrap_err()),
+        format!("{:?}", response_pathfinder.unwrap_err())
+    );
+}